@@ -0,0 +1,5 @@
+mod payment_policy;
+pub use payment_policy::*;
+
+mod paying_client;
+pub use paying_client::*;
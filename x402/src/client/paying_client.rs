@@ -0,0 +1,229 @@
+use reqwest::{
+    Client, IntoUrl, Request, RequestBuilder, Response, StatusCode, header::HeaderValue,
+};
+use solana_signer::Signer;
+
+use crate::{
+    ExactSvmTransactionBuilder, PaymentPolicy, PaymentRequirements, PaymentRequirementsResponse,
+    SolanaRpc, X_PAYMENT_HEADER_KEY, X_PAYMENT_RESPONSE_HEADER_KEY, X402Error, X402Result,
+    XPaymentResponse, associated_token_address, parse_solana_pubkey, token_program_id,
+};
+
+/// A reqwest client that pays for resources answered with `402 Payment Required`.
+///
+/// The [PaymentRequirementsResponse] of the 402 response is parsed, an entry of its
+/// `accepts` is selected by the [PaymentPolicy], the payment transaction is built and
+/// signed by `payer` and the request is retried with the `X-PAYMENT` header.
+/// Requests with a streaming body cannot be retried and fail with
+/// [X402Error::PaymentRequestError] when payment is required.
+#[derive(Debug, Clone)]
+pub struct X402PayingClient<R, S> {
+    http: Client,
+    rpc: R,
+    payer: S,
+    policy: PaymentPolicy,
+}
+
+impl<R: SolanaRpc, S: Signer> X402PayingClient<R, S> {
+    /// The recent blockhash of payment transactions is fetched from `rpc`
+    pub fn new(rpc: R, payer: S, policy: PaymentPolicy) -> Self {
+        Self {
+            http: Client::new(),
+            rpc,
+            payer,
+            policy,
+        }
+    }
+
+    /// Send requests with a configured reqwest [Client]
+    pub fn set_http_client(mut self, http: Client) -> Self {
+        self.http = http;
+
+        self
+    }
+
+    pub fn http(&self) -> &Client {
+        &self.http
+    }
+
+    pub fn rpc(&self) -> &R {
+        &self.rpc
+    }
+
+    pub fn payer(&self) -> &S {
+        &self.payer
+    }
+
+    pub fn policy(&self) -> &PaymentPolicy {
+        &self.policy
+    }
+
+    pub fn policy_mut(&mut self) -> &mut PaymentPolicy {
+        &mut self.policy
+    }
+
+    /// Start a `GET` request, send it with [Self::send]
+    pub fn get(&self, url: impl IntoUrl) -> RequestBuilder {
+        self.http.get(url)
+    }
+
+    /// Start a `POST` request, send it with [Self::send]
+    pub fn post(&self, url: impl IntoUrl) -> RequestBuilder {
+        self.http.post(url)
+    }
+
+    pub async fn send(&self, request: RequestBuilder) -> X402Result<X402PaidResponse> {
+        let request = request
+            .build()
+            .map_err(|_| X402Error::PaymentRequestError)?;
+
+        self.execute(request).await
+    }
+
+    /// Execute `request`, paying for it if the resource server requires a payment
+    pub async fn execute(&self, request: Request) -> X402Result<X402PaidResponse> {
+        let retry = request.try_clone();
+
+        let response = self
+            .http
+            .execute(request)
+            .await
+            .map_err(|_| X402Error::PaymentRequestError)?;
+        if response.status() != StatusCode::PAYMENT_REQUIRED {
+            return Ok(X402PaidResponse {
+                response,
+                payment: Option::None,
+            });
+        }
+
+        let mut retry = retry.ok_or(X402Error::PaymentRequestError)?;
+        let body = response
+            .bytes()
+            .await
+            .map_err(|_| X402Error::PaymentRequestError)?;
+        let payment_required = serde_json::from_slice::<PaymentRequirementsResponse<'_>>(&body)
+            .map_err(|_| X402Error::InvalidPaymentRequirementsResponse)?;
+
+        let requirements = self.policy.select(payment_required.accepts())?;
+        retry.headers_mut().insert(
+            X_PAYMENT_HEADER_KEY,
+            self.payment_header(&payment_required, requirements).await?,
+        );
+
+        let response = self
+            .http
+            .execute(retry)
+            .await
+            .map_err(|_| X402Error::PaymentRequestError)?;
+        if response.status() == StatusCode::PAYMENT_REQUIRED {
+            return Err(rejection_error(response).await);
+        }
+
+        let payment = response
+            .headers()
+            .get(X_PAYMENT_RESPONSE_HEADER_KEY)
+            .map(decode_payment_response)
+            .transpose()?;
+
+        Ok(X402PaidResponse { response, payment })
+    }
+
+    /// The base64 encoded `X-PAYMENT` header paying `requirements`
+    async fn payment_header(
+        &self,
+        payment_required: &PaymentRequirementsResponse<'_>,
+        requirements: &PaymentRequirements<'_>,
+    ) -> X402Result<HeaderValue> {
+        let recent_blockhash = self.rpc.get_latest_blockhash().await?;
+
+        let mut builder = ExactSvmTransactionBuilder::new(requirements);
+        builder.set_recent_blockhash(recent_blockhash);
+
+        let extra = requirements.extra();
+        let mint = parse_solana_pubkey(requirements.asset())?;
+        let destination = associated_token_address(
+            &parse_solana_pubkey(requirements.pay_to())?,
+            &mint,
+            &token_program_id(extra.token_extensions_mint()),
+        );
+
+        // The fee payer pays the rent of the token account of a recipient that has none yet
+        if self.rpc.get_token_account(&destination).await?.is_none() {
+            builder.set_create_destination(true);
+        }
+
+        // Token-2022 mints may withhold a fee the payer covers on top of the amount required
+        if extra.token_extensions_mint() {
+            let mint = self
+                .rpc
+                .get_mint(&mint)
+                .await?
+                .ok_or(X402Error::InvalidExactSvmPayloadTransactionAssetMismatch)?;
+            mint.extensions.ensure_transferable()?;
+
+            if let Some(transfer_fee) = mint.extensions.transfer_fee {
+                let amount = transfer_fee
+                    .calculate_pre_fee_amount(requirements.max_amount_required())
+                    .ok_or(X402Error::InvalidTransferInstruction)?;
+                if !self.policy.accepts_amount(requirements, amount) {
+                    return Err(X402Error::PaymentPolicyAmountExceeded);
+                }
+
+                builder.set_transfer_fee(transfer_fee);
+            }
+        }
+        let payload = builder
+            .build_payload(&self.payer)?
+            .change_x402_version(payment_required.x402_version());
+
+        HeaderValue::try_from(payload.to_header_value()?)
+            .map_err(|_| X402Error::TransactionSerializationError)
+    }
+}
+
+/// The response to a request paid by [X402PayingClient]
+#[derive(Debug)]
+pub struct X402PaidResponse {
+    response: Response,
+    payment: Option<XPaymentResponse<'static>>,
+}
+
+impl X402PaidResponse {
+    pub fn response(&self) -> &Response {
+        &self.response
+    }
+
+    pub fn into_response(self) -> Response {
+        self.response
+    }
+
+    /// The decoded `X-PAYMENT-RESPONSE` header, `None` if no payment was made
+    pub fn payment(&self) -> Option<&XPaymentResponse<'static>> {
+        self.payment.as_ref()
+    }
+
+    pub fn into_parts(self) -> (Response, Option<XPaymentResponse<'static>>) {
+        (self.response, self.payment)
+    }
+}
+
+fn decode_payment_response(header: &HeaderValue) -> X402Result<XPaymentResponse<'static>> {
+    let header = header
+        .to_str()
+        .map_err(|_| X402Error::InvalidPaymentResponseHeader)?;
+    let mut buffer = Vec::new();
+
+    XPaymentResponse::from_header_value(header, &mut buffer).map(XPaymentResponse::into_owned)
+}
+
+/// The error code of a 402 answering a paid request, [X402Error::PaymentRejected] if it has none
+async fn rejection_error(response: Response) -> X402Error {
+    let Ok(body) = response.bytes().await else {
+        return X402Error::PaymentRejected;
+    };
+
+    serde_json::from_slice::<PaymentRequirementsResponse<'_>>(&body)
+        .ok()
+        .and_then(|rejection| X402Error::try_from(rejection.error_reason()).ok())
+        .unwrap_or(X402Error::PaymentRejected)
+}
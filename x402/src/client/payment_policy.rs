@@ -0,0 +1,115 @@
+use crate::{PaymentRequirements, PaymentScheme, X402Error, X402Network, X402Result};
+
+/// Decides which of the payment requirements of a `402 Payment Required` response
+/// a client is willing to pay with the `exact` scheme on Solana.
+/// Only the allowed assets are paid, each up to its own maximum amount, and amounts
+/// of different assets are never compared since their atomic units differ.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct PaymentPolicy {
+    allowed_assets: Vec<PaymentPolicyAsset>,
+}
+
+impl PaymentPolicy {
+    /// A policy paying nothing until assets are allowed with [Self::allow_asset]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pay with the token mint `asset` on `network`, at most `max_amount` atomic units
+    /// of the asset for a single request. Can be called for each allowed asset,
+    /// the assets allowed first are preferred.
+    pub fn allow_asset(
+        &mut self,
+        network: impl Into<X402Network>,
+        asset: &str,
+        max_amount: u64,
+    ) -> &mut Self {
+        self.allowed_assets
+            .push(PaymentPolicyAsset::new(network, asset, max_amount));
+
+        self
+    }
+
+    pub fn allowed_assets(&self) -> &[PaymentPolicyAsset] {
+        self.allowed_assets.as_slice()
+    }
+
+    /// The allowed asset `requirements` are paid with, `None` if the asset is not allowed
+    pub fn allowed_asset(
+        &self,
+        requirements: &PaymentRequirements<'_>,
+    ) -> Option<&PaymentPolicyAsset> {
+        self.allowed_assets
+            .iter()
+            .find(|allowed| allowed.matches(requirements))
+    }
+
+    /// Whether the client is willing to pay `requirements`
+    pub fn accepts(&self, requirements: &PaymentRequirements<'_>) -> bool {
+        requirements.scheme() == PaymentScheme::Exact
+            && requirements.network().is_solana()
+            && self.accepts_amount(requirements, requirements.max_amount_required())
+    }
+
+    /// Whether the client is willing to pay `amount` atomic units of the asset of `requirements`.
+    /// The amount paid exceeds `maxAmountRequired` when the token withholds a transfer fee.
+    pub fn accepts_amount(&self, requirements: &PaymentRequirements<'_>, amount: u64) -> bool {
+        self.allowed_asset(requirements)
+            .is_some_and(|allowed| amount <= allowed.max_amount)
+    }
+
+    /// The cheapest of the `accepts` the policy is willing to pay in the most preferred asset
+    pub fn select<'a, 'x>(
+        &self,
+        accepts: &'a [PaymentRequirements<'x>],
+    ) -> X402Result<&'a PaymentRequirements<'x>> {
+        self.allowed_assets
+            .iter()
+            .find_map(|allowed| {
+                accepts
+                    .iter()
+                    .filter(|requirements| {
+                        allowed.matches(requirements) && self.accepts(requirements)
+                    })
+                    .min_by_key(|requirements| requirements.max_amount_required())
+            })
+            .ok_or(X402Error::NoAcceptablePaymentRequirements)
+    }
+}
+
+/// An asset a [PaymentPolicy] pays with, a token mint on a network
+/// and the most paid with it for a single request
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+pub struct PaymentPolicyAsset {
+    network: X402Network,
+    asset: String,
+    max_amount: u64,
+}
+
+impl PaymentPolicyAsset {
+    pub fn new(network: impl Into<X402Network>, asset: &str, max_amount: u64) -> Self {
+        Self {
+            network: network.into(),
+            asset: asset.to_owned(),
+            max_amount,
+        }
+    }
+
+    pub fn network(&self) -> X402Network {
+        self.network
+    }
+
+    pub fn asset(&self) -> &str {
+        self.asset.as_str()
+    }
+
+    /// Maximum amount in atomic units of the asset
+    pub fn max_amount(&self) -> u64 {
+        self.max_amount
+    }
+
+    /// Whether `requirements` are paid with this asset
+    pub fn matches(&self, requirements: &PaymentRequirements<'_>) -> bool {
+        self.network == requirements.network() && self.asset == requirements.asset()
+    }
+}
@@ -0,0 +1,94 @@
+use std::borrow::Cow;
+
+use crate::{EvmAddress, PaymentRequirements, X402Error, X402Result, keccak256, uint256_word};
+
+/// The EIP-712 type of the domain of tokens implementing EIP-3009
+pub const EIP712_DOMAIN_TYPE: &str =
+    "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+
+/// The EIP-712 domain separating the signatures of a token contract on a chain
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+pub struct Eip712Domain<'x> {
+    name: Cow<'x, str>,
+    version: Cow<'x, str>,
+    chain_id: u64,
+    verifying_contract: EvmAddress,
+}
+
+impl<'x> Eip712Domain<'x> {
+    pub fn new(
+        name: impl Into<Cow<'x, str>>,
+        version: impl Into<Cow<'x, str>>,
+        chain_id: u64,
+        verifying_contract: EvmAddress,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            version: version.into(),
+            chain_id,
+            verifying_contract,
+        }
+    }
+
+    /// The domain of the token `asset` of `requirements` on the EVM chain of its network,
+    /// named by the `name` and `version` of the requirements extras
+    pub fn from_requirements(requirements: &'x PaymentRequirements<'_>) -> X402Result<Self> {
+        let extra = requirements.extra();
+        let network = requirements
+            .network()
+            .evm()
+            .ok_or(X402Error::InvalidNetwork)?;
+
+        Ok(Self {
+            name: extra.name().ok_or(X402Error::Eip712DomainIsMissing)?.into(),
+            version: extra
+                .version()
+                .ok_or(X402Error::Eip712DomainIsMissing)?
+                .into(),
+            chain_id: network.chain_id(),
+            verifying_contract: EvmAddress::parse(requirements.asset())?,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    pub fn version(&self) -> &str {
+        self.version.as_ref()
+    }
+
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    pub fn verifying_contract(&self) -> EvmAddress {
+        self.verifying_contract
+    }
+
+    /// The `hashStruct` of the domain
+    pub fn separator(&self) -> [u8; 32] {
+        keccak256(&[
+            &keccak256(&[EIP712_DOMAIN_TYPE.as_bytes()]),
+            &keccak256(&[self.name.as_bytes()]),
+            &keccak256(&[self.version.as_bytes()]),
+            &uint256_word(self.chain_id.into()),
+            &self.verifying_contract.to_word(),
+        ])
+    }
+
+    /// The digest signed for the message with `struct_hash` in this domain
+    pub fn signing_hash(&self, struct_hash: &[u8; 32]) -> [u8; 32] {
+        keccak256(&[&[0x19, 0x01], &self.separator(), struct_hash])
+    }
+
+    /// Convert into a domain that owns its strings
+    pub fn into_owned(self) -> Eip712Domain<'static> {
+        Eip712Domain {
+            name: Cow::Owned(self.name.into_owned()),
+            version: Cow::Owned(self.version.into_owned()),
+            chain_id: self.chain_id,
+            verifying_contract: self.verifying_contract,
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use core::{fmt, str::FromStr};
+
+use k256::ecdsa::VerifyingKey;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{X402Error, X402Result, decode_prefixed_hex, keccak256};
+
+/// A 20 byte EVM account or contract address.
+/// Displayed with the EIP-55 mixed case checksum.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
+pub struct EvmAddress([u8; 20]);
+
+impl EvmAddress {
+    pub const fn new(bytes: [u8; 20]) -> Self {
+        Self(bytes)
+    }
+
+    /// Parse a `0x` prefixed hex address. Mixed case addresses must have a valid EIP-55 checksum.
+    pub fn parse(value: &str) -> X402Result<Self> {
+        let address = decode_prefixed_hex::<20>(value)
+            .map(Self)
+            .ok_or(X402Error::InvalidEvmAddress)?;
+
+        let digits = &value[2..];
+        let is_mixed_case = digits.chars().any(|c| c.is_ascii_lowercase())
+            && digits.chars().any(|c| c.is_ascii_uppercase());
+        if is_mixed_case && address.to_checksum() != value {
+            return Err(X402Error::InvalidEvmAddress);
+        }
+
+        Ok(address)
+    }
+
+    /// The address of the account controlled by the private key of `public_key`
+    pub fn from_public_key(public_key: &VerifyingKey) -> Self {
+        let public_key = public_key.to_encoded_point(false);
+        let hash = keccak256(&[&public_key.as_bytes()[1..]]);
+
+        let mut address = [0u8; 20];
+        address.copy_from_slice(&hash[12..]);
+
+        Self(address)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 20] {
+        &self.0
+    }
+
+    /// The address left padded to an ABI encoded 32 byte word
+    pub fn to_word(&self) -> [u8; 32] {
+        let mut word = [0u8; 32];
+        word[12..].copy_from_slice(&self.0);
+
+        word
+    }
+
+    /// The `0x` prefixed EIP-55 mixed case checksum encoding of the address
+    pub fn to_checksum(&self) -> String {
+        let lowercase = hex::encode(self.0);
+        let hash = keccak256(&[lowercase.as_bytes()]);
+
+        let checksum = lowercase
+            .char_indices()
+            .map(|(index, c)| {
+                let nibble = (hash[index / 2] >> (4 * (1 - index % 2))) & 0x0f;
+
+                if nibble >= 8 {
+                    c.to_ascii_uppercase()
+                } else {
+                    c
+                }
+            })
+            .collect::<String>();
+
+        String::from("0x") + &checksum
+    }
+}
+
+impl fmt::Display for EvmAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_checksum())
+    }
+}
+
+impl FromStr for EvmAddress {
+    type Err = X402Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value)
+    }
+}
+
+impl TryFrom<&str> for EvmAddress {
+    type Error = X402Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl Serialize for EvmAddress {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_checksum())
+    }
+}
+
+impl<'de> Deserialize<'de> for EvmAddress {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+
+        Self::parse(&value).map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,68 @@
+use core::fmt;
+
+use k256::ecdsa::SigningKey;
+
+use crate::{
+    Eip712Domain, EvmAddress, EvmSignature, ExactEvmAuthorization, ExactEvmPayload, X402Error,
+    X402Result, decode_prefixed_hex,
+};
+
+/// A secp256k1 private key signing EIP-712 payment authorizations
+#[derive(Clone)]
+pub struct EvmSigner {
+    key: SigningKey,
+    address: EvmAddress,
+}
+
+impl EvmSigner {
+    pub fn from_bytes(secret: &[u8; 32]) -> X402Result<Self> {
+        let key = SigningKey::from_slice(secret).map_err(|_| X402Error::InvalidEvmPrivateKey)?;
+        let address = EvmAddress::from_public_key(key.verifying_key());
+
+        Ok(Self { key, address })
+    }
+
+    /// Parse a `0x` prefixed hex private key
+    pub fn from_hex(secret: &str) -> X402Result<Self> {
+        let secret = decode_prefixed_hex::<32>(secret).ok_or(X402Error::InvalidEvmPrivateKey)?;
+
+        Self::from_bytes(&secret)
+    }
+
+    pub fn address(&self) -> EvmAddress {
+        self.address
+    }
+
+    /// Sign a 32 byte digest with a low `s` and `v` of 27 or 28
+    pub fn sign_hash(&self, digest: &[u8; 32]) -> X402Result<EvmSignature> {
+        let (signature, recovery_id) = self
+            .key
+            .sign_prehash_recoverable(digest)
+            .map_err(|_| X402Error::TransactionSigningError)?;
+
+        let mut bytes = [0u8; 65];
+        bytes[..64].copy_from_slice(&signature.to_bytes());
+        bytes[64] = 27 + recovery_id.to_byte();
+
+        Ok(EvmSignature::new(bytes))
+    }
+
+    /// Sign `authorization` in `domain` returning the `exact` scheme payload
+    pub fn sign_authorization(
+        &self,
+        domain: &Eip712Domain<'_>,
+        authorization: ExactEvmAuthorization,
+    ) -> X402Result<ExactEvmPayload> {
+        let signature = self.sign_hash(&authorization.signing_hash(domain))?;
+
+        Ok(ExactEvmPayload::new(signature, authorization))
+    }
+}
+
+impl fmt::Debug for EvmSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EvmSigner")
+            .field("address", &self.address)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,176 @@
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Eip712Domain, EvmAddress, X402Error, X402Result, deserialize_decimal_string,
+    deserialize_prefixed_hex, keccak256, serialize_decimal_string, serialize_prefixed_hex,
+    uint256_word,
+};
+
+/// The EIP-712 type of the EIP-3009 `transferWithAuthorization` message
+pub const TRANSFER_WITH_AUTHORIZATION_TYPE: &str = "TransferWithAuthorization(address from,address to,uint256 value,uint256 validAfter,uint256 validBefore,bytes32 nonce)";
+
+/// The payload of the `exact` scheme on EVM chains, an EIP-3009
+/// `transferWithAuthorization` signed by the payer
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Serialize, Deserialize)]
+pub struct ExactEvmPayload {
+    signature: EvmSignature,
+    authorization: ExactEvmAuthorization,
+}
+
+impl ExactEvmPayload {
+    pub fn new(signature: EvmSignature, authorization: ExactEvmAuthorization) -> Self {
+        Self {
+            signature,
+            authorization,
+        }
+    }
+
+    pub fn signature(&self) -> &EvmSignature {
+        &self.signature
+    }
+
+    pub fn authorization(&self) -> &ExactEvmAuthorization {
+        &self.authorization
+    }
+
+    /// The account the tokens are transferred from
+    pub fn payer(&self) -> EvmAddress {
+        self.authorization.from
+    }
+
+    /// The account that signed the authorization in `domain`
+    pub fn recover_signer(&self, domain: &Eip712Domain<'_>) -> X402Result<EvmAddress> {
+        self.signature
+            .recover(&self.authorization.signing_hash(domain))
+    }
+}
+
+/// The parameters of an EIP-3009 `transferWithAuthorization`
+#[derive(
+    Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Serialize, Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct ExactEvmAuthorization {
+    /// The payer
+    from: EvmAddress,
+    /// The recipient
+    to: EvmAddress,
+    /// Amount in atomic token units
+    #[serde(serialize_with = "serialize_decimal_string")]
+    #[serde(deserialize_with = "deserialize_decimal_string")]
+    value: u128,
+    /// Unix timestamp in seconds after which the authorization is valid
+    #[serde(serialize_with = "serialize_decimal_string")]
+    #[serde(deserialize_with = "deserialize_decimal_string")]
+    valid_after: u64,
+    /// Unix timestamp in seconds before which the authorization is valid
+    #[serde(serialize_with = "serialize_decimal_string")]
+    #[serde(deserialize_with = "deserialize_decimal_string")]
+    valid_before: u64,
+    /// Unique nonce preventing the authorization from being used twice
+    #[serde(serialize_with = "serialize_prefixed_hex")]
+    #[serde(deserialize_with = "deserialize_prefixed_hex")]
+    nonce: [u8; 32],
+}
+
+impl ExactEvmAuthorization {
+    pub fn new(
+        from: EvmAddress,
+        to: EvmAddress,
+        value: u128,
+        valid_after: u64,
+        valid_before: u64,
+        nonce: [u8; 32],
+    ) -> Self {
+        Self {
+            from,
+            to,
+            value,
+            valid_after,
+            valid_before,
+            nonce,
+        }
+    }
+
+    pub fn from(&self) -> EvmAddress {
+        self.from
+    }
+
+    pub fn to(&self) -> EvmAddress {
+        self.to
+    }
+
+    pub fn value(&self) -> u128 {
+        self.value
+    }
+
+    pub fn valid_after(&self) -> u64 {
+        self.valid_after
+    }
+
+    pub fn valid_before(&self) -> u64 {
+        self.valid_before
+    }
+
+    pub fn nonce(&self) -> &[u8; 32] {
+        &self.nonce
+    }
+
+    /// The EIP-712 `hashStruct` of the authorization
+    pub fn struct_hash(&self) -> [u8; 32] {
+        keccak256(&[
+            &keccak256(&[TRANSFER_WITH_AUTHORIZATION_TYPE.as_bytes()]),
+            &self.from.to_word(),
+            &self.to.to_word(),
+            &uint256_word(self.value),
+            &uint256_word(self.valid_after.into()),
+            &uint256_word(self.valid_before.into()),
+            &self.nonce,
+        ])
+    }
+
+    /// The digest the payer signs to authorize the transfer in `domain`
+    pub fn signing_hash(&self, domain: &Eip712Domain<'_>) -> [u8; 32] {
+        domain.signing_hash(&self.struct_hash())
+    }
+}
+
+/// A 65 byte `r || s || v` secp256k1 signature
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Serialize, Deserialize)]
+pub struct EvmSignature(
+    #[serde(serialize_with = "serialize_prefixed_hex")]
+    #[serde(deserialize_with = "deserialize_prefixed_hex")]
+    [u8; 65],
+);
+
+impl EvmSignature {
+    pub const fn new(bytes: [u8; 65]) -> Self {
+        Self(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 65] {
+        &self.0
+    }
+
+    /// The address whose key signed `digest`. Signatures with a high `s`
+    /// are rejected like the `ecrecover` wrappers of EIP-3009 tokens do.
+    pub fn recover(&self, digest: &[u8; 32]) -> X402Result<EvmAddress> {
+        let signature = Signature::from_slice(&self.0[..64])
+            .map_err(|_| X402Error::InvalidExactSvmPayloadSignature)?;
+        if signature.normalize_s().is_some() {
+            return Err(X402Error::InvalidExactSvmPayloadSignature);
+        }
+
+        let recovery_id = match self.0[64] {
+            0 | 27 => RecoveryId::from_byte(0),
+            1 | 28 => RecoveryId::from_byte(1),
+            _ => Option::None,
+        }
+        .ok_or(X402Error::InvalidExactSvmPayloadSignature)?;
+
+        VerifyingKey::recover_from_prehash(digest, &signature, recovery_id)
+            .map(|public_key| EvmAddress::from_public_key(&public_key))
+            .map_err(|_| X402Error::InvalidExactSvmPayloadSignature)
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{
+    Eip712Domain, EvmAddress, EvmSigner, ExactEvmAuthorization, ExactEvmPayload,
+    PaymentRequirements, X402Error, X402Result, unix_timestamp,
+};
+
+/// Builds the EIP-3009 authorization paying [PaymentRequirements] with the `exact` scheme
+/// on the EVM chain of its network. The authorization is valid from [Self::DEFAULT_VALID_AFTER_SKEW]
+/// seconds ago until `maxTimeoutSeconds` from now unless set explicitly.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ExactEvmPayloadBuilder<'x> {
+    requirements: &'x PaymentRequirements<'x>,
+    nonce: Option<[u8; 32]>,
+    valid_after: Option<u64>,
+    valid_before: Option<u64>,
+}
+
+impl<'x> ExactEvmPayloadBuilder<'x> {
+    /// Seconds the default `validAfter` is backdated to tolerate clock skew
+    pub const DEFAULT_VALID_AFTER_SKEW: u64 = 600;
+
+    pub fn new(requirements: &'x PaymentRequirements<'x>) -> Self {
+        Self {
+            requirements,
+            nonce: Option::None,
+            valid_after: Option::None,
+            valid_before: Option::None,
+        }
+    }
+
+    /// The unique nonce of the authorization, usually 32 random bytes
+    pub fn set_nonce(&mut self, nonce: [u8; 32]) -> &mut Self {
+        self.nonce.replace(nonce);
+
+        self
+    }
+
+    pub fn set_valid_after(&mut self, unix_timestamp: u64) -> &mut Self {
+        self.valid_after.replace(unix_timestamp);
+
+        self
+    }
+
+    pub fn set_valid_before(&mut self, unix_timestamp: u64) -> &mut Self {
+        self.valid_before.replace(unix_timestamp);
+
+        self
+    }
+
+    pub fn requirements(&self) -> &PaymentRequirements<'x> {
+        self.requirements
+    }
+
+    /// The EIP-712 domain of the token of the requirements
+    pub fn domain(&self) -> X402Result<Eip712Domain<'x>> {
+        Eip712Domain::from_requirements(self.requirements)
+    }
+
+    /// The authorization transferring the required amount from `from` to `payTo`
+    pub fn authorization(&self, from: EvmAddress) -> X402Result<ExactEvmAuthorization> {
+        let now = unix_timestamp();
+
+        Ok(ExactEvmAuthorization::new(
+            from,
+            EvmAddress::parse(self.requirements.pay_to())?,
+            self.requirements.max_amount_required().into(),
+            self.valid_after
+                .unwrap_or(now.saturating_sub(Self::DEFAULT_VALID_AFTER_SKEW)),
+            self.valid_before
+                .unwrap_or(now.saturating_add(self.requirements.max_timeout_seconds())),
+            self.nonce.ok_or(X402Error::EvmNonceIsMissing)?,
+        ))
+    }
+
+    /// The authorization signed by `signer`
+    pub fn build(&self, signer: &EvmSigner) -> X402Result<ExactEvmPayload> {
+        signer.sign_authorization(&self.domain()?, self.authorization(signer.address())?)
+    }
+}
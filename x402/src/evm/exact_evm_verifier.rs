@@ -0,0 +1,71 @@
+use crate::{
+    Eip712Domain, EvmAddress, ExactEvmPayload, PaymentRequirements, PaymentScheme,
+    VerifyPayloadResponse, X402Error, X402Result, unix_timestamp,
+};
+
+/// Verifies an `exact` scheme EIP-3009 authorization against the [PaymentRequirements]
+/// it pays, offline. The token balance and the nonce are checked by the token contract
+/// when the authorization is submitted.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ExactEvmVerifier<'x> {
+    requirements: &'x PaymentRequirements<'x>,
+    payload: &'x ExactEvmPayload,
+}
+
+impl<'x> ExactEvmVerifier<'x> {
+    /// Seconds an authorization must remain valid for to be settled on chain
+    pub const VALID_BEFORE_MARGIN: u64 = 6;
+
+    pub fn new(requirements: &'x PaymentRequirements<'x>, payload: &'x ExactEvmPayload) -> Self {
+        Self {
+            requirements,
+            payload,
+        }
+    }
+
+    /// Verify the payment now returning the payer
+    pub fn verify(&self) -> X402Result<EvmAddress> {
+        self.verify_at(unix_timestamp())
+    }
+
+    /// Verify the payment at the unix timestamp `now` returning the payer
+    pub fn verify_at(&self, now: u64) -> X402Result<EvmAddress> {
+        let authorization = self.payload.authorization();
+
+        if self.requirements.scheme() != PaymentScheme::Exact {
+            return Err(X402Error::UnsupportedScheme);
+        }
+
+        let domain = Eip712Domain::from_requirements(self.requirements)?;
+
+        if authorization.to() != EvmAddress::parse(self.requirements.pay_to())? {
+            return Err(X402Error::InvalidExactSvmPayloadRecipientMismatch);
+        }
+
+        if authorization.value() < u128::from(self.requirements.max_amount_required()) {
+            return Err(X402Error::InvalidExactSvmPayloadAuthorizationValue);
+        }
+
+        if authorization.valid_before() < now.saturating_add(Self::VALID_BEFORE_MARGIN) {
+            return Err(X402Error::InvalidExactSvmPayloadAuthorizationValidBefore);
+        }
+
+        if authorization.valid_after() > now {
+            return Err(X402Error::InvalidExactSvmPayloadAuthorizationValidAfter);
+        }
+
+        if self.payload.recover_signer(&domain)? != authorization.from() {
+            return Err(X402Error::InvalidExactSvmPayloadSignature);
+        }
+
+        Ok(authorization.from())
+    }
+
+    /// Verify the payment now returning the response of the `/verify` API
+    pub fn verify_response(&self) -> VerifyPayloadResponse<'static> {
+        match self.verify() {
+            Ok(payer) => VerifyPayloadResponse::valid(payer.to_string()),
+            Err(error) => VerifyPayloadResponse::invalid(&error, self.payload.payer().to_string()),
+        }
+    }
+}
@@ -0,0 +1,20 @@
+mod utils;
+pub use utils::*;
+
+mod evm_address;
+pub use evm_address::*;
+
+mod eip712;
+pub use eip712::*;
+
+mod exact_evm_payload;
+pub use exact_evm_payload::*;
+
+mod evm_signer;
+pub use evm_signer::*;
+
+mod exact_evm_payload_builder;
+pub use exact_evm_payload_builder::*;
+
+mod exact_evm_verifier;
+pub use exact_evm_verifier::*;
@@ -0,0 +1,79 @@
+use serde::{Deserialize, Deserializer, Serializer};
+use sha3::{Digest, Keccak256};
+
+/// The Keccak-256 hash of the concatenation of `parts`
+pub fn keccak256(parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    parts.iter().for_each(|part| hasher.update(part));
+
+    hasher.finalize().into()
+}
+
+/// Encode `value` as a 32 byte big-endian `uint256` word
+pub fn uint256_word(value: u128) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[16..].copy_from_slice(&value.to_be_bytes());
+
+    word
+}
+
+/// Decode `0x` prefixed hex into exactly `N` bytes
+pub fn decode_prefixed_hex<const N: usize>(value: &str) -> Option<[u8; N]> {
+    let mut bytes = [0u8; N];
+    hex::decode_to_slice(value.strip_prefix("0x")?, &mut bytes).ok()?;
+
+    Some(bytes)
+}
+
+/// Encode `bytes` as lowercase `0x` prefixed hex
+pub fn encode_prefixed_hex(bytes: &[u8]) -> String {
+    String::from("0x") + &hex::encode(bytes)
+}
+
+pub fn serialize_prefixed_hex<S, const N: usize>(
+    bytes: &[u8; N],
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&encode_prefixed_hex(bytes))
+}
+
+pub fn deserialize_prefixed_hex<'de, D, const N: usize>(
+    deserializer: D,
+) -> Result<[u8; N], D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+
+    decode_prefixed_hex(&value)
+        .ok_or_else(|| serde::de::Error::custom(format!("expected {N} bytes of 0x prefixed hex")))
+}
+
+/// Serialize an integer as a decimal string like the `uint256` values of x402 EVM payloads
+pub fn serialize_decimal_string<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: ToString,
+{
+    serializer.serialize_str(&value.to_string())
+}
+
+pub fn deserialize_decimal_string<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: core::str::FromStr,
+{
+    let value = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+
+    value
+        .parse()
+        .map_err(|_| serde::de::Error::custom("expected a decimal integer string"))
+}
+
+/// The current unix timestamp in seconds
+pub fn unix_timestamp() -> u64 {
+    crate::X402Timestamp::now().unix_seconds()
+}
@@ -0,0 +1,53 @@
+use core::future::Future;
+use std::sync::Arc;
+
+use crate::{SettlementResponse, VerifyPayload, VerifyPayloadResponse, X402Result};
+
+/// The `/verify` and `/settle` APIs of a facilitator, either a remote one reached over HTTP
+/// or one running in the same process. Rejected payments are returned as the [crate::X402Error]
+/// of their `invalidReason` or `errorReason`.
+pub trait Facilitator {
+    /// Verify a payment without submitting it
+    fn verify(
+        &self,
+        payload: &VerifyPayload<'_>,
+    ) -> impl Future<Output = X402Result<VerifyPayloadResponse<'static>>> + Send;
+
+    /// Verify and submit a payment
+    fn settle(
+        &self,
+        payload: &VerifyPayload<'_>,
+    ) -> impl Future<Output = X402Result<SettlementResponse<'static, ()>>> + Send;
+}
+
+impl<T: Facilitator + Sync + ?Sized> Facilitator for &T {
+    fn verify(
+        &self,
+        payload: &VerifyPayload<'_>,
+    ) -> impl Future<Output = X402Result<VerifyPayloadResponse<'static>>> + Send {
+        (**self).verify(payload)
+    }
+
+    fn settle(
+        &self,
+        payload: &VerifyPayload<'_>,
+    ) -> impl Future<Output = X402Result<SettlementResponse<'static, ()>>> + Send {
+        (**self).settle(payload)
+    }
+}
+
+impl<T: Facilitator + Send + Sync + ?Sized> Facilitator for Arc<T> {
+    fn verify(
+        &self,
+        payload: &VerifyPayload<'_>,
+    ) -> impl Future<Output = X402Result<VerifyPayloadResponse<'static>>> + Send {
+        (**self).verify(payload)
+    }
+
+    fn settle(
+        &self,
+        payload: &VerifyPayload<'_>,
+    ) -> impl Future<Output = X402Result<SettlementResponse<'static, ()>>> + Send {
+        (**self).settle(payload)
+    }
+}
@@ -0,0 +1,240 @@
+use core::time::Duration;
+
+use reqwest::{
+    Client, StatusCode, Url,
+    header::{AUTHORIZATION, HeaderMap, HeaderName, HeaderValue},
+};
+use serde::Serialize;
+
+use crate::{
+    FACILITATOR_SETTLE_PATH, FACILITATOR_SUPPORTED_PATH, FACILITATOR_VERIFY_PATH, Facilitator,
+    SettlementResponse, SupportedSchemes, VerifyPayload, VerifyPayloadResponse, X402Error,
+    X402Result,
+};
+
+/// A client of the `/verify`, `/settle` and `/supported` APIs of a remote facilitator.
+///
+/// Rejected payments are returned as errors: the `invalidReason` or `errorReason`
+/// of the response is parsed with `X402Error::try_from(&str)` and responses without
+/// a reason are mapped from their HTTP status code.
+#[derive(Debug, Clone)]
+pub struct FacilitatorClient {
+    http: Client,
+    base_url: Url,
+}
+
+impl FacilitatorClient {
+    /// A client with the default configuration, see [FacilitatorClientBuilder] to configure it
+    pub fn new(base_url: &str) -> X402Result<Self> {
+        FacilitatorClientBuilder::new(base_url).build()
+    }
+
+    pub fn builder(base_url: &str) -> FacilitatorClientBuilder {
+        FacilitatorClientBuilder::new(base_url)
+    }
+
+    pub fn base_url(&self) -> &str {
+        self.base_url.as_str()
+    }
+
+    /// Verify a payment, a payment that is not valid is returned as the error of its `invalidReason`
+    pub async fn verify(
+        &self,
+        payload: &VerifyPayload<'_>,
+    ) -> X402Result<VerifyPayloadResponse<'static>> {
+        let (status, body) = self.post(FACILITATOR_VERIFY_PATH, payload).await?;
+
+        let response = serde_json::from_slice::<VerifyPayloadResponse<'_>>(&body)
+            .map_err(|_| status_error(status, X402Error::UnexpectedVerifyError))?;
+
+        match response.invalid_reason.as_deref() {
+            Some(reason) if !response.is_valid || !status.is_success() => {
+                Err(X402Error::try_from(reason).unwrap_or_else(|error| error))
+            }
+            _ if !response.is_valid || !status.is_success() => {
+                Err(status_error(status, X402Error::UnexpectedVerifyError))
+            }
+            _ => Ok(response.into_owned()),
+        }
+    }
+
+    /// Settle a payment, a failed settlement is returned as the error of its `errorReason`
+    pub async fn settle(
+        &self,
+        payload: &VerifyPayload<'_>,
+    ) -> X402Result<SettlementResponse<'static, ()>> {
+        let (status, body) = self.post(FACILITATOR_SETTLE_PATH, payload).await?;
+
+        let response = serde_json::from_slice::<SettlementResponse<'_, ()>>(&body)
+            .map_err(|_| status_error(status, X402Error::UnexpectedSettleError))?;
+
+        match response.error_reason() {
+            Some(reason) if !response.success() || !status.is_success() => {
+                Err(X402Error::try_from(reason).unwrap_or_else(|error| error))
+            }
+            _ if !response.success() || !status.is_success() => {
+                Err(status_error(status, X402Error::UnexpectedSettleError))
+            }
+            _ => Ok(response.into_owned()),
+        }
+    }
+
+    /// The payment kinds supported by the facilitator
+    pub async fn supported(&self) -> X402Result<SupportedSchemes> {
+        let response = self
+            .http
+            .get(self.url(FACILITATOR_SUPPORTED_PATH)?)
+            .send()
+            .await
+            .map_err(|_| X402Error::FacilitatorRequestError)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(status_error(status, X402Error::FacilitatorRequestError));
+        }
+
+        response
+            .json::<SupportedSchemes>()
+            .await
+            .map_err(|_| X402Error::InvalidFacilitatorResponse)
+    }
+
+    async fn post(
+        &self,
+        path: &str,
+        payload: &impl Serialize,
+    ) -> X402Result<(StatusCode, Vec<u8>)> {
+        let response = self
+            .http
+            .post(self.url(path)?)
+            .json(payload)
+            .send()
+            .await
+            .map_err(|_| X402Error::FacilitatorRequestError)?;
+
+        let status = response.status();
+        let body = response
+            .bytes()
+            .await
+            .map_err(|_| X402Error::FacilitatorRequestError)?;
+
+        Ok((status, body.to_vec()))
+    }
+
+    fn url(&self, path: &str) -> X402Result<Url> {
+        // Join relative to the base path so facilitators mounted below a path keep it
+        let path = path.trim_start_matches('/');
+
+        if self.base_url.path().ends_with('/') {
+            self.base_url.join(path)
+        } else {
+            let mut base_url = self.base_url.clone();
+            base_url.set_path(&(base_url.path().to_owned() + "/"));
+            base_url.join(path)
+        }
+        .map_err(|_| X402Error::InvalidFacilitatorClientConfig)
+    }
+}
+
+impl Facilitator for FacilitatorClient {
+    async fn verify(
+        &self,
+        payload: &VerifyPayload<'_>,
+    ) -> X402Result<VerifyPayloadResponse<'static>> {
+        FacilitatorClient::verify(self, payload).await
+    }
+
+    async fn settle(
+        &self,
+        payload: &VerifyPayload<'_>,
+    ) -> X402Result<SettlementResponse<'static, ()>> {
+        FacilitatorClient::settle(self, payload).await
+    }
+}
+
+/// The error of a response without an error code in its body
+fn status_error(status: StatusCode, fallback: X402Error) -> X402Error {
+    if status == StatusCode::BAD_REQUEST {
+        X402Error::InvalidPayload
+    } else if status.is_success() {
+        X402Error::InvalidFacilitatorResponse
+    } else {
+        fallback
+    }
+}
+
+/// Configures the [FacilitatorClient]
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct FacilitatorClientBuilder {
+    base_url: String,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    headers: Vec<(String, String)>,
+}
+
+impl FacilitatorClientBuilder {
+    /// Total time allowed for a request to the facilitator
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.to_owned(),
+            timeout: Some(Self::DEFAULT_TIMEOUT),
+            ..Default::default()
+        }
+    }
+
+    pub fn set_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout.replace(timeout);
+
+        self
+    }
+
+    pub fn set_connect_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.connect_timeout.replace(timeout);
+
+        self
+    }
+
+    /// Add a header sent with every request, like an API key
+    pub fn add_header(&mut self, name: &str, value: &str) -> &mut Self {
+        self.headers.push((name.to_owned(), value.to_owned()));
+
+        self
+    }
+
+    /// Send `token` in the `Authorization: Bearer` header of every request
+    pub fn set_bearer_auth(&mut self, token: &str) -> &mut Self {
+        self.add_header(AUTHORIZATION.as_str(), &(String::from("Bearer ") + token))
+    }
+
+    pub fn build(&self) -> X402Result<FacilitatorClient> {
+        let base_url =
+            Url::parse(&self.base_url).map_err(|_| X402Error::InvalidFacilitatorClientConfig)?;
+
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            let name = HeaderName::try_from(name.as_str())
+                .map_err(|_| X402Error::InvalidFacilitatorClientConfig)?;
+            let mut value = HeaderValue::try_from(value.as_str())
+                .map_err(|_| X402Error::InvalidFacilitatorClientConfig)?;
+            value.set_sensitive(true);
+
+            headers.insert(name, value);
+        }
+
+        let mut http = Client::builder().default_headers(headers);
+        if let Some(timeout) = self.timeout {
+            http = http.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            http = http.connect_timeout(timeout);
+        }
+
+        let http = http
+            .build()
+            .map_err(|_| X402Error::InvalidFacilitatorClientConfig)?;
+
+        Ok(FacilitatorClient { http, base_url })
+    }
+}
@@ -0,0 +1,19 @@
+/// Path of the facilitator API verifying payments
+pub const FACILITATOR_VERIFY_PATH: &str = "/verify";
+/// Path of the facilitator API settling payments
+pub const FACILITATOR_SETTLE_PATH: &str = "/settle";
+/// Path of the facilitator API listing the supported payment kinds
+pub const FACILITATOR_SUPPORTED_PATH: &str = "/supported";
+
+mod api;
+pub use api::*;
+
+#[cfg(feature = "facilitator-server")]
+mod server;
+#[cfg(feature = "facilitator-server")]
+pub use server::*;
+
+#[cfg(feature = "facilitator-client")]
+mod client;
+#[cfg(feature = "facilitator-client")]
+pub use client::*;
@@ -0,0 +1,275 @@
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    body::Bytes,
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use serde::Serialize;
+use solana_signer::Signer;
+use tokio::net::TcpListener;
+
+use crate::{
+    ExactSvmSettler, ExactSvmVerifier, FACILITATOR_SETTLE_PATH, FACILITATOR_SUPPORTED_PATH,
+    FACILITATOR_VERIFY_PATH, Facilitator, PaymentScheme, SchemeKind, SettlementResponse, SolanaRpc,
+    SupportedSchemes, UptoSvmSettler, UptoSvmVerifier, VerifyPayload, VerifyPayloadResponse,
+    X402Error, X402PaymentErrorStatusCode, X402Result, parse_solana_pubkey,
+};
+
+/// A facilitator HTTP service exposing the `/verify`, `/settle` and `/supported` APIs
+/// for the `exact` and `upto` schemes on Solana.
+///
+/// Payments are verified against the chain state through the [SolanaRpc] and settled
+/// by co-signing with the fee payer `S` of the facilitator. Failed requests are answered
+/// with the status code of [X402Error::status_code] and the error code in the
+/// `invalidReason` or `errorReason` of the body.
+#[derive(Debug)]
+pub struct FacilitatorServer<R, S> {
+    supported: SupportedSchemes,
+    settler: ExactSvmSettler<R, S>,
+}
+
+impl<R, S> FacilitatorServer<R, S>
+where
+    R: SolanaRpc + Send + Sync + 'static,
+    S: Signer + Send + Sync + 'static,
+{
+    pub fn new(kinds: &[SchemeKind], rpc: R, fee_payer: S) -> Self {
+        let mut supported = SupportedSchemes::new();
+        supported.add_kinds(kinds);
+
+        Self {
+            supported,
+            settler: ExactSvmSettler::new(rpc, fee_payer),
+        }
+    }
+
+    /// Configure the commitment, confirmation timeout and compute budget caps of settlements
+    pub fn settler_mut(&mut self) -> &mut ExactSvmSettler<R, S> {
+        &mut self.settler
+    }
+
+    pub fn settler(&self) -> &ExactSvmSettler<R, S> {
+        &self.settler
+    }
+
+    pub fn supported(&self) -> &SupportedSchemes {
+        &self.supported
+    }
+
+    /// Verify a payment returning the response of the `/verify` API
+    pub async fn verify(&self, payload: &VerifyPayload<'_>) -> VerifyPayloadResponse<'static> {
+        if let Err(error) = self
+            .supported
+            .ensure_supported(&SchemeKind::of_payload(&payload.payment_payload))
+            .and_then(|()| self.ensure_fee_payer(payload))
+        {
+            let payer_hint = ExactSvmVerifier::new(payload).payer_hint();
+
+            return VerifyPayloadResponse::invalid(&error, payer_hint);
+        }
+
+        let rpc = self.settler.rpc();
+        let caps = self.settler.compute_budget_caps();
+
+        match payload.payment_requirements.scheme() {
+            PaymentScheme::Exact => {
+                ExactSvmVerifier::new(payload)
+                    .set_compute_budget_caps(caps)
+                    .verify_response_with_rpc(rpc)
+                    .await
+            }
+            PaymentScheme::Upto => {
+                UptoSvmVerifier::new(payload)
+                    .set_compute_budget_caps(caps)
+                    .verify_response_with_rpc(rpc)
+                    .await
+            }
+        }
+    }
+
+    /// Verify and settle a payment
+    pub async fn settle(
+        &self,
+        payload: &VerifyPayload<'_>,
+    ) -> X402Result<SettlementResponse<'static, ()>> {
+        self.supported
+            .ensure_supported(&SchemeKind::of_payload(&payload.payment_payload))?;
+        self.ensure_fee_payer(payload)?;
+
+        let rpc = self.settler.rpc();
+        let caps = self.settler.compute_budget_caps();
+
+        match payload.payment_requirements.scheme() {
+            PaymentScheme::Exact => {
+                ExactSvmVerifier::new(payload)
+                    .set_compute_budget_caps(caps)
+                    .verify_with_rpc(rpc)
+                    .await?;
+
+                self.settler.settle(payload).await
+            }
+            PaymentScheme::Upto => {
+                UptoSvmVerifier::new(payload)
+                    .set_compute_budget_caps(caps)
+                    .verify_with_rpc(rpc)
+                    .await?;
+
+                self.upto_settler().settle(payload).await
+            }
+        }
+    }
+
+    /// Ensure `extra.feePayer` of the requirements is the fee payer of the facilitator,
+    /// payments for another fee payer could be verified but never settled
+    fn ensure_fee_payer(&self, payload: &VerifyPayload<'_>) -> X402Result<()> {
+        let fee_payer = payload.payment_requirements.extra().fee_payer();
+
+        if parse_solana_pubkey(fee_payer).ok() == Some(self.settler.fee_payer().pubkey()) {
+            return Ok(());
+        }
+
+        match payload.payment_requirements.scheme() {
+            PaymentScheme::Exact => {
+                Err(X402Error::InvalidExactSvmPayloadTransactionFeePayerMismatch)
+            }
+            PaymentScheme::Upto => Err(X402Error::InvalidUptoSvmPayloadTransactionFeePayerMismatch),
+        }
+    }
+
+    /// The settler of `upto` payments sharing the RPC, fee payer, confirmation
+    /// settings and compute budget caps of the [Self::settler]
+    fn upto_settler(&self) -> UptoSvmSettler<&R, &S> {
+        let mut settler = UptoSvmSettler::new(self.settler.rpc(), self.settler.fee_payer());
+        settler
+            .set_commitment(self.settler.commitment())
+            .set_confirmation_timeout(self.settler.confirmation_timeout())
+            .set_compute_budget_caps(self.settler.compute_budget_caps());
+
+        settler
+    }
+
+    /// The routes of the facilitator
+    pub fn router(self) -> Router {
+        Router::new()
+            .route(FACILITATOR_VERIFY_PATH, post(verify_handler::<R, S>))
+            .route(FACILITATOR_SETTLE_PATH, post(settle_handler::<R, S>))
+            .route(FACILITATOR_SUPPORTED_PATH, get(supported_handler::<R, S>))
+            .with_state(Arc::new(self))
+    }
+
+    /// Serve the facilitator on `listener` until the server stops
+    pub async fn serve(self, listener: TcpListener) -> std::io::Result<()> {
+        axum::serve(listener, self.router()).await
+    }
+}
+
+impl<R, S> Facilitator for FacilitatorServer<R, S>
+where
+    R: SolanaRpc + Send + Sync + 'static,
+    S: Signer + Send + Sync + 'static,
+{
+    async fn verify(
+        &self,
+        payload: &VerifyPayload<'_>,
+    ) -> X402Result<VerifyPayloadResponse<'static>> {
+        let response = FacilitatorServer::verify(self, payload).await;
+
+        if response.is_valid {
+            Ok(response)
+        } else {
+            Err(response
+                .invalid_reason_error()
+                .unwrap_or(X402Error::UnexpectedVerifyError))
+        }
+    }
+
+    async fn settle(
+        &self,
+        payload: &VerifyPayload<'_>,
+    ) -> X402Result<SettlementResponse<'static, ()>> {
+        FacilitatorServer::settle(self, payload).await
+    }
+}
+
+async fn supported_handler<R, S>(
+    State(server): State<Arc<FacilitatorServer<R, S>>>,
+) -> Json<SupportedSchemes>
+where
+    R: SolanaRpc + Send + Sync + 'static,
+    S: Signer + Send + Sync + 'static,
+{
+    Json(server.supported.clone())
+}
+
+async fn verify_handler<R, S>(
+    State(server): State<Arc<FacilitatorServer<R, S>>>,
+    body: Bytes,
+) -> Response
+where
+    R: SolanaRpc + Send + Sync + 'static,
+    S: Signer + Send + Sync + 'static,
+{
+    let response = match serde_json::from_slice::<VerifyPayload<'_>>(&body) {
+        Ok(payload) => FacilitatorServer::verify(&server, &payload).await,
+        Err(_) => VerifyPayloadResponse::invalid(&X402Error::InvalidPayload, ""),
+    };
+
+    let status = response
+        .invalid_reason_error()
+        .map(|error| match error {
+            X402Error::UnsupportedX402Error => X402PaymentErrorStatusCode::ServerError,
+            error => error.status_code(),
+        })
+        .map_or(StatusCode::OK, |status| http_status(&status));
+
+    json_response(status, &response)
+}
+
+async fn settle_handler<R, S>(
+    State(server): State<Arc<FacilitatorServer<R, S>>>,
+    body: Bytes,
+) -> Response
+where
+    R: SolanaRpc + Send + Sync + 'static,
+    S: Signer + Send + Sync + 'static,
+{
+    let payload = match serde_json::from_slice::<VerifyPayload<'_>>(&body) {
+        Ok(payload) => payload,
+        Err(_) => return settlement_error(&X402Error::InvalidPayload, None),
+    };
+
+    match FacilitatorServer::settle(&server, &payload).await {
+        Ok(settlement) => json_response(StatusCode::OK, &settlement),
+        Err(error) => settlement_error(&error, Some(&payload)),
+    }
+}
+
+fn settlement_error(error: &X402Error, payload: Option<&VerifyPayload<'_>>) -> Response {
+    let mut settlement = SettlementResponse::<()>::new(false);
+    settlement.set_error_reason(
+        error
+            .code()
+            .or(X402Error::UnexpectedSettleError.code())
+            .unwrap_or_default(),
+    );
+
+    if let Some(payload) = payload {
+        settlement
+            .set_network(payload.payment_requirements.network())
+            .set_payer(ExactSvmVerifier::new(payload).payer_hint());
+    }
+
+    json_response(http_status(&error.status_code()), &settlement)
+}
+
+fn http_status(status: &X402PaymentErrorStatusCode) -> StatusCode {
+    StatusCode::from_u16(status.status_code()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+fn json_response(status: StatusCode, body: &impl Serialize) -> Response {
+    (status, Json(body)).into_response()
+}
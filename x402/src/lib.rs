@@ -1,2 +1,39 @@
 mod types;
 pub use types::*;
+
+mod solana;
+pub use solana::*;
+
+mod replay;
+pub use replay::*;
+
+mod receipts;
+pub use receipts::*;
+
+#[cfg(any(
+    feature = "facilitator-server",
+    feature = "facilitator-client",
+    feature = "axum-middleware"
+))]
+mod facilitator;
+#[cfg(any(
+    feature = "facilitator-server",
+    feature = "facilitator-client",
+    feature = "axum-middleware"
+))]
+pub use facilitator::*;
+
+#[cfg(feature = "axum-middleware")]
+mod middleware;
+#[cfg(feature = "axum-middleware")]
+pub use middleware::*;
+
+#[cfg(feature = "paying-client")]
+mod client;
+#[cfg(feature = "paying-client")]
+pub use client::*;
+
+#[cfg(feature = "evm")]
+mod evm;
+#[cfg(feature = "evm")]
+pub use evm::*;
@@ -0,0 +1,2 @@
+mod payment_layer;
+pub use payment_layer::*;
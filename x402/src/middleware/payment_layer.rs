@@ -0,0 +1,314 @@
+use core::{
+    future::Future,
+    mem,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::Request,
+    http::{HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use tower::{Layer, Service};
+
+use crate::{
+    Facilitator, InMemoryReplayGuard, PAYMENT_REQUIRED_HEADER_KEY, PaymentRequired,
+    PaymentRequirements, PaymentRequirementsResponse, PaymentScheme, ReplayGuard, ReplayKey,
+    VersionedPaymentPayload, X402Error, X402Version, XPaymentResponse,
+};
+
+/// A tower [Layer] gating the routes it wraps behind an x402 payment.
+///
+/// Requests without a payment are answered with `402 Payment Required`, a
+/// [PaymentRequirementsResponse] body for x402 v1 clients and a [PaymentRequired]
+/// `PAYMENT-REQUIRED` header for x402 v2 clients, both accepting the `requirements` template.
+/// Payments of both versions are accepted, from the `X-PAYMENT` or `PAYMENT-SIGNATURE` header.
+/// Payments are verified by the facilitator before the inner handler runs and are only
+/// settled if the handler succeeds, the settlement is returned in the `X-PAYMENT-RESPONSE`
+/// or `PAYMENT-RESPONSE` header matching the version of the payment.
+/// The [crate::VerifyPayloadResponse] of the payment is added to the request extensions.
+///
+/// Handlers of `upto` routes add the [X402SettlementAmount] used by the request to the
+/// response extensions, the whole `maxAmountRequired` is settled otherwise.
+///
+/// Each payment is recorded by the [ReplayGuard] `G` before it is verified and a payment
+/// used again is rejected with [X402Error::PaymentAlreadyUsed]. Payments that fail
+/// verification or settlement or whose handler fails are released so they can be used again.
+/// An [InMemoryReplayGuard] is used unless [Self::with_replay_guard] sets another one.
+#[derive(Debug, Clone)]
+pub struct X402PaymentLayer<F, G = Arc<InMemoryReplayGuard>> {
+    facilitator: F,
+    requirements: Arc<PaymentRequirements<'static>>,
+    replay_guard: G,
+}
+
+/// The amount of an `upto` payment used by a request, settled instead of `maxAmountRequired`
+/// when added to the extensions of the response by the handler
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct X402SettlementAmount(pub u64);
+
+impl<F> X402PaymentLayer<F> {
+    pub fn new(facilitator: F, requirements: PaymentRequirements<'static>) -> Self {
+        Self {
+            facilitator,
+            requirements: Arc::new(requirements),
+            replay_guard: Arc::new(InMemoryReplayGuard::new()),
+        }
+    }
+}
+
+impl<F, G> X402PaymentLayer<F, G> {
+    /// Record the payments with `replay_guard`, share it between the layers of
+    /// routes that accept the same payments
+    pub fn with_replay_guard<R>(self, replay_guard: R) -> X402PaymentLayer<F, R> {
+        X402PaymentLayer {
+            facilitator: self.facilitator,
+            requirements: self.requirements,
+            replay_guard,
+        }
+    }
+
+    pub fn facilitator(&self) -> &F {
+        &self.facilitator
+    }
+
+    pub fn requirements(&self) -> &PaymentRequirements<'static> {
+        &self.requirements
+    }
+
+    pub fn replay_guard(&self) -> &G {
+        &self.replay_guard
+    }
+}
+
+impl<S, F: Clone, G: Clone> Layer<S> for X402PaymentLayer<F, G> {
+    type Service = X402PaymentService<S, F, G>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        X402PaymentService {
+            inner,
+            facilitator: self.facilitator.clone(),
+            requirements: self.requirements.clone(),
+            replay_guard: self.replay_guard.clone(),
+        }
+    }
+}
+
+/// The [Service] created by [X402PaymentLayer]
+#[derive(Debug, Clone)]
+pub struct X402PaymentService<S, F, G> {
+    inner: S,
+    facilitator: F,
+    requirements: Arc<PaymentRequirements<'static>>,
+    replay_guard: G,
+}
+
+impl<S, F, G> Service<Request> for X402PaymentService<S, F, G>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    F: Facilitator + Clone + Send + Sync + 'static,
+    G: ReplayGuard + Clone + Send + Sync + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request) -> Self::Future {
+        // The clone may not be ready, keep the service that was polled ready for this call
+        let clone = self.inner.clone();
+        let mut inner = mem::replace(&mut self.inner, clone);
+        let facilitator = self.facilitator.clone();
+        let requirements = self.requirements.clone();
+        let replay_guard = self.replay_guard.clone();
+
+        Box::pin(async move {
+            let Some((version, header)) =
+                [X402Version::V2, X402Version::V1]
+                    .into_iter()
+                    .find_map(|version| {
+                        let header = request.headers().get(version.payment_header_key())?;
+
+                        Some((version, header))
+                    })
+            else {
+                return Ok(payment_required(
+                    &requirements,
+                    StatusCode::PAYMENT_REQUIRED,
+                    Option::None,
+                ));
+            };
+
+            let mut buffer = Vec::new();
+            let payload = match header
+                .to_str()
+                .map_err(|_| X402Error::InvalidHeaderValueBase64)
+                .and_then(|header| {
+                    VersionedPaymentPayload::from_header_value(version, header, &mut buffer)
+                })
+                .and_then(|payment_payload| payment_payload.verify_payload(&requirements))
+            {
+                Ok(payload) => payload,
+                Err(error) => {
+                    return Ok(payment_error(
+                        &requirements,
+                        &error,
+                        X402Error::UnexpectedVerifyError,
+                    ));
+                }
+            };
+
+            let replay_key = match ReplayKey::from_payment_payload(&payload.payment_payload) {
+                Ok(replay_key) => replay_key,
+                Err(error) => {
+                    return Ok(payment_error(
+                        &requirements,
+                        &error,
+                        X402Error::UnexpectedVerifyError,
+                    ));
+                }
+            };
+
+            if let Err(error) = replay_guard.record(&replay_key).await {
+                return Ok(payment_error(
+                    &requirements,
+                    &error,
+                    X402Error::UnexpectedVerifyError,
+                ));
+            }
+
+            match facilitator.verify(&payload).await {
+                Ok(verified) => {
+                    request.extensions_mut().insert(verified);
+                }
+                Err(error) => {
+                    // The payment can be retried once the failure is resolved
+                    let _ = replay_guard.release(&replay_key).await;
+
+                    return Ok(payment_error(
+                        &requirements,
+                        &error,
+                        X402Error::UnexpectedVerifyError,
+                    ));
+                }
+            }
+
+            let mut response = inner.call(request).await?;
+            if !response.status().is_success() {
+                let _ = replay_guard.release(&replay_key).await;
+
+                return Ok(response);
+            }
+
+            let settlement_amount = response
+                .extensions_mut()
+                .remove::<X402SettlementAmount>()
+                .map_or(requirements.max_amount_required(), |amount| amount.0);
+
+            let mut payload = payload;
+            if requirements.scheme() == PaymentScheme::Upto {
+                payload.settlement_amount = Some(settlement_amount);
+            }
+
+            let settlement = match facilitator.settle(&payload).await {
+                Ok(settlement) => settlement,
+                Err(error) => {
+                    let _ = replay_guard.release(&replay_key).await;
+
+                    return Ok(payment_error(
+                        &requirements,
+                        &error,
+                        X402Error::UnexpectedSettleError,
+                    ));
+                }
+            };
+
+            let payment_response = XPaymentResponse::new()
+                .set_success()
+                .set_network(settlement.network())
+                .set_transaction_signature(settlement.transaction().unwrap_or_default())
+                .set_payer(settlement.payer());
+
+            let header = match version {
+                X402Version::V1 => payment_response.to_header_value(),
+                X402Version::V2 => payment_response.to_v2_header_value(),
+            };
+
+            match header.and_then(|header| {
+                HeaderValue::try_from(header).map_err(|_| X402Error::UnexpectedSettleError)
+            }) {
+                Ok(header) => {
+                    response
+                        .headers_mut()
+                        .insert(version.payment_response_header_key(), header);
+                }
+                Err(error) => {
+                    return Ok(payment_error(
+                        &requirements,
+                        &error,
+                        X402Error::UnexpectedSettleError,
+                    ));
+                }
+            }
+
+            Ok(response)
+        })
+    }
+}
+
+/// Answer with the code of `error`, errors without a code are reported as an invalid
+/// payload or as the `unexpected` error of the failed step for server errors
+fn payment_error(
+    requirements: &PaymentRequirements<'static>,
+    error: &X402Error,
+    unexpected: X402Error,
+) -> Response {
+    let status = StatusCode::from_u16(error.status_code().status_code())
+        .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    let fallback = if status.is_server_error() {
+        unexpected
+    } else {
+        X402Error::InvalidPayload
+    };
+
+    payment_required(requirements, status, error.code().or(fallback.code()))
+}
+
+/// Answer with the requirements in the body for x402 v1 clients and in the
+/// `PAYMENT-REQUIRED` header for x402 v2 clients. Without an `error` the payment is missing.
+fn payment_required(
+    requirements: &PaymentRequirements<'static>,
+    status: StatusCode,
+    error: Option<&str>,
+) -> Response {
+    let mut body = PaymentRequirementsResponse::new();
+    body.set_error_reason(error.unwrap_or(PaymentRequirementsResponse::ERROR))
+        .add_payment_requirement(requirements.clone());
+
+    let mut payment_required = PaymentRequired::new(requirements.into());
+    payment_required
+        .set_error_reason(error.unwrap_or(PaymentRequired::ERROR))
+        .add_payment_requirement(requirements.into());
+
+    let mut response = (status, Json(body)).into_response();
+
+    // The header is omitted when the requirements are too large for it
+    if let Some(header) = payment_required
+        .to_header_value()
+        .ok()
+        .and_then(|header| HeaderValue::try_from(header).ok())
+    {
+        response
+            .headers_mut()
+            .insert(PAYMENT_REQUIRED_HEADER_KEY, header);
+    }
+
+    response
+}
@@ -0,0 +1,80 @@
+use core::future::Future;
+use std::sync::{Mutex, MutexGuard};
+
+use crate::{PaymentReceipt, ReceiptQuery, ReceiptStore, X402Error, X402Result};
+
+/// A [ReceiptStore] keeping the receipts in memory, for tests and short lived processes
+#[derive(Debug, Default)]
+pub struct InMemoryReceiptStore {
+    receipts: Mutex<Vec<PaymentReceipt>>,
+}
+
+impl InMemoryReceiptStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of receipts stored
+    pub fn len(&self) -> X402Result<usize> {
+        Ok(self.receipts()?.len())
+    }
+
+    pub fn is_empty(&self) -> X402Result<bool> {
+        Ok(self.receipts()?.is_empty())
+    }
+
+    fn receipts(&self) -> X402Result<MutexGuard<'_, Vec<PaymentReceipt>>> {
+        self.receipts
+            .lock()
+            .map_err(|_| X402Error::ReceiptStoreError)
+    }
+
+    fn record_receipt(&self, receipt: &PaymentReceipt) -> X402Result<()> {
+        let mut receipts = self.receipts()?;
+
+        let is_recorded = receipts.iter().any(|recorded| {
+            recorded.network() == receipt.network() && recorded.signature() == receipt.signature()
+        });
+
+        if !is_recorded {
+            receipts.push(receipt.clone());
+        }
+
+        Ok(())
+    }
+
+    fn query_receipts(&self, query: &ReceiptQuery) -> X402Result<Vec<PaymentReceipt>> {
+        let mut receipts = self
+            .receipts()?
+            .iter()
+            .filter(|receipt| query.matches(receipt))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        receipts.sort_by(|a, b| {
+            a.timestamp()
+                .cmp(&b.timestamp())
+                .then_with(|| a.signature().cmp(b.signature()))
+        });
+        receipts.truncate(query.limit().unwrap_or(usize::MAX));
+
+        Ok(receipts)
+    }
+}
+
+impl ReceiptStore for InMemoryReceiptStore {
+    fn record(&self, receipt: &PaymentReceipt) -> impl Future<Output = X402Result<()>> + Send {
+        let outcome = self.record_receipt(receipt);
+
+        async move { outcome }
+    }
+
+    fn query(
+        &self,
+        query: &ReceiptQuery,
+    ) -> impl Future<Output = X402Result<Vec<PaymentReceipt>>> + Send {
+        let outcome = self.query_receipts(query);
+
+        async move { outcome }
+    }
+}
@@ -0,0 +1,29 @@
+mod payment_receipt;
+pub use payment_receipt::*;
+
+mod receipt_store;
+pub use receipt_store::*;
+
+mod in_memory_receipt_store;
+pub use in_memory_receipt_store::*;
+
+mod receipt_export;
+pub use receipt_export::*;
+
+#[cfg(feature = "receipts-sqlite")]
+mod sqlite_receipt_store;
+#[cfg(feature = "receipts-sqlite")]
+pub use sqlite_receipt_store::*;
+
+#[cfg(any(
+    feature = "facilitator-server",
+    feature = "facilitator-client",
+    feature = "axum-middleware"
+))]
+mod receipt_recorder;
+#[cfg(any(
+    feature = "facilitator-server",
+    feature = "facilitator-client",
+    feature = "axum-middleware"
+))]
+pub use receipt_recorder::*;
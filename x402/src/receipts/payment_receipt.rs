@@ -0,0 +1,197 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    PaymentScheme, SettlementResponse, VerifyPayload, X402Error, X402Network, X402Result,
+    X402Timestamp, deserialize_network, serialize_network,
+};
+
+/// The record of a settled payment
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentReceipt {
+    /// URL of the resource paid for
+    resource: String,
+    /// Address of the payer's wallet
+    payer: String,
+    /// Amount settled in atomic token units
+    amount: u64,
+    /// Mint or token contract address of the asset paid with
+    asset: String,
+    #[serde(deserialize_with = "deserialize_network")]
+    #[serde(serialize_with = "serialize_network")]
+    network: X402Network,
+    /// Signature of the settlement transaction
+    signature: String,
+    /// When the payment was settled, to the second
+    timestamp: X402Timestamp,
+}
+
+impl PaymentReceipt {
+    pub fn new(
+        resource: impl Into<String>,
+        payer: impl Into<String>,
+        amount: u64,
+        asset: impl Into<String>,
+        network: impl Into<X402Network>,
+        signature: impl Into<String>,
+        timestamp: X402Timestamp,
+    ) -> Self {
+        Self {
+            resource: resource.into(),
+            payer: payer.into(),
+            amount,
+            asset: asset.into(),
+            network: network.into(),
+            signature: signature.into(),
+            timestamp: timestamp.trunc_seconds(),
+        }
+    }
+
+    /// The receipt of a successful settlement of `payload`, timestamped with the time of
+    /// the settlement or now if the settlement has no timestamp.
+    /// The amount is the `settlementAmount` of `upto` payments and `maxAmountRequired` otherwise.
+    pub fn from_settlement(
+        payload: &VerifyPayload<'_>,
+        settlement: &SettlementResponse<'_, ()>,
+    ) -> X402Result<Self> {
+        let requirements = &payload.payment_requirements;
+
+        let signature = settlement
+            .transaction()
+            .filter(|signature| settlement.success() && !signature.is_empty())
+            .ok_or(X402Error::UnsettledPaymentReceipt)?;
+
+        let amount = match requirements.scheme() {
+            PaymentScheme::Exact => requirements.max_amount_required(),
+            PaymentScheme::Upto => payload
+                .settlement_amount
+                .ok_or(X402Error::InvalidUptoSvmSettlementAmount)?,
+        };
+
+        Ok(Self::new(
+            requirements.resource(),
+            settlement.payer(),
+            amount,
+            requirements.asset(),
+            settlement.network(),
+            signature,
+            settlement.timestamp().unwrap_or_else(X402Timestamp::now),
+        ))
+    }
+
+    /// URL of the resource paid for
+    pub fn resource(&self) -> &str {
+        self.resource.as_str()
+    }
+
+    /// Address of the payer's wallet
+    pub fn payer(&self) -> &str {
+        self.payer.as_str()
+    }
+
+    /// Amount settled in atomic token units
+    pub fn amount(&self) -> u64 {
+        self.amount
+    }
+
+    /// Mint or token contract address of the asset paid with
+    pub fn asset(&self) -> &str {
+        self.asset.as_str()
+    }
+
+    pub fn network(&self) -> X402Network {
+        self.network
+    }
+
+    /// Signature of the settlement transaction
+    pub fn signature(&self) -> &str {
+        self.signature.as_str()
+    }
+
+    /// When the payment was settled, to the second
+    pub fn timestamp(&self) -> X402Timestamp {
+        self.timestamp
+    }
+}
+
+/// Filters the receipts returned by a [crate::ReceiptStore], every filter set must match
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Default)]
+pub struct ReceiptQuery {
+    payer: Option<String>,
+    resource: Option<String>,
+    from: Option<X402Timestamp>,
+    until: Option<X402Timestamp>,
+    limit: Option<usize>,
+}
+
+impl ReceiptQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_payer(&mut self, payer: impl Into<String>) -> &mut Self {
+        self.payer.replace(payer.into());
+
+        self
+    }
+
+    pub fn set_resource(&mut self, resource: impl Into<String>) -> &mut Self {
+        self.resource.replace(resource.into());
+
+        self
+    }
+
+    /// Receipts settled at or after `from`
+    pub fn set_from(&mut self, from: X402Timestamp) -> &mut Self {
+        self.from.replace(from);
+
+        self
+    }
+
+    /// Receipts settled before `until`
+    pub fn set_until(&mut self, until: X402Timestamp) -> &mut Self {
+        self.until.replace(until);
+
+        self
+    }
+
+    /// Maximum number of receipts returned
+    pub fn set_limit(&mut self, limit: usize) -> &mut Self {
+        self.limit.replace(limit);
+
+        self
+    }
+
+    pub fn payer(&self) -> Option<&str> {
+        self.payer.as_deref()
+    }
+
+    pub fn resource(&self) -> Option<&str> {
+        self.resource.as_deref()
+    }
+
+    pub fn from(&self) -> Option<X402Timestamp> {
+        self.from
+    }
+
+    pub fn until(&self) -> Option<X402Timestamp> {
+        self.until
+    }
+
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    /// Whether the receipt matches every filter of the query
+    pub fn matches(&self, receipt: &PaymentReceipt) -> bool {
+        self.payer
+            .as_deref()
+            .is_none_or(|payer| payer == receipt.payer())
+            && self
+                .resource
+                .as_deref()
+                .is_none_or(|resource| resource == receipt.resource())
+            && self.from.is_none_or(|from| receipt.timestamp() >= from)
+            && self.until.is_none_or(|until| receipt.timestamp() < until)
+    }
+}
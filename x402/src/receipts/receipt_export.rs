@@ -0,0 +1,44 @@
+use std::io::Write;
+
+use crate::{BlockchainNetwork, PaymentReceipt, X402Error, X402Result};
+
+/// The header row of [export_receipts_csv]
+pub const RECEIPTS_CSV_HEADER: &str = "timestamp,network,resource,payer,asset,amount,signature";
+
+/// Write the receipts as CSV with a [RECEIPTS_CSV_HEADER] row
+pub fn export_receipts_csv(receipts: &[PaymentReceipt], mut writer: impl Write) -> X402Result<()> {
+    writeln!(writer, "{RECEIPTS_CSV_HEADER}").map_err(|_| X402Error::ReceiptStoreError)?;
+
+    receipts.iter().try_for_each(|receipt| {
+        writeln!(
+            writer,
+            "{},{},{},{},{},{},{}",
+            receipt.timestamp(),
+            csv_field(receipt.network().x402_identifier()),
+            csv_field(receipt.resource()),
+            csv_field(receipt.payer()),
+            csv_field(receipt.asset()),
+            receipt.amount(),
+            csv_field(receipt.signature()),
+        )
+        .map_err(|_| X402Error::ReceiptStoreError)
+    })?;
+
+    writer.flush().map_err(|_| X402Error::ReceiptStoreError)
+}
+
+/// Write the receipts as a JSON array
+pub fn export_receipts_json(receipts: &[PaymentReceipt], mut writer: impl Write) -> X402Result<()> {
+    serde_json::to_writer(&mut writer, receipts).map_err(|_| X402Error::ReceiptStoreError)?;
+
+    writer.flush().map_err(|_| X402Error::ReceiptStoreError)
+}
+
+/// Quote fields containing separators, quotes or line breaks (RFC 4180)
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
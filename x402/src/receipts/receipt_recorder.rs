@@ -0,0 +1,88 @@
+use crate::{
+    Facilitator, PaymentReceipt, ReceiptStore, SettlementResponse, VerifyPayload,
+    VerifyPayloadResponse, X402Error, X402Result,
+};
+
+/// Called with the error of a receipt that could not be recorded, the payload
+/// and the settlement it is the receipt of
+pub type ReceiptFailureCallback =
+    fn(&X402Error, &VerifyPayload<'_>, &SettlementResponse<'static, ()>);
+
+/// A [Facilitator] recording a [PaymentReceipt] of every successful settlement of the
+/// wrapped facilitator in a [ReceiptStore].
+///
+/// The settlement is returned even if the receipt could not be recorded since the
+/// payment already landed, use it as the facilitator of the [crate::X402PaymentLayer]
+/// to keep the receipts of a resource server. Receipts that could not be recorded are
+/// passed to the callback set with [Self::with_failure_callback], they are ignored otherwise.
+#[derive(Debug, Clone)]
+pub struct ReceiptRecorder<F, S, E = ReceiptFailureCallback> {
+    facilitator: F,
+    store: S,
+    on_failure: E,
+}
+
+impl<F, S> ReceiptRecorder<F, S> {
+    pub fn new(facilitator: F, store: S) -> Self {
+        Self {
+            facilitator,
+            store,
+            on_failure: |_, _, _| (),
+        }
+    }
+}
+
+impl<F, S, E> ReceiptRecorder<F, S, E> {
+    /// Call `on_failure` for every receipt that could not be recorded,
+    /// to log it or record it again later
+    pub fn with_failure_callback<C>(self, on_failure: C) -> ReceiptRecorder<F, S, C>
+    where
+        C: Fn(&X402Error, &VerifyPayload<'_>, &SettlementResponse<'static, ()>),
+    {
+        ReceiptRecorder {
+            facilitator: self.facilitator,
+            store: self.store,
+            on_failure,
+        }
+    }
+
+    pub fn facilitator(&self) -> &F {
+        &self.facilitator
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+}
+
+impl<F, S, E> Facilitator for ReceiptRecorder<F, S, E>
+where
+    F: Facilitator + Sync,
+    S: ReceiptStore + Sync,
+    E: Fn(&X402Error, &VerifyPayload<'_>, &SettlementResponse<'static, ()>) + Sync,
+{
+    async fn verify(
+        &self,
+        payload: &VerifyPayload<'_>,
+    ) -> X402Result<VerifyPayloadResponse<'static>> {
+        self.facilitator.verify(payload).await
+    }
+
+    async fn settle(
+        &self,
+        payload: &VerifyPayload<'_>,
+    ) -> X402Result<SettlementResponse<'static, ()>> {
+        let settlement = self.facilitator.settle(payload).await?;
+
+        let recorded = match PaymentReceipt::from_settlement(payload, &settlement) {
+            Ok(receipt) => self.store.record(&receipt).await,
+            Err(error) => Err(error),
+        };
+
+        if let Err(error) = recorded {
+            (self.on_failure)(&error, payload, &settlement);
+        }
+
+        Ok(settlement)
+    }
+}
@@ -0,0 +1,43 @@
+use core::future::Future;
+use std::sync::Arc;
+
+use crate::{PaymentReceipt, ReceiptQuery, X402Result};
+
+/// Persists the receipts of settled payments.
+/// Implement this for the database of choice.
+pub trait ReceiptStore {
+    /// Persist the receipt, a receipt of the same settlement transaction is only stored once
+    fn record(&self, receipt: &PaymentReceipt) -> impl Future<Output = X402Result<()>> + Send;
+
+    /// The receipts matching `query` ordered by their timestamp
+    fn query(
+        &self,
+        query: &ReceiptQuery,
+    ) -> impl Future<Output = X402Result<Vec<PaymentReceipt>>> + Send;
+}
+
+impl<T: ReceiptStore + Sync + ?Sized> ReceiptStore for &T {
+    fn record(&self, receipt: &PaymentReceipt) -> impl Future<Output = X402Result<()>> + Send {
+        (**self).record(receipt)
+    }
+
+    fn query(
+        &self,
+        query: &ReceiptQuery,
+    ) -> impl Future<Output = X402Result<Vec<PaymentReceipt>>> + Send {
+        (**self).query(query)
+    }
+}
+
+impl<T: ReceiptStore + Send + Sync + ?Sized> ReceiptStore for Arc<T> {
+    fn record(&self, receipt: &PaymentReceipt) -> impl Future<Output = X402Result<()>> + Send {
+        (**self).record(receipt)
+    }
+
+    fn query(
+        &self,
+        query: &ReceiptQuery,
+    ) -> impl Future<Output = X402Result<Vec<PaymentReceipt>>> + Send {
+        (**self).query(query)
+    }
+}
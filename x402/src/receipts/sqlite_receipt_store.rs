@@ -0,0 +1,199 @@
+use core::future::Future;
+use std::{
+    path::Path,
+    sync::{Mutex, MutexGuard},
+};
+
+use rusqlite::{Connection, OptionalExtension, Row, params};
+
+use crate::{
+    BlockchainNetwork, PaymentReceipt, ReceiptQuery, ReceiptStore, X402Error, X402Network,
+    X402Result, X402Timestamp,
+};
+
+/// A [ReceiptStore] persisting the receipts to a SQLite database.
+///
+/// The receipts are stored in the `x402_receipts` table, created when the store is
+/// opened, and indexed by payer, resource and timestamp.
+#[derive(Debug)]
+pub struct SqliteReceiptStore {
+    connection: Mutex<Connection>,
+}
+
+impl SqliteReceiptStore {
+    /// Open the database at `path`, creating it if it does not exist
+    pub fn open(path: impl AsRef<Path>) -> X402Result<Self> {
+        Connection::open(path)
+            .map_err(|_| X402Error::ReceiptStoreError)
+            .and_then(Self::from_connection)
+    }
+
+    /// Open a database that only lives as long as the store
+    pub fn open_in_memory() -> X402Result<Self> {
+        Connection::open_in_memory()
+            .map_err(|_| X402Error::ReceiptStoreError)
+            .and_then(Self::from_connection)
+    }
+
+    /// Use an open connection, creating the receipts table if it does not exist
+    pub fn from_connection(connection: Connection) -> X402Result<Self> {
+        connection
+            .execute_batch(
+                "CREATE TABLE IF NOT EXISTS x402_receipts (
+                    network TEXT NOT NULL,
+                    signature TEXT NOT NULL,
+                    resource TEXT NOT NULL,
+                    payer TEXT NOT NULL,
+                    asset TEXT NOT NULL,
+                    amount TEXT NOT NULL,
+                    timestamp INTEGER NOT NULL,
+                    PRIMARY KEY (network, signature)
+                );
+                CREATE INDEX IF NOT EXISTS x402_receipts_payer ON x402_receipts (payer, timestamp);
+                CREATE INDEX IF NOT EXISTS x402_receipts_resource ON x402_receipts (resource, timestamp);
+                CREATE INDEX IF NOT EXISTS x402_receipts_timestamp ON x402_receipts (timestamp);",
+            )
+            .map_err(|_| X402Error::ReceiptStoreError)?;
+
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    /// The receipt of the settlement transaction `signature` on `network`
+    pub fn get(&self, network: X402Network, signature: &str) -> X402Result<Option<PaymentReceipt>> {
+        let receipt = {
+            let connection = self.connection()?;
+
+            connection
+                .query_row(
+                    "SELECT resource, payer, amount, asset, network, signature, timestamp
+                    FROM x402_receipts WHERE network = ?1 AND signature = ?2",
+                    params![network.x402_identifier(), signature],
+                    receipt_columns,
+                )
+                .optional()
+                .map_err(|_| X402Error::ReceiptStoreError)?
+        };
+
+        receipt.map(parse_receipt).transpose()
+    }
+
+    fn connection(&self) -> X402Result<MutexGuard<'_, Connection>> {
+        self.connection
+            .lock()
+            .map_err(|_| X402Error::ReceiptStoreError)
+    }
+
+    fn record_receipt(&self, receipt: &PaymentReceipt) -> X402Result<()> {
+        let timestamp = i64::try_from(receipt.timestamp().unix_seconds())
+            .map_err(|_| X402Error::ReceiptStoreError)?;
+
+        self.connection()?
+            .execute(
+                "INSERT OR IGNORE INTO x402_receipts
+                (network, signature, resource, payer, asset, amount, timestamp)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    receipt.network().x402_identifier(),
+                    receipt.signature(),
+                    receipt.resource(),
+                    receipt.payer(),
+                    receipt.asset(),
+                    receipt.amount().to_string(),
+                    timestamp,
+                ],
+            )
+            .map_err(|_| X402Error::ReceiptStoreError)?;
+
+        Ok(())
+    }
+
+    fn query_receipts(&self, query: &ReceiptQuery) -> X402Result<Vec<PaymentReceipt>> {
+        let bound = |time: X402Timestamp| i64::try_from(time.unix_seconds()).unwrap_or(i64::MAX);
+        // A negative limit is no limit in SQLite
+        let limit = query
+            .limit()
+            .map_or(-1, |limit| i64::try_from(limit).unwrap_or(i64::MAX));
+
+        let rows = {
+            let connection = self.connection()?;
+            let mut statement = connection
+                .prepare_cached(
+                    "SELECT resource, payer, amount, asset, network, signature, timestamp
+                    FROM x402_receipts
+                    WHERE (?1 IS NULL OR payer = ?1)
+                    AND (?2 IS NULL OR resource = ?2)
+                    AND (?3 IS NULL OR timestamp >= ?3)
+                    AND (?4 IS NULL OR timestamp < ?4)
+                    ORDER BY timestamp, signature
+                    LIMIT ?5",
+                )
+                .map_err(|_| X402Error::ReceiptStoreError)?;
+
+            statement
+                .query_map(
+                    params![
+                        query.payer(),
+                        query.resource(),
+                        query.from().map(bound),
+                        query.until().map(bound),
+                        limit,
+                    ],
+                    receipt_columns,
+                )
+                .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+                .map_err(|_| X402Error::ReceiptStoreError)?
+        };
+
+        rows.into_iter().map(parse_receipt).collect()
+    }
+}
+
+impl ReceiptStore for SqliteReceiptStore {
+    fn record(&self, receipt: &PaymentReceipt) -> impl Future<Output = X402Result<()>> + Send {
+        let outcome = self.record_receipt(receipt);
+
+        async move { outcome }
+    }
+
+    fn query(
+        &self,
+        query: &ReceiptQuery,
+    ) -> impl Future<Output = X402Result<Vec<PaymentReceipt>>> + Send {
+        let outcome = self.query_receipts(query);
+
+        async move { outcome }
+    }
+}
+
+/// The columns of a receipt row, in the order they are selected
+type ReceiptColumns = (String, String, String, String, String, String, i64);
+
+fn receipt_columns(row: &Row<'_>) -> rusqlite::Result<ReceiptColumns> {
+    Ok((
+        row.get(0)?,
+        row.get(1)?,
+        row.get(2)?,
+        row.get(3)?,
+        row.get(4)?,
+        row.get(5)?,
+        row.get(6)?,
+    ))
+}
+
+fn parse_receipt(
+    (resource, payer, amount, asset, network, signature, timestamp): ReceiptColumns,
+) -> X402Result<PaymentReceipt> {
+    Ok(PaymentReceipt::new(
+        resource,
+        payer,
+        amount.parse().map_err(|_| X402Error::ReceiptStoreError)?,
+        asset,
+        X402Network::try_from(network.as_str())?,
+        signature,
+        u64::try_from(timestamp)
+            .map(X402Timestamp::from_unix_seconds)
+            .map_err(|_| X402Error::ReceiptStoreError)?,
+    ))
+}
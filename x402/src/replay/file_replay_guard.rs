@@ -0,0 +1,255 @@
+use core::future::Future;
+use std::{
+    collections::HashMap,
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard},
+};
+
+use crate::{ReplayGuard, ReplayKey, X402Error, X402Result, X402Timestamp};
+
+/// A [ReplayGuard] persisting the used payments to a file so they are
+/// remembered across restarts of the resource server.
+///
+/// Each payment is appended to the file as a line with the time it was recorded
+/// and synced before it is accepted, a released payment is appended as a line
+/// starting with `-`. Payments recorded more than `max_timeout_seconds` ago are
+/// forgotten, their transactions or authorizations can no longer be settled once
+/// the `maxTimeoutSeconds` of the requirements they pay has elapsed. The file is
+/// rewritten without the released and forgotten payments once they make up
+/// most of its lines.
+///
+/// The file is written and synced on the thread calling [ReplayGuard::record] and
+/// [ReplayGuard::release], which blocks the executor of an async runtime while the
+/// file is synced.
+#[derive(Debug)]
+pub struct FileReplayGuard {
+    path: PathBuf,
+    max_timeout_seconds: u64,
+    state: Mutex<FileState>,
+}
+
+#[derive(Debug)]
+struct FileState {
+    /// The time each payment was recorded at, in seconds since the Unix epoch
+    keys: HashMap<String, u64>,
+    file: File,
+    /// Lines of the file that no longer record a payment
+    obsolete_lines: usize,
+    /// When the forgotten payments are next removed from `keys`
+    next_prune: u64,
+}
+
+impl FileReplayGuard {
+    /// Payments remembered by [Self::open]
+    pub const DEFAULT_MAX_TIMEOUT_SECONDS: u64 = 3_600;
+
+    /// The file is not rewritten while it has fewer obsolete lines
+    const MIN_COMPACTION_LINES: usize = 1_024;
+
+    /// Interval between two removals of the forgotten payments
+    const PRUNE_INTERVAL_SECONDS: u64 = 60;
+
+    /// Open the file at `path`, creating it if it does not exist,
+    /// and load the payments it records
+    pub fn open(path: impl AsRef<Path>) -> X402Result<Self> {
+        Self::open_with_max_timeout(path, Self::DEFAULT_MAX_TIMEOUT_SECONDS)
+    }
+
+    /// Open the file at `path` remembering payments for `max_timeout_seconds`,
+    /// the largest `maxTimeoutSeconds` of the requirements paid
+    pub fn open_with_max_timeout(
+        path: impl AsRef<Path>,
+        max_timeout_seconds: u64,
+    ) -> X402Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&path)
+            .map_err(|_| X402Error::ReplayGuardError)?;
+
+        let now = X402Timestamp::now().unix_seconds();
+        let mut keys = HashMap::new();
+        let mut lines = 0;
+
+        for line in BufReader::new(&file).lines() {
+            let line = line.map_err(|_| X402Error::ReplayGuardError)?;
+            if line.is_empty() {
+                continue;
+            }
+            lines += 1;
+
+            if let Some(released) = line.strip_prefix('-') {
+                keys.remove(released);
+                continue;
+            }
+
+            // Files written before the time was recorded only have the key
+            let (key, recorded_at) = match line.split_once(' ') {
+                Some((key, recorded_at)) => (
+                    key,
+                    recorded_at
+                        .parse::<u64>()
+                        .map_err(|_| X402Error::ReplayGuardError)?,
+                ),
+                None => (line.as_str(), now),
+            };
+            keys.insert(key.to_owned(), recorded_at);
+        }
+
+        let guard = Self {
+            path,
+            max_timeout_seconds,
+            state: Mutex::new(FileState {
+                obsolete_lines: lines - keys.len(),
+                keys,
+                file,
+                next_prune: now,
+            }),
+        };
+        guard.prune(&mut *guard.state()?, now)?;
+
+        Ok(guard)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn max_timeout_seconds(&self) -> u64 {
+        self.max_timeout_seconds
+    }
+
+    /// Number of payments recorded
+    pub fn len(&self) -> X402Result<usize> {
+        let now = X402Timestamp::now().unix_seconds();
+
+        Ok(self
+            .state()?
+            .keys
+            .values()
+            .filter(|recorded_at| !self.is_forgotten(**recorded_at, now))
+            .count())
+    }
+
+    pub fn is_empty(&self) -> X402Result<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Whether the payment was already used
+    pub fn contains(&self, key: &ReplayKey) -> X402Result<bool> {
+        let now = X402Timestamp::now().unix_seconds();
+
+        Ok(self
+            .state()?
+            .keys
+            .get(&key.to_string())
+            .is_some_and(|recorded_at| !self.is_forgotten(*recorded_at, now)))
+    }
+
+    fn state(&self) -> X402Result<MutexGuard<'_, FileState>> {
+        self.state.lock().map_err(|_| X402Error::ReplayGuardError)
+    }
+
+    fn is_forgotten(&self, recorded_at: u64, now: u64) -> bool {
+        now.saturating_sub(recorded_at) > self.max_timeout_seconds
+    }
+
+    fn record_key(&self, key: &ReplayKey) -> X402Result<()> {
+        let mut state = self.state()?;
+        let now = X402Timestamp::now().unix_seconds();
+        let key = key.to_string();
+
+        if state
+            .keys
+            .get(&key)
+            .is_some_and(|recorded_at| !self.is_forgotten(*recorded_at, now))
+        {
+            return Err(X402Error::PaymentAlreadyUsed);
+        }
+
+        writeln!(state.file, "{key} {now}")
+            .and_then(|_| state.file.sync_data())
+            .map_err(|_| X402Error::ReplayGuardError)?;
+        if state.keys.insert(key, now).is_some() {
+            state.obsolete_lines += 1;
+        }
+
+        if now >= state.next_prune {
+            self.prune(&mut state, now)?;
+        }
+
+        Ok(())
+    }
+
+    fn release_key(&self, key: &ReplayKey) -> X402Result<()> {
+        let mut state = self.state()?;
+        let key = key.to_string();
+
+        if !state.keys.contains_key(&key) {
+            return Ok(());
+        }
+
+        // A lost tombstone only keeps the payment used, it is not synced
+        writeln!(state.file, "-{key}").map_err(|_| X402Error::ReplayGuardError)?;
+        state.keys.remove(&key);
+        state.obsolete_lines += 2;
+
+        self.compact_if_obsolete(&mut state)
+    }
+
+    /// Forget the payments recorded more than `max_timeout_seconds` ago
+    fn prune(&self, state: &mut FileState, now: u64) -> X402Result<()> {
+        let recorded = state.keys.len();
+        state
+            .keys
+            .retain(|_, recorded_at| !self.is_forgotten(*recorded_at, now));
+        state.obsolete_lines += recorded - state.keys.len();
+        state.next_prune = now.saturating_add(Self::PRUNE_INTERVAL_SECONDS);
+
+        self.compact_if_obsolete(state)
+    }
+
+    /// Write the remaining payments to a new file replacing the current one
+    /// once most of its lines are obsolete
+    fn compact_if_obsolete(&self, state: &mut FileState) -> X402Result<()> {
+        if state.obsolete_lines < state.keys.len().max(Self::MIN_COMPACTION_LINES) {
+            return Ok(());
+        }
+
+        let mut path = self.path.clone().into_os_string();
+        path.push(".tmp");
+
+        let mut contents = String::new();
+        state.keys.iter().for_each(|(key, recorded_at)| {
+            contents.push_str(&format!("{key} {recorded_at}\n"));
+        });
+
+        let file = fs::write(&path, contents)
+            .and_then(|_| File::open(&path)?.sync_all())
+            .and_then(|_| fs::rename(&path, &self.path))
+            .and_then(|_| OpenOptions::new().append(true).open(&self.path))
+            .map_err(|_| X402Error::ReplayGuardError)?;
+        state.file = file;
+        state.obsolete_lines = 0;
+
+        Ok(())
+    }
+}
+
+impl ReplayGuard for FileReplayGuard {
+    fn record(&self, key: &ReplayKey) -> impl Future<Output = X402Result<()>> + Send {
+        let outcome = self.record_key(key);
+
+        async move { outcome }
+    }
+
+    fn release(&self, key: &ReplayKey) -> impl Future<Output = X402Result<()>> + Send {
+        let outcome = self.release_key(key);
+
+        async move { outcome }
+    }
+}
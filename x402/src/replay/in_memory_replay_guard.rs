@@ -0,0 +1,121 @@
+use core::future::Future;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Mutex, MutexGuard},
+};
+
+use crate::{ReplayGuard, ReplayKey, X402Error, X402Result};
+
+/// A [ReplayGuard] keeping the most recently used payments in memory.
+///
+/// Once `capacity` payments are recorded the least recently used one is forgotten,
+/// the capacity should cover the payments received while their transactions or
+/// authorizations can still be settled. The payments are lost on restart, use a
+/// [crate::FileReplayGuard] to keep them.
+#[derive(Debug)]
+pub struct InMemoryReplayGuard {
+    capacity: usize,
+    state: Mutex<LruState>,
+}
+
+#[derive(Debug, Default)]
+struct LruState {
+    /// The last use of each key
+    keys: HashMap<ReplayKey, u64>,
+    /// The keys ordered by their last use
+    uses: BTreeMap<u64, ReplayKey>,
+    clock: u64,
+}
+
+impl InMemoryReplayGuard {
+    /// Payments remembered by [Self::new]
+    pub const DEFAULT_CAPACITY: usize = 100_000;
+
+    pub fn new() -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            state: Mutex::new(LruState::default()),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of payments remembered
+    pub fn len(&self) -> X402Result<usize> {
+        Ok(self.state()?.keys.len())
+    }
+
+    pub fn is_empty(&self) -> X402Result<bool> {
+        Ok(self.state()?.keys.is_empty())
+    }
+
+    /// Whether the payment was already used
+    pub fn contains(&self, key: &ReplayKey) -> X402Result<bool> {
+        Ok(self.state()?.keys.contains_key(key))
+    }
+
+    fn state(&self) -> X402Result<MutexGuard<'_, LruState>> {
+        self.state.lock().map_err(|_| X402Error::ReplayGuardError)
+    }
+
+    fn record_key(&self, key: &ReplayKey) -> X402Result<()> {
+        let mut state = self.state()?;
+        state.clock += 1;
+        let now = state.clock;
+
+        // A replayed payment is touched so that it is not the next one forgotten
+        if let Some(last_use) = state.keys.insert(*key, now) {
+            state.uses.remove(&last_use);
+            state.uses.insert(now, *key);
+
+            return Err(X402Error::PaymentAlreadyUsed);
+        }
+
+        state.uses.insert(now, *key);
+
+        while state.keys.len() > self.capacity {
+            let Some((_, oldest)) = state.uses.pop_first() else {
+                break;
+            };
+            state.keys.remove(&oldest);
+        }
+
+        Ok(())
+    }
+
+    fn release_key(&self, key: &ReplayKey) -> X402Result<()> {
+        let mut state = self.state()?;
+
+        if let Some(last_use) = state.keys.remove(key) {
+            state.uses.remove(&last_use);
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for InMemoryReplayGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReplayGuard for InMemoryReplayGuard {
+    fn record(&self, key: &ReplayKey) -> impl Future<Output = X402Result<()>> + Send {
+        let outcome = self.record_key(key);
+
+        async move { outcome }
+    }
+
+    fn release(&self, key: &ReplayKey) -> impl Future<Output = X402Result<()>> + Send {
+        let outcome = self.release_key(key);
+
+        async move { outcome }
+    }
+}
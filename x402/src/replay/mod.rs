@@ -0,0 +1,11 @@
+mod replay_key;
+pub use replay_key::*;
+
+mod replay_guard;
+pub use replay_guard::*;
+
+mod in_memory_replay_guard;
+pub use in_memory_replay_guard::*;
+
+mod file_replay_guard;
+pub use file_replay_guard::*;
@@ -0,0 +1,40 @@
+use core::future::Future;
+use std::sync::Arc;
+
+use crate::{ReplayKey, X402Result};
+
+/// Records the payments already used so the same payment cannot pay for
+/// several requests before its settlement lands.
+/// Implement this for a store shared by every instance of the resource server.
+///
+/// Payments are only recorded by the `X402PaymentLayer` of the resource server,
+/// the `FacilitatorServer` does not consult a guard and verifies the same payment
+/// as often as it is asked to, a payment settled twice is rejected by the chain.
+pub trait ReplayGuard {
+    /// Record the first use of the payment, returns [crate::X402Error::PaymentAlreadyUsed]
+    /// if the payment was already used
+    fn record(&self, key: &ReplayKey) -> impl Future<Output = X402Result<()>> + Send;
+
+    /// Forget a payment that was not settled so that it can be used again
+    fn release(&self, key: &ReplayKey) -> impl Future<Output = X402Result<()>> + Send;
+}
+
+impl<T: ReplayGuard + Sync + ?Sized> ReplayGuard for &T {
+    fn record(&self, key: &ReplayKey) -> impl Future<Output = X402Result<()>> + Send {
+        (**self).record(key)
+    }
+
+    fn release(&self, key: &ReplayKey) -> impl Future<Output = X402Result<()>> + Send {
+        (**self).release(key)
+    }
+}
+
+impl<T: ReplayGuard + Send + Sync + ?Sized> ReplayGuard for Arc<T> {
+    fn record(&self, key: &ReplayKey) -> impl Future<Output = X402Result<()>> + Send {
+        (**self).record(key)
+    }
+
+    fn release(&self, key: &ReplayKey) -> impl Future<Output = X402Result<()>> + Send {
+        (**self).release(key)
+    }
+}
@@ -0,0 +1,75 @@
+use core::fmt;
+
+use solana_hash::Hash;
+use solana_signature::Signature;
+use solana_transaction::versioned::VersionedTransaction;
+
+use crate::{X402Error, X402Result, XPaymentPayload, decode_svm_transaction};
+
+/// Identifies a payment so that it can only be used once
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum ReplayKey {
+    /// A Solana payment transaction, identified by the signature of the payer
+    /// and the blockhash or durable nonce the transaction is valid for
+    Svm {
+        signature: Signature,
+        blockhash: Hash,
+    },
+    /// An EIP-3009 authorization, identified by the authorizer and its nonce
+    Evm { from: [u8; 20], nonce: [u8; 32] },
+}
+
+impl ReplayKey {
+    /// The key of a partially signed Solana payment transaction. The fee payer signs
+    /// last so the signature of the payer, the first signer after the fee payer, is used.
+    pub fn from_svm_transaction(transaction: &VersionedTransaction) -> X402Result<Self> {
+        let signature = transaction
+            .signatures
+            .get(1)
+            .copied()
+            .ok_or(X402Error::InvalidPayload)?;
+
+        Ok(Self::Svm {
+            signature,
+            blockhash: *transaction.message.recent_blockhash(),
+        })
+    }
+
+    /// The key of the transaction or EVM authorization of an `X-PAYMENT` payload
+    pub fn from_payment_payload(payload: &XPaymentPayload<'_>) -> X402Result<Self> {
+        #[cfg(feature = "evm")]
+        if let Some(evm_payload) = payload.transaction().evm_payload() {
+            return Ok(Self::from_evm_payload(evm_payload));
+        }
+
+        Self::from_svm_transaction(&decode_svm_transaction(payload.transaction())?)
+    }
+
+    /// The key of a signed EIP-3009 authorization
+    #[cfg(feature = "evm")]
+    pub fn from_evm_payload(payload: &crate::ExactEvmPayload) -> Self {
+        let authorization = payload.authorization();
+
+        Self::Evm {
+            from: *authorization.from().as_bytes(),
+            nonce: *authorization.nonce(),
+        }
+    }
+}
+
+impl fmt::Display for ReplayKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Svm {
+                signature,
+                blockhash,
+            } => write!(f, "svm:{signature}:{blockhash}"),
+            Self::Evm { from, nonce } => {
+                f.write_str("evm:0x")?;
+                from.iter().try_for_each(|byte| write!(f, "{byte:02x}"))?;
+                f.write_str(":0x")?;
+                nonce.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+            }
+        }
+    }
+}
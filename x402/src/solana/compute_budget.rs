@@ -0,0 +1,124 @@
+use solana_compute_budget_interface::ComputeBudgetInstruction;
+use solana_instruction::Instruction;
+
+use crate::{X402Error, X402Result};
+
+/// The compute unit limit and price requested by a payment transaction.
+/// The priority fee they add up to is paid by the fee payer of the facilitator.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct ComputeBudget {
+    /// Compute units requested for the transaction
+    pub unit_limit: u32,
+    /// Priority fee in micro-lamports per compute unit
+    pub unit_price: u64,
+}
+
+impl ComputeBudget {
+    const MICRO_LAMPORTS_PER_LAMPORT: u128 = 1_000_000;
+
+    pub fn new(unit_limit: u32, unit_price: u64) -> Self {
+        Self {
+            unit_limit,
+            unit_price,
+        }
+    }
+
+    /// The priority fee in lamports, rounded up like the runtime
+    pub fn priority_fee(&self) -> u64 {
+        let micro_lamports = u128::from(self.unit_limit) * u128::from(self.unit_price);
+
+        u64::try_from(micro_lamports.div_ceil(Self::MICRO_LAMPORTS_PER_LAMPORT)).unwrap_or(u64::MAX)
+    }
+
+    /// The `SetComputeUnitLimit` and `SetComputeUnitPrice` instructions, in that order
+    pub fn instructions(&self) -> [Instruction; 2] {
+        [
+            ComputeBudgetInstruction::set_compute_unit_limit(self.unit_limit),
+            ComputeBudgetInstruction::set_compute_unit_price(self.unit_price),
+        ]
+    }
+}
+
+/// How a transaction builder picks the [ComputeBudget] of a payment
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum ComputeBudgetStrategy {
+    /// A fixed compute unit limit and price
+    Fixed(ComputeBudget),
+    /// The highest unit price whose priority fee for `unit_limit` compute units
+    /// is at most `max_priority_fee` lamports
+    PriorityFee {
+        unit_limit: u32,
+        max_priority_fee: u64,
+    },
+}
+
+impl ComputeBudgetStrategy {
+    /// The compute budget of the strategy
+    pub fn compute_budget(&self) -> ComputeBudget {
+        match *self {
+            Self::Fixed(compute_budget) => compute_budget,
+            Self::PriorityFee {
+                unit_limit,
+                max_priority_fee,
+            } => {
+                let unit_price = (u128::from(max_priority_fee)
+                    * ComputeBudget::MICRO_LAMPORTS_PER_LAMPORT)
+                    .checked_div(u128::from(unit_limit))
+                    .map_or(0, |unit_price| {
+                        u64::try_from(unit_price).unwrap_or(u64::MAX)
+                    });
+
+                ComputeBudget::new(unit_limit, unit_price)
+            }
+        }
+    }
+}
+
+/// The largest compute budget a facilitator accepts in payment transactions,
+/// bounding the priority fees its fee payer pays for clients
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct ComputeBudgetCaps {
+    /// Maximum compute unit limit
+    pub max_unit_limit: u32,
+    /// Maximum compute unit price in micro-lamports
+    pub max_unit_price: u64,
+}
+
+impl ComputeBudgetCaps {
+    /// Maximum compute unit limit unless configured, well above what a payment needs
+    pub const DEFAULT_MAX_UNIT_LIMIT: u32 = 200_000;
+    /// Maximum compute unit price unless configured, 5 lamports per compute unit
+    pub const DEFAULT_MAX_UNIT_PRICE: u64 = 5_000_000;
+
+    pub fn new(max_unit_limit: u32, max_unit_price: u64) -> Self {
+        Self {
+            max_unit_limit,
+            max_unit_price,
+        }
+    }
+
+    /// Fails with [X402Error::ComputeUnitLimitExceeded] or [X402Error::ComputeUnitPriceExceeded]
+    /// if the compute budget exceeds the caps
+    pub fn ensure_within(&self, compute_budget: &ComputeBudget) -> X402Result<()> {
+        if compute_budget.unit_limit > self.max_unit_limit {
+            return Err(X402Error::ComputeUnitLimitExceeded);
+        }
+
+        if compute_budget.unit_price > self.max_unit_price {
+            return Err(X402Error::ComputeUnitPriceExceeded);
+        }
+
+        Ok(())
+    }
+
+    /// The largest priority fee in lamports of a transaction within the caps
+    pub fn max_priority_fee(&self) -> u64 {
+        ComputeBudget::new(self.max_unit_limit, self.max_unit_price).priority_fee()
+    }
+}
+
+impl Default for ComputeBudgetCaps {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_MAX_UNIT_LIMIT, Self::DEFAULT_MAX_UNIT_PRICE)
+    }
+}
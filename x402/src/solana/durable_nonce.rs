@@ -0,0 +1,68 @@
+use solana_hash::Hash;
+use solana_instruction::Instruction;
+use solana_message::compiled_instruction::CompiledInstruction;
+use solana_pubkey::Pubkey;
+use solana_system_interface::instruction::SystemInstruction;
+
+use crate::{SolanaRpc, X402Error, X402Result, instruction_accounts, program_id};
+
+/// A durable nonce used in place of the recent blockhash of a payment transaction.
+///
+/// The transaction starts with an `AdvanceNonceAccount` instruction of the nonce
+/// `account` signed by its `authority` and its recent blockhash is the current nonce
+/// of the account. It stays valid until the nonce is advanced instead of expiring
+/// with the blockhash, so payments can be signed ahead of time.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct SolanaDurableNonce {
+    /// The nonce account advanced by the transaction
+    pub account: Pubkey,
+    /// The authority allowed to advance the nonce account
+    pub authority: Pubkey,
+}
+
+impl SolanaDurableNonce {
+    pub fn new(account: Pubkey, authority: Pubkey) -> Self {
+        Self { account, authority }
+    }
+
+    /// The `AdvanceNonceAccount` instruction starting the transaction
+    pub fn instruction(&self) -> Instruction {
+        solana_system_interface::instruction::advance_nonce_account(&self.account, &self.authority)
+    }
+
+    /// Fails with [X402Error::InvalidDurableNonce] unless the nonce account exists,
+    /// is advanced by [Self::authority] and its current nonce is `nonce`
+    pub async fn verify_with_rpc(&self, rpc: &impl SolanaRpc, nonce: &Hash) -> X402Result<()> {
+        let nonce_account = rpc
+            .get_nonce_account(&self.account)
+            .await?
+            .ok_or(X402Error::InvalidDurableNonce)?;
+
+        if nonce_account.authority != self.authority || nonce_account.durable_nonce != *nonce {
+            return Err(X402Error::InvalidDurableNonce);
+        }
+
+        Ok(())
+    }
+
+    /// Decode an `AdvanceNonceAccount` instruction, `None` for other instructions
+    pub(crate) fn decode(
+        account_keys: &[Pubkey],
+        instruction: &CompiledInstruction,
+    ) -> Option<Self> {
+        if program_id(account_keys, instruction) != Some(&solana_sdk_ids::system_program::id()) {
+            return None;
+        }
+
+        match bincode::deserialize(&instruction.data) {
+            Ok(SystemInstruction::AdvanceNonceAccount) => (),
+            _ => return None,
+        }
+
+        let [account, recent_blockhashes, authority] =
+            instruction_accounts::<3>(account_keys, instruction)?;
+
+        (recent_blockhashes == solana_sdk_ids::sysvar::recent_blockhashes::id())
+            .then_some(Self::new(account, authority))
+    }
+}
@@ -0,0 +1,113 @@
+use core::time::Duration;
+
+use solana_signer::Signer;
+
+use crate::{
+    Commitment, ComputeBudgetCaps, ExactSvmVerifier, SettlementResponse, SolanaRpcTransport,
+    VerifyPayload, X402Error, X402Result, X402Timestamp, sign_as_fee_payer,
+};
+
+/// Settles `exact` scheme payments on Solana for a facilitator.
+///
+/// The payment is verified again, co-signed with the fee payer keypair of the
+/// facilitator (`extra.feePayer`), submitted through the [SolanaRpcTransport]
+/// and awaited until it reaches the configured [Commitment].
+#[derive(Debug)]
+pub struct ExactSvmSettler<R, S> {
+    rpc: R,
+    fee_payer: S,
+    commitment: Commitment,
+    confirmation_timeout: Duration,
+    compute_budget_caps: ComputeBudgetCaps,
+}
+
+impl<R, S> ExactSvmSettler<R, S>
+where
+    R: SolanaRpcTransport,
+    S: Signer,
+{
+    /// Time to wait for the transaction to reach the commitment level
+    pub const DEFAULT_CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(60);
+
+    pub fn new(rpc: R, fee_payer: S) -> Self {
+        Self {
+            rpc,
+            fee_payer,
+            commitment: Commitment::default(),
+            confirmation_timeout: Self::DEFAULT_CONFIRMATION_TIMEOUT,
+            compute_budget_caps: ComputeBudgetCaps::default(),
+        }
+    }
+
+    pub fn set_commitment(&mut self, commitment: Commitment) -> &mut Self {
+        self.commitment = commitment;
+
+        self
+    }
+
+    pub fn set_confirmation_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.confirmation_timeout = timeout;
+
+        self
+    }
+
+    /// Set the largest compute budget of the payments co-signed by the fee payer
+    pub fn set_compute_budget_caps(&mut self, caps: ComputeBudgetCaps) -> &mut Self {
+        self.compute_budget_caps = caps;
+
+        self
+    }
+
+    pub fn rpc(&self) -> &R {
+        &self.rpc
+    }
+
+    pub fn fee_payer(&self) -> &S {
+        &self.fee_payer
+    }
+
+    pub fn commitment(&self) -> Commitment {
+        self.commitment
+    }
+
+    pub fn confirmation_timeout(&self) -> Duration {
+        self.confirmation_timeout
+    }
+
+    pub fn compute_budget_caps(&self) -> ComputeBudgetCaps {
+        self.compute_budget_caps
+    }
+
+    /// Co-sign, submit and confirm the payment transaction
+    pub async fn settle(
+        &self,
+        payload: &VerifyPayload<'_>,
+    ) -> X402Result<SettlementResponse<'static, ()>> {
+        let verified = ExactSvmVerifier::new(payload)
+            .set_compute_budget_caps(self.compute_budget_caps)
+            .verify()?;
+
+        if &self.fee_payer.pubkey() != verified.fee_payer() {
+            return Err(X402Error::FeePayerKeypairMismatch);
+        }
+
+        let payer = verified.payer().to_string();
+        let mut transaction = verified.transaction_take();
+        sign_as_fee_payer(&mut transaction, &self.fee_payer)
+            .map_err(|_| X402Error::InvalidExactSvmPayloadTransactionSignature)?;
+
+        let signature = self.rpc.send_transaction(&transaction).await?;
+        self.rpc
+            .confirm_transaction(&signature, self.commitment, self.confirmation_timeout)
+            .await?;
+
+        let mut response = SettlementResponse::new(true);
+        response
+            .set_transaction_signature(signature.to_string())
+            .set_network(payload.payment_requirements.network())
+            .set_payer(payer)
+            .set_timestamp(X402Timestamp::now());
+
+        Ok(response)
+    }
+}
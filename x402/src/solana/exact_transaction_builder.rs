@@ -0,0 +1,224 @@
+use solana_hash::Hash;
+use solana_instruction::Instruction;
+use solana_message::Message;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+
+use crate::{
+    ComputeBudget, ComputeBudgetStrategy, PaymentRequirements, SolanaDurableNonce,
+    SolanaTransferFee, X402Error, X402Result, XPaymentPayload, associated_token_address,
+    parse_solana_pubkey, token_program_id,
+};
+
+/// Builds the transaction for the `exact` scheme on Solana.
+///
+/// The transaction contains the compute budget instructions followed by a single
+/// `TransferChecked` instruction moving `maxAmountRequired` of the `asset` from the
+/// associated token account of the signer to the associated token account of `payTo`.
+/// [Self::set_create_destination] adds an idempotent instruction creating the
+/// associated token account of `payTo` before the transfer, funded by the fee payer.
+/// The `extra.feePayer` of the payment requirements is set as the fee payer so the
+/// transaction is only partially signed by the client; the facilitator adds its
+/// signature when settling the payment.
+///
+/// [Self::set_durable_nonce] starts the transaction with an `AdvanceNonceAccount`
+/// instruction so it stays valid for the whole `maxTimeoutSeconds`.
+///
+/// For Token-2022 mints with a transfer fee, [Self::set_transfer_fee] makes the
+/// transfer a `TransferCheckedWithFee` of `maxAmountRequired` plus the fee so the
+/// recipient is credited `maxAmountRequired`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ExactSvmTransactionBuilder<'x> {
+    requirements: &'x PaymentRequirements<'x>,
+    recent_blockhash: Option<Hash>,
+    compute_unit_limit: u32,
+    compute_unit_price: u64,
+    nonce_account: Option<Pubkey>,
+    transfer_fee: Option<SolanaTransferFee>,
+    create_destination: bool,
+}
+
+impl<'x> ExactSvmTransactionBuilder<'x> {
+    /// Compute units requested for the transaction
+    pub const DEFAULT_COMPUTE_UNIT_LIMIT: u32 = 60_000;
+    /// Priority fee in micro-lamports per compute unit
+    pub const DEFAULT_COMPUTE_UNIT_PRICE: u64 = 1;
+
+    pub fn new(requirements: &'x PaymentRequirements<'x>) -> Self {
+        Self {
+            requirements,
+            recent_blockhash: Option::default(),
+            compute_unit_limit: Self::DEFAULT_COMPUTE_UNIT_LIMIT,
+            compute_unit_price: Self::DEFAULT_COMPUTE_UNIT_PRICE,
+            nonce_account: Option::default(),
+            transfer_fee: Option::default(),
+            create_destination: false,
+        }
+    }
+
+    pub fn set_recent_blockhash(&mut self, recent_blockhash: Hash) -> &mut Self {
+        self.recent_blockhash.replace(recent_blockhash);
+
+        self
+    }
+
+    /// Use the current `nonce` of `nonce_account` as the recent blockhash so the
+    /// transaction does not expire. The signer of the payment must be the authority
+    /// of the nonce account.
+    pub fn set_durable_nonce(&mut self, nonce_account: Pubkey, nonce: Hash) -> &mut Self {
+        self.nonce_account.replace(nonce_account);
+        self.recent_blockhash.replace(nonce);
+
+        self
+    }
+
+    pub fn set_compute_unit_limit(&mut self, compute_unit_limit: u32) -> &mut Self {
+        self.compute_unit_limit = compute_unit_limit;
+
+        self
+    }
+
+    pub fn set_compute_unit_price(&mut self, micro_lamports: u64) -> &mut Self {
+        self.compute_unit_price = micro_lamports;
+
+        self
+    }
+
+    /// Set the compute unit limit and price picked by `strategy`
+    pub fn set_compute_budget(&mut self, strategy: ComputeBudgetStrategy) -> &mut Self {
+        let compute_budget = strategy.compute_budget();
+        self.compute_unit_limit = compute_budget.unit_limit;
+        self.compute_unit_price = compute_budget.unit_price;
+
+        self
+    }
+
+    /// The compute unit limit and price of the transaction
+    pub fn compute_budget(&self) -> ComputeBudget {
+        ComputeBudget::new(self.compute_unit_limit, self.compute_unit_price)
+    }
+
+    /// The transfer fee of the mint in effect for the current epoch
+    pub fn set_transfer_fee(&mut self, transfer_fee: SolanaTransferFee) -> &mut Self {
+        self.transfer_fee.replace(transfer_fee);
+
+        self
+    }
+
+    /// Create the associated token account of `payTo` if it does not exist yet,
+    /// the rent is paid by `extra.feePayer`
+    pub fn set_create_destination(&mut self, create_destination: bool) -> &mut Self {
+        self.create_destination = create_destination;
+
+        self
+    }
+
+    /// The payment requirements the transaction is built for
+    pub fn requirements(&self) -> &PaymentRequirements<'x> {
+        self.requirements
+    }
+
+    /// Build the instructions of the payment where `owner` is the wallet paying for the resource
+    pub fn instructions(&self, owner: &impl Signer) -> X402Result<Vec<Instruction>> {
+        let extra = self.requirements.extra();
+
+        let owner = owner.pubkey();
+        let mint = parse_solana_pubkey(self.requirements.asset())?;
+        let pay_to = parse_solana_pubkey(self.requirements.pay_to())?;
+        let token_program = token_program_id(extra.token_extensions_mint());
+
+        let source = associated_token_address(&owner, &mint, &token_program);
+        let destination = associated_token_address(&pay_to, &mint, &token_program);
+
+        let amount = self.requirements.max_amount_required();
+
+        let transfer = match self.transfer_fee {
+            Some(transfer_fee) => {
+                let amount = transfer_fee
+                    .calculate_pre_fee_amount(amount)
+                    .ok_or(X402Error::InvalidTransferInstruction)?;
+
+                spl_token_2022::extension::transfer_fee::instruction::transfer_checked_with_fee(
+                    &token_program,
+                    &source,
+                    &mint,
+                    &destination,
+                    &owner,
+                    &[],
+                    amount,
+                    extra.decimals(),
+                    transfer_fee.calculate_fee(amount),
+                )
+            }
+            None => spl_token_2022::instruction::transfer_checked(
+                &token_program,
+                &source,
+                &mint,
+                &destination,
+                &owner,
+                &[],
+                amount,
+                extra.decimals(),
+            ),
+        }
+        .map_err(|_| X402Error::InvalidTransferInstruction)?;
+
+        let mut instructions = self.durable_nonce_instructions(&owner);
+        instructions.extend(self.compute_budget().instructions());
+
+        if self.create_destination {
+            let fee_payer = parse_solana_pubkey(extra.fee_payer())?;
+
+            instructions.push(
+                spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                    &fee_payer,
+                    &pay_to,
+                    &mint,
+                    &token_program,
+                ),
+            );
+        }
+
+        instructions.push(transfer);
+
+        Ok(instructions)
+    }
+
+    /// The `AdvanceNonceAccount` instruction of the durable nonce advanced by `owner`, if any
+    fn durable_nonce_instructions(&self, owner: &Pubkey) -> Vec<Instruction> {
+        self.nonce_account
+            .map(|nonce_account| SolanaDurableNonce::new(nonce_account, *owner).instruction())
+            .into_iter()
+            .collect()
+    }
+
+    /// Build the transaction and partially sign it with the `owner` of the tokens
+    pub fn build(&self, owner: &impl Signer) -> X402Result<Transaction> {
+        let recent_blockhash = self
+            .recent_blockhash
+            .ok_or(X402Error::RecentBlockhashIsMissing)?;
+        let fee_payer = parse_solana_pubkey(self.requirements.extra().fee_payer())?;
+
+        let message = Message::new_with_blockhash(
+            &self.instructions(owner)?,
+            Some(&fee_payer),
+            &recent_blockhash,
+        );
+
+        let mut transaction = Transaction::new_unsigned(message);
+        transaction
+            .try_partial_sign(&[owner], recent_blockhash)
+            .map_err(|_| X402Error::TransactionSigningError)?;
+
+        Ok(transaction)
+    }
+
+    /// Build the partially signed transaction and wrap it in the payload of the `X-PAYMENT` header
+    pub fn build_payload(&self, owner: &impl Signer) -> X402Result<XPaymentPayload<'static>> {
+        let transaction = bincode::serialize(&self.build(owner)?)
+            .map_err(|_| X402Error::TransactionSerializationError)?;
+
+        Ok(XPaymentPayload::new(transaction).set_network(self.requirements.network()))
+    }
+}
@@ -0,0 +1,472 @@
+use borsh::BorshDeserialize;
+use solana_compute_budget_interface::ComputeBudgetInstruction;
+use solana_message::compiled_instruction::CompiledInstruction;
+use solana_pubkey::Pubkey;
+use solana_transaction::versioned::VersionedTransaction;
+use spl_associated_token_account::instruction::AssociatedTokenAccountInstruction;
+use spl_token_2022::{
+    extension::transfer_fee::instruction::TransferFeeInstruction, instruction::TokenInstruction,
+};
+
+use crate::{
+    ComputeBudget, ComputeBudgetCaps, PaymentScheme, SolanaDurableNonce, SolanaRpc, VerifyPayload,
+    VerifyPayloadResponse, X402Error, X402Result, associated_token_address, decode_compute_budget,
+    decode_svm_transaction, ensure_fee_payer_not_in_instructions, instruction_accounts,
+    is_signed_except_fee_payer, parse_solana_pubkey, program_id, token_program_id,
+};
+
+/// Verifies the transaction of an `exact` scheme payment on Solana against the
+/// payment requirements it is paying for.
+///
+/// The transaction must contain, in order, a `SetComputeUnitLimit` instruction,
+/// a `SetComputeUnitPrice` instruction, an optional instruction funded by `extra.feePayer`
+/// creating the associated token account of `payTo` and a single `TransferChecked` instruction
+/// of `maxAmountRequired` of the `asset` to the associated token account of `payTo`.
+/// Token-2022 mints may use a `TransferCheckedWithFee` instead, whose amount minus
+/// the fee is `maxAmountRequired`.
+/// It may be preceded by an `AdvanceNonceAccount` instruction whose nonce is the recent blockhash.
+/// The compute unit limit and price must be within the [ComputeBudgetCaps] of the facilitator.
+/// The fee payer must be `extra.feePayer`, which is not allowed in any other account of the
+/// instructions than the funder of the token account, and every other signer must have signed.
+///
+/// [Self::verify] only checks the transaction itself, [Self::verify_with_rpc]
+/// also checks the chain state like balances and the validity of the blockhash or durable nonce.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ExactSvmVerifier<'x> {
+    payload: &'x VerifyPayload<'x>,
+    caps: ComputeBudgetCaps,
+}
+
+impl<'x> ExactSvmVerifier<'x> {
+    /// Number of instructions without the create associated token account instruction,
+    /// not counting the `AdvanceNonceAccount` instruction of a durable nonce
+    pub const MIN_INSTRUCTIONS: usize = 3;
+    /// Number of instructions with the create associated token account instruction,
+    /// not counting the `AdvanceNonceAccount` instruction of a durable nonce
+    pub const MAX_INSTRUCTIONS: usize = 4;
+
+    pub fn new(payload: &'x VerifyPayload<'x>) -> Self {
+        Self {
+            payload,
+            caps: ComputeBudgetCaps::default(),
+        }
+    }
+
+    /// Set the largest compute unit limit and price accepted, [ComputeBudgetCaps::default] unless set
+    pub fn set_compute_budget_caps(mut self, caps: ComputeBudgetCaps) -> Self {
+        self.caps = caps;
+
+        self
+    }
+
+    /// Verify the payment and return the response of the `/verify` API
+    pub fn verify_response(&self) -> VerifyPayloadResponse<'static> {
+        match self.verify() {
+            Ok(verified) => VerifyPayloadResponse::valid(verified.payer().to_string()),
+            Err(error) => VerifyPayloadResponse::invalid(&error, self.payer_hint()),
+        }
+    }
+
+    /// Verify the payment returning the decoded transaction and transfer details
+    pub fn verify(&self) -> X402Result<VerifiedExactSvmPayment> {
+        let payment = &self.payload.payment_payload;
+        let requirements = &self.payload.payment_requirements;
+        let extra = requirements.extra();
+
+        if requirements.scheme() != PaymentScheme::Exact
+            || payment.scheme() != requirements.scheme()
+        {
+            return Err(X402Error::InvalidScheme);
+        }
+
+        if !requirements.network().is_solana() || payment.network() != requirements.network() {
+            return Err(X402Error::InvalidNetwork);
+        }
+
+        let transaction = decode_svm_transaction(payment.transaction())?;
+        let message = &transaction.message;
+
+        if message.sanitize().is_err()
+            || message
+                .address_table_lookups()
+                .is_some_and(|lookups| !lookups.is_empty())
+        {
+            return Err(X402Error::InvalidExactSvmPayloadTransaction);
+        }
+
+        let account_keys = message.static_account_keys();
+        let fee_payer = parse_solana_pubkey(extra.fee_payer())?;
+
+        if account_keys.first() != Some(&fee_payer) {
+            return Err(X402Error::InvalidExactSvmPayloadTransactionFeePayerMismatch);
+        }
+
+        // A durable nonce transaction starts with `AdvanceNonceAccount`, followed by the payment
+        let durable_nonce = message
+            .instructions()
+            .first()
+            .and_then(|instruction| SolanaDurableNonce::decode(account_keys, instruction));
+        let offset = usize::from(durable_nonce.is_some());
+        let instructions = &message.instructions()[offset..];
+        if !(Self::MIN_INSTRUCTIONS..=Self::MAX_INSTRUCTIONS).contains(&instructions.len()) {
+            return Err(X402Error::InvalidExactSvmPayloadTransactionInstructionsLength);
+        }
+
+        let mint = parse_solana_pubkey(requirements.asset())?;
+        let pay_to = parse_solana_pubkey(requirements.pay_to())?;
+        let token_program = token_program_id(extra.token_extensions_mint());
+        let creates_destination = instructions.len() == Self::MAX_INSTRUCTIONS;
+
+        // The fee payer may only fund the associated token account of `payTo`
+        let ata_program = spl_associated_token_account::id();
+        let fee_payer_funds_ata = [(offset + 2, &ata_program, 0)];
+        ensure_fee_payer_not_in_instructions(
+            account_keys,
+            message.instructions(),
+            &fee_payer,
+            if creates_destination {
+                &fee_payer_funds_ata
+            } else {
+                &[]
+            },
+        )?;
+
+        let compute_budget = ComputeBudget::new(
+            Self::verify_compute_unit_limit(account_keys, &instructions[0])?,
+            Self::verify_compute_unit_price(account_keys, &instructions[1])?,
+        );
+        self.caps.ensure_within(&compute_budget)?;
+
+        if creates_destination {
+            Self::verify_create_ata(
+                account_keys,
+                &instructions[2],
+                &fee_payer,
+                &pay_to,
+                &mint,
+                &token_program,
+            )?;
+        }
+
+        let transfer = &instructions[instructions.len() - 1];
+        let not_transfer_checked = if extra.token_extensions_mint() {
+            X402Error::InvalidExactSvmPayloadTransactionInstructionNotToken2022TransferChecked
+        } else {
+            X402Error::InvalidExactSvmPayloadTransactionInstructionNotSplTokenTransferChecked
+        };
+
+        if program_id(account_keys, transfer) != Some(&token_program) {
+            return Err(not_transfer_checked);
+        }
+
+        let (amount, decimals, fee) = match TokenInstruction::unpack(&transfer.data) {
+            Ok(TokenInstruction::TransferChecked { amount, decimals }) => (amount, decimals, 0),
+            Ok(TokenInstruction::TransferFeeExtension) if extra.token_extensions_mint() => {
+                match TransferFeeInstruction::unpack(&transfer.data[1..]) {
+                    Ok(TransferFeeInstruction::TransferCheckedWithFee {
+                        amount,
+                        decimals,
+                        fee,
+                    }) => (amount, decimals, fee),
+                    _ => return Err(not_transfer_checked),
+                }
+            }
+            _ => return Err(not_transfer_checked),
+        };
+
+        let [source, transfer_mint, destination, authority] =
+            instruction_accounts::<4>(account_keys, transfer).ok_or(not_transfer_checked)?;
+
+        if transfer_mint != mint {
+            return Err(X402Error::InvalidExactSvmPayloadTransactionAssetMismatch);
+        }
+
+        if source == associated_token_address(&fee_payer, &mint, &token_program) {
+            return Err(X402Error::FeePayerNotAllowedInInstruction);
+        }
+
+        if destination != associated_token_address(&pay_to, &mint, &token_program) {
+            return Err(X402Error::InvalidExactSvmPayloadTransactionTransferToIncorrectAta);
+        }
+
+        if amount.checked_sub(fee) != Some(requirements.max_amount_required()) {
+            return Err(X402Error::InvalidExactSvmPayloadTransactionAmountMismatch);
+        }
+
+        if decimals != extra.decimals() {
+            return Err(X402Error::InvalidExactSvmPayloadTransactionDecimalsMismatch);
+        }
+
+        Self::verify_signatures(&transaction)?;
+
+        Ok(VerifiedExactSvmPayment {
+            transaction,
+            payer: authority,
+            fee_payer,
+            mint,
+            source,
+            destination,
+            amount,
+            fee,
+            creates_destination,
+            durable_nonce,
+        })
+    }
+
+    /// Verify the payment and return the response of the `/verify` API,
+    /// checking the chain state through `rpc` as in [Self::verify_with_rpc]
+    pub async fn verify_response_with_rpc(
+        &self,
+        rpc: &impl SolanaRpc,
+    ) -> VerifyPayloadResponse<'static> {
+        match self.verify_with_rpc(rpc).await {
+            Ok(verified) => VerifyPayloadResponse::valid(verified.payer().to_string()),
+            Err(error) => VerifyPayloadResponse::invalid(&error, self.payer_hint()),
+        }
+    }
+
+    /// Verify the payment like [Self::verify] and check that it can be settled
+    /// against the current chain state: the blockhash is still valid or the durable
+    /// nonce is the current nonce of its account, the mint exists with the required
+    /// decimals and its extensions allow the transfer, the fee of the transfer is the
+    /// transfer fee of the mint, the payer holds enough tokens and the associated
+    /// token account of `payTo` exists or is created by the transaction.
+    pub async fn verify_with_rpc(
+        &self,
+        rpc: &impl SolanaRpc,
+    ) -> X402Result<VerifiedExactSvmPayment> {
+        let verified = self.verify()?;
+
+        let recent_blockhash = verified.transaction().message.recent_blockhash();
+        match verified.durable_nonce() {
+            Some(durable_nonce) => durable_nonce.verify_with_rpc(rpc, recent_blockhash).await?,
+            None if !rpc.is_blockhash_valid(recent_blockhash).await? => {
+                return Err(X402Error::InvalidTransactionState);
+            }
+            None => (),
+        }
+
+        let mint = rpc
+            .get_mint(verified.mint())
+            .await?
+            .ok_or(X402Error::InvalidExactSvmPayloadTransactionAssetMismatch)?;
+        let extra = self.payload.payment_requirements.extra();
+
+        if mint.token_program != token_program_id(extra.token_extensions_mint()) {
+            return Err(X402Error::InvalidExactSvmPayloadTransactionAssetMismatch);
+        }
+
+        if mint.decimals != extra.decimals() {
+            return Err(X402Error::InvalidExactSvmPayloadTransactionDecimalsMismatch);
+        }
+
+        mint.extensions.ensure_transferable()?;
+
+        if mint.extensions.transfer_fee_of(verified.amount()) != verified.fee() {
+            return Err(X402Error::InvalidExactSvmPayloadTransactionTransferFeeMismatch);
+        }
+
+        let source = rpc.get_token_account(verified.source()).await?;
+
+        // A token account of the fee payer may not be debited, even by a delegate
+        if source
+            .as_ref()
+            .is_some_and(|source| &source.owner == verified.fee_payer())
+        {
+            return Err(X402Error::FeePayerNotAllowedInInstruction);
+        }
+
+        let source = source
+            .filter(|source| &source.mint == verified.mint() && &source.owner == verified.payer())
+            .ok_or(X402Error::InvalidExactSvmPayloadTransactionSenderAtaNotFound)?;
+
+        if source.amount < verified.amount() {
+            return Err(X402Error::InsufficientFunds);
+        }
+
+        if !verified.creates_destination()
+            && rpc
+                .get_token_account(verified.destination())
+                .await?
+                .is_none()
+        {
+            return Err(X402Error::InvalidExactSvmPayloadTransactionReceiverAtaNotFound);
+        }
+
+        Ok(verified)
+    }
+
+    /// The authority of the last instruction if it can be decoded, used to report the payer
+    /// of a payment that failed verification
+    pub(crate) fn payer_hint(&self) -> String {
+        decode_svm_transaction(self.payload.payment_payload.transaction())
+            .ok()
+            .and_then(|transaction| {
+                let message = &transaction.message;
+
+                message.instructions().last().and_then(|instruction| {
+                    instruction_accounts::<4>(message.static_account_keys(), instruction)
+                        .map(|[_, _, _, authority]| authority.to_string())
+                })
+            })
+            .unwrap_or_default()
+    }
+
+    fn verify_compute_unit_limit(
+        account_keys: &[Pubkey],
+        instruction: &CompiledInstruction,
+    ) -> X402Result<u32> {
+        match decode_compute_budget(account_keys, instruction) {
+            Some(ComputeBudgetInstruction::SetComputeUnitLimit(units)) => Ok(units),
+            _ => Err(X402Error::InvalidExactSvmPayloadTransactionComputeLimitInstruction),
+        }
+    }
+
+    fn verify_compute_unit_price(
+        account_keys: &[Pubkey],
+        instruction: &CompiledInstruction,
+    ) -> X402Result<u64> {
+        match decode_compute_budget(account_keys, instruction) {
+            Some(ComputeBudgetInstruction::SetComputeUnitPrice(micro_lamports)) => {
+                Ok(micro_lamports)
+            }
+            _ => Err(X402Error::InvalidExactSvmPayloadTransactionComputePriceInstruction),
+        }
+    }
+
+    fn verify_create_ata(
+        account_keys: &[Pubkey],
+        instruction: &CompiledInstruction,
+        fee_payer: &Pubkey,
+        pay_to: &Pubkey,
+        mint: &Pubkey,
+        token_program: &Pubkey,
+    ) -> X402Result<()> {
+        if program_id(account_keys, instruction) != Some(&spl_associated_token_account::id()) {
+            return Err(X402Error::InvalidExactSvmPayloadTransactionCreateAtaInstruction);
+        }
+
+        // `Create`, also encoded as an empty instruction data, fails the settlement when the
+        // account was created in the meantime, only `CreateIdempotent` is accepted
+        if !matches!(
+            AssociatedTokenAccountInstruction::try_from_slice(&instruction.data),
+            Ok(AssociatedTokenAccountInstruction::CreateIdempotent)
+        ) {
+            return Err(X402Error::InvalidExactSvmPayloadTransactionCreateAtaInstruction);
+        }
+
+        let [
+            funder,
+            ata,
+            wallet,
+            ata_mint,
+            _system_program,
+            ata_token_program,
+        ] = instruction_accounts::<6>(account_keys, instruction)
+            .ok_or(X402Error::InvalidExactSvmPayloadTransactionCreateAtaInstruction)?;
+
+        // Only `extra.feePayer` may pay the rent of the account
+        if funder != *fee_payer {
+            return Err(X402Error::InvalidExactSvmPayloadTransactionCreateAtaInstruction);
+        }
+
+        if wallet != *pay_to {
+            return Err(
+                X402Error::InvalidExactSvmPayloadTransactionCreateAtaInstructionIncorrectPayee,
+            );
+        }
+
+        if ata_mint != *mint {
+            return Err(
+                X402Error::InvalidExactSvmPayloadTransactionCreateAtaInstructionIncorrectAsset,
+            );
+        }
+
+        if ata_token_program != *token_program
+            || ata != associated_token_address(pay_to, mint, token_program)
+        {
+            return Err(X402Error::InvalidExactSvmPayloadTransactionCreateAtaInstruction);
+        }
+
+        Ok(())
+    }
+
+    /// Every required signer except the fee payer, which signs at settlement, must have signed
+    fn verify_signatures(transaction: &VersionedTransaction) -> X402Result<()> {
+        is_signed_except_fee_payer(transaction)
+            .then_some(())
+            .ok_or(X402Error::InvalidExactSvmPayloadTransactionSignature)
+    }
+}
+
+/// A payment transaction that passed verification
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct VerifiedExactSvmPayment {
+    transaction: VersionedTransaction,
+    payer: Pubkey,
+    fee_payer: Pubkey,
+    mint: Pubkey,
+    source: Pubkey,
+    destination: Pubkey,
+    amount: u64,
+    fee: u64,
+    creates_destination: bool,
+    durable_nonce: Option<SolanaDurableNonce>,
+}
+
+impl VerifiedExactSvmPayment {
+    /// The decoded transaction, only partially signed by the payer
+    pub fn transaction(&self) -> &VersionedTransaction {
+        &self.transaction
+    }
+
+    /// The decoded transaction but consumes self
+    pub fn transaction_take(self) -> VersionedTransaction {
+        self.transaction
+    }
+
+    /// The durable nonce used in place of a recent blockhash, if any
+    pub fn durable_nonce(&self) -> Option<&SolanaDurableNonce> {
+        self.durable_nonce.as_ref()
+    }
+
+    /// The owner of the tokens authorizing the transfer
+    pub fn payer(&self) -> &Pubkey {
+        &self.payer
+    }
+
+    /// The fee payer of the transaction (`extra.feePayer`)
+    pub fn fee_payer(&self) -> &Pubkey {
+        &self.fee_payer
+    }
+
+    /// The mint of the tokens transferred
+    pub fn mint(&self) -> &Pubkey {
+        &self.mint
+    }
+
+    /// The token account the tokens are transferred from
+    pub fn source(&self) -> &Pubkey {
+        &self.source
+    }
+
+    /// The associated token account of `payTo`
+    pub fn destination(&self) -> &Pubkey {
+        &self.destination
+    }
+
+    /// The amount transferred in atomic token units, including the transfer fee
+    pub fn amount(&self) -> u64 {
+        self.amount
+    }
+
+    /// The transfer fee withheld from the amount transferred, zero for `TransferChecked`
+    pub fn fee(&self) -> u64 {
+        self.fee
+    }
+
+    /// Whether the transaction creates the associated token account of `payTo`
+    pub fn creates_destination(&self) -> bool {
+        self.creates_destination
+    }
+}
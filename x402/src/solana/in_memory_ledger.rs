@@ -0,0 +1,631 @@
+use core::{future::Future, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Mutex, MutexGuard},
+};
+
+use borsh::BorshDeserialize;
+use solana_hash::Hash;
+use solana_message::{VersionedMessage, compiled_instruction::CompiledInstruction};
+use solana_pubkey::Pubkey;
+use solana_signature::Signature;
+use solana_transaction::versioned::VersionedTransaction;
+use spl_associated_token_account::instruction::AssociatedTokenAccountInstruction;
+use spl_token_2022::{
+    extension::transfer_fee::instruction::TransferFeeInstruction, instruction::TokenInstruction,
+};
+
+use crate::{
+    Commitment, SolanaDurableNonce, SolanaMintAccount, SolanaMintExtensions, SolanaNonceAccount,
+    SolanaRpc, SolanaRpcTransport, SolanaTokenAccount, X402Error, X402Result,
+    associated_token_address, instruction_accounts, program_id,
+};
+
+/// An in-memory Solana ledger implementing [SolanaRpc] for tests that need
+/// chain state without running a validator.
+///
+/// Mints, associated token accounts and balances are set up directly on the
+/// ledger. Submitted transactions are executed atomically: compute budget
+/// instructions are accepted, `AdvanceNonceAccount`, associated token account
+/// creation, `ApproveChecked`, `Revoke` and `TransferChecked` or `TransferCheckedWithFee`
+/// by the owner or a delegate are simulated and any other instruction fails the
+/// transaction with [X402Error::InvalidTransactionState]. Executed
+/// transactions are immediately finalized.
+///
+/// Transactions starting with `AdvanceNonceAccount` may use the current nonce of a
+/// nonce account as their blockhash, which is replaced by a new nonce once executed.
+///
+/// Transfer fees of the [SolanaMintExtensions] of a mint are withheld from the amount
+/// credited to the recipient, transfers of non-transferable mints and of mints with a
+/// transfer hook fail.
+#[derive(Debug)]
+pub struct InMemorySolanaLedger {
+    state: Mutex<LedgerState>,
+}
+
+#[derive(Debug, Default, Clone)]
+struct LedgerState {
+    mints: HashMap<Pubkey, SolanaMintAccount>,
+    token_accounts: HashMap<Pubkey, SolanaTokenAccount>,
+    /// The delegate and delegated amount of token accounts
+    delegations: HashMap<Pubkey, (Pubkey, u64)>,
+    nonce_accounts: HashMap<Pubkey, SolanaNonceAccount>,
+    /// Blockhashes transactions can use, the last one is the latest
+    blockhashes: Vec<Hash>,
+    processed: HashSet<Signature>,
+}
+
+impl InMemorySolanaLedger {
+    pub fn new() -> Self {
+        let state = LedgerState {
+            blockhashes: vec![Hash::new_unique()],
+            ..Default::default()
+        };
+
+        Self {
+            state: Mutex::new(state),
+        }
+    }
+
+    /// Add a mint owned by `token_program` (SPL Token or Token-2022)
+    pub fn add_mint(&self, mint: Pubkey, decimals: u8, token_program: Pubkey) -> X402Result<()> {
+        self.state()?
+            .mints
+            .insert(mint, SolanaMintAccount::new(decimals, token_program));
+
+        Ok(())
+    }
+
+    /// Set the Token-2022 extensions of a mint added with [Self::add_mint]
+    pub fn set_mint_extensions(
+        &self,
+        mint: &Pubkey,
+        extensions: SolanaMintExtensions,
+    ) -> X402Result<()> {
+        let mut state = self.state()?;
+        let mint_account = state
+            .mints
+            .get_mut(mint)
+            .ok_or(X402Error::InvalidTransactionState)?;
+        mint_account.extensions = extensions;
+
+        Ok(())
+    }
+
+    /// Create the associated token account of `owner` for `mint` returning its address
+    pub fn create_associated_token_account(
+        &self,
+        owner: &Pubkey,
+        mint: &Pubkey,
+    ) -> X402Result<Pubkey> {
+        self.state()?.create_associated_token_account(owner, mint)
+    }
+
+    /// Mint `amount` tokens to the associated token account of `owner`,
+    /// creating the account if it does not exist
+    pub fn mint_to(&self, owner: &Pubkey, mint: &Pubkey, amount: u64) -> X402Result<Pubkey> {
+        let mut state = self.state()?;
+        let address = state.create_associated_token_account(owner, mint)?;
+
+        let account = state
+            .token_accounts
+            .get_mut(&address)
+            .ok_or(X402Error::InvalidTransactionState)?;
+        account.amount = account
+            .amount
+            .checked_add(amount)
+            .ok_or(X402Error::InvalidTransactionState)?;
+
+        Ok(address)
+    }
+
+    /// The balance of the associated token account of `owner` for `mint`,
+    /// `None` if the account does not exist
+    pub fn balance(&self, owner: &Pubkey, mint: &Pubkey) -> X402Result<Option<u64>> {
+        let state = self.state()?;
+
+        let balance = state.mints.get(mint).and_then(|mint_account| {
+            let address = associated_token_address(owner, mint, &mint_account.token_program);
+
+            state
+                .token_accounts
+                .get(&address)
+                .map(|account| account.amount)
+        });
+
+        Ok(balance)
+    }
+
+    /// Add a nonce account advanced by `authority` returning its durable nonce
+    pub fn add_nonce_account(&self, address: Pubkey, authority: Pubkey) -> X402Result<Hash> {
+        let durable_nonce = Hash::new_unique();
+        self.state()?
+            .nonce_accounts
+            .insert(address, SolanaNonceAccount::new(authority, durable_nonce));
+
+        Ok(durable_nonce)
+    }
+
+    /// Produce a new latest blockhash, older blockhashes stay valid until expired
+    pub fn advance_blockhash(&self) -> X402Result<Hash> {
+        let blockhash = Hash::new_unique();
+        self.state()?.blockhashes.push(blockhash);
+
+        Ok(blockhash)
+    }
+
+    /// Expire `blockhash` so transactions using it are rejected
+    pub fn expire_blockhash(&self, blockhash: &Hash) -> X402Result<()> {
+        self.state()?.blockhashes.retain(|valid| valid != blockhash);
+
+        Ok(())
+    }
+
+    /// Signatures of the transactions executed by the ledger
+    pub fn processed_transactions(&self) -> X402Result<Vec<Signature>> {
+        Ok(self.state()?.processed.iter().copied().collect())
+    }
+
+    /// The delegate and remaining delegated amount of the associated token account
+    /// of `owner` for `mint`
+    pub fn delegation(&self, owner: &Pubkey, mint: &Pubkey) -> X402Result<Option<(Pubkey, u64)>> {
+        let state = self.state()?;
+
+        let delegation = state.mints.get(mint).and_then(|mint_account| {
+            let address = associated_token_address(owner, mint, &mint_account.token_program);
+
+            state.delegations.get(&address).copied()
+        });
+
+        Ok(delegation)
+    }
+
+    fn state(&self) -> X402Result<MutexGuard<'_, LedgerState>> {
+        self.state.lock().map_err(|_| X402Error::RpcRequestError)
+    }
+
+    fn execute(&self, transaction: &VersionedTransaction) -> X402Result<Signature> {
+        let signature = transaction
+            .signatures
+            .first()
+            .copied()
+            .ok_or(X402Error::InvalidTransactionState)?;
+
+        if !transaction.verify_with_results().iter().all(|valid| *valid) {
+            return Err(X402Error::InvalidTransactionState);
+        }
+
+        let mut state = self.state()?;
+        let message = &transaction.message;
+
+        // A transaction starting with `AdvanceNonceAccount` may use the nonce as its blockhash
+        let uses_durable_nonce = message
+            .instructions()
+            .first()
+            .and_then(|instruction| {
+                SolanaDurableNonce::decode(message.static_account_keys(), instruction)
+            })
+            .and_then(|durable_nonce| state.nonce_accounts.get(&durable_nonce.account))
+            .is_some_and(|nonce_account| {
+                nonce_account.durable_nonce == *message.recent_blockhash()
+            });
+
+        if state.processed.contains(&signature)
+            || !(uses_durable_nonce || state.blockhashes.contains(message.recent_blockhash()))
+        {
+            return Err(X402Error::InvalidTransactionState);
+        }
+
+        // Execute on a copy so a failing instruction leaves the ledger untouched
+        let mut next = state.clone();
+        message
+            .instructions()
+            .iter()
+            .try_for_each(|instruction| next.execute_instruction(message, instruction))?;
+
+        next.processed.insert(signature);
+        *state = next;
+
+        Ok(signature)
+    }
+}
+
+impl Default for InMemorySolanaLedger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LedgerState {
+    fn create_associated_token_account(
+        &mut self,
+        owner: &Pubkey,
+        mint: &Pubkey,
+    ) -> X402Result<Pubkey> {
+        let token_program = self
+            .mints
+            .get(mint)
+            .ok_or(X402Error::InvalidTransactionState)?
+            .token_program;
+        let address = associated_token_address(owner, mint, &token_program);
+
+        self.token_accounts
+            .entry(address)
+            .or_insert(SolanaTokenAccount {
+                mint: *mint,
+                owner: *owner,
+                amount: 0,
+                token_program,
+            });
+
+        Ok(address)
+    }
+
+    fn execute_instruction(
+        &mut self,
+        message: &VersionedMessage,
+        instruction: &CompiledInstruction,
+    ) -> X402Result<()> {
+        let account_keys = message.static_account_keys();
+        let program =
+            *program_id(account_keys, instruction).ok_or(X402Error::InvalidTransactionState)?;
+
+        if program == solana_compute_budget_interface::id() {
+            Ok(())
+        } else if program == solana_sdk_ids::system_program::id() {
+            self.execute_advance_nonce(message, instruction)
+        } else if program == spl_associated_token_account::id() {
+            self.execute_create_ata(message, instruction)
+        } else if program == spl_token::id() || program == spl_token_2022::id() {
+            match TokenInstruction::unpack(&instruction.data) {
+                Ok(TokenInstruction::ApproveChecked { amount, decimals }) => {
+                    self.execute_approve_checked(message, instruction, &program, amount, decimals)
+                }
+                Ok(TokenInstruction::Revoke) => self.execute_revoke(message, instruction, &program),
+                Ok(TokenInstruction::TransferChecked { amount, decimals }) => self
+                    .execute_transfer_checked(
+                        message,
+                        instruction,
+                        &program,
+                        amount,
+                        decimals,
+                        Option::None,
+                    ),
+                Ok(TokenInstruction::TransferFeeExtension) if program == spl_token_2022::id() => {
+                    match TransferFeeInstruction::unpack(&instruction.data[1..]) {
+                        Ok(TransferFeeInstruction::TransferCheckedWithFee {
+                            amount,
+                            decimals,
+                            fee,
+                        }) => self.execute_transfer_checked(
+                            message,
+                            instruction,
+                            &program,
+                            amount,
+                            decimals,
+                            Some(fee),
+                        ),
+                        _ => Err(X402Error::InvalidTransactionState),
+                    }
+                }
+                _ => Err(X402Error::InvalidTransactionState),
+            }
+        } else {
+            Err(X402Error::InvalidTransactionState)
+        }
+    }
+
+    fn execute_create_ata(
+        &mut self,
+        message: &VersionedMessage,
+        instruction: &CompiledInstruction,
+    ) -> X402Result<()> {
+        let idempotent = match instruction.data.as_slice() {
+            [] => false,
+            data => match AssociatedTokenAccountInstruction::try_from_slice(data) {
+                Ok(AssociatedTokenAccountInstruction::Create) => false,
+                Ok(AssociatedTokenAccountInstruction::CreateIdempotent) => true,
+                _ => return Err(X402Error::InvalidTransactionState),
+            },
+        };
+
+        let [funder, ata, wallet, mint, _system_program, token_program] =
+            instruction_accounts::<6>(message.static_account_keys(), instruction)
+                .ok_or(X402Error::InvalidTransactionState)?;
+
+        let mint_account = self
+            .mints
+            .get(&mint)
+            .ok_or(X402Error::InvalidTransactionState)?;
+
+        if !is_signer(message, instruction, &funder)
+            || mint_account.token_program != token_program
+            || ata != associated_token_address(&wallet, &mint, &token_program)
+        {
+            return Err(X402Error::InvalidTransactionState);
+        }
+
+        match self.token_accounts.get(&ata) {
+            Some(existing) if idempotent && existing.owner == wallet && existing.mint == mint => {
+                Ok(())
+            }
+            Some(_) => Err(X402Error::InvalidTransactionState),
+            None => self
+                .create_associated_token_account(&wallet, &mint)
+                .map(|_| ()),
+        }
+    }
+
+    fn execute_advance_nonce(
+        &mut self,
+        message: &VersionedMessage,
+        instruction: &CompiledInstruction,
+    ) -> X402Result<()> {
+        let durable_nonce = SolanaDurableNonce::decode(message.static_account_keys(), instruction)
+            .ok_or(X402Error::InvalidTransactionState)?;
+
+        let nonce_account = self
+            .nonce_accounts
+            .get_mut(&durable_nonce.account)
+            .ok_or(X402Error::InvalidTransactionState)?;
+
+        if nonce_account.authority != durable_nonce.authority
+            || !is_signer(message, instruction, &durable_nonce.authority)
+        {
+            return Err(X402Error::InvalidTransactionState);
+        }
+
+        nonce_account.durable_nonce = Hash::new_unique();
+
+        Ok(())
+    }
+
+    fn execute_approve_checked(
+        &mut self,
+        message: &VersionedMessage,
+        instruction: &CompiledInstruction,
+        token_program: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> X402Result<()> {
+        let [source, mint, delegate, owner] =
+            instruction_accounts::<4>(message.static_account_keys(), instruction)
+                .ok_or(X402Error::InvalidTransactionState)?;
+
+        let mint_account = self
+            .mints
+            .get(&mint)
+            .ok_or(X402Error::InvalidTransactionState)?;
+        let source_account = self
+            .token_accounts
+            .get(&source)
+            .ok_or(X402Error::InvalidTransactionState)?;
+
+        let is_valid = mint_account.token_program == *token_program
+            && mint_account.decimals == decimals
+            && source_account.mint == mint
+            && source_account.owner == owner
+            && is_signer(message, instruction, &owner);
+
+        if !is_valid {
+            return Err(X402Error::InvalidTransactionState);
+        }
+
+        // Approving replaces any previous delegation of the account
+        self.delegations.insert(source, (delegate, amount));
+
+        Ok(())
+    }
+
+    fn execute_revoke(
+        &mut self,
+        message: &VersionedMessage,
+        instruction: &CompiledInstruction,
+        token_program: &Pubkey,
+    ) -> X402Result<()> {
+        let [source, authority] =
+            instruction_accounts::<2>(message.static_account_keys(), instruction)
+                .ok_or(X402Error::InvalidTransactionState)?;
+
+        let source_account = self
+            .token_accounts
+            .get(&source)
+            .ok_or(X402Error::InvalidTransactionState)?;
+
+        // Token-2022 also lets the delegate revoke its own delegation
+        let is_delegate = *token_program == spl_token_2022::id()
+            && self
+                .delegations
+                .get(&source)
+                .is_some_and(|(delegate, _)| *delegate == authority);
+
+        if (source_account.owner != authority && !is_delegate)
+            || !is_signer(message, instruction, &authority)
+        {
+            return Err(X402Error::InvalidTransactionState);
+        }
+
+        self.delegations.remove(&source);
+
+        Ok(())
+    }
+
+    fn execute_transfer_checked(
+        &mut self,
+        message: &VersionedMessage,
+        instruction: &CompiledInstruction,
+        token_program: &Pubkey,
+        amount: u64,
+        decimals: u8,
+        expected_fee: Option<u64>,
+    ) -> X402Result<()> {
+        let [source, mint, destination, authority] =
+            instruction_accounts::<4>(message.static_account_keys(), instruction)
+                .ok_or(X402Error::InvalidTransactionState)?;
+
+        let mint_account = self
+            .mints
+            .get(&mint)
+            .ok_or(X402Error::InvalidTransactionState)?;
+        let source_account = self
+            .token_accounts
+            .get(&source)
+            .ok_or(X402Error::InvalidTransactionState)?;
+        let destination_account = self
+            .token_accounts
+            .get(&destination)
+            .ok_or(X402Error::InvalidTransactionState)?;
+
+        let is_valid = mint_account.token_program == *token_program
+            && mint_account.decimals == decimals
+            && source_account.mint == mint
+            && destination_account.mint == mint
+            && is_signer(message, instruction, &authority)
+            && source_account.amount >= amount;
+
+        let is_delegate = match self.delegations.get(&source) {
+            Some((delegate, delegated)) => *delegate == authority && *delegated >= amount,
+            None => false,
+        };
+
+        if !is_valid || (source_account.owner != authority && !is_delegate) {
+            return Err(X402Error::InvalidTransactionState);
+        }
+
+        let extensions = mint_account.extensions;
+        let fee = extensions.transfer_fee_of(amount);
+
+        if extensions.ensure_transferable().is_err()
+            || expected_fee.is_some_and(|expected_fee| expected_fee != fee)
+        {
+            return Err(X402Error::InvalidTransactionState);
+        }
+
+        if source_account.owner != authority
+            && let Some((_, delegated)) = self.delegations.get_mut(&source)
+        {
+            *delegated -= amount;
+
+            if *delegated == 0 {
+                self.delegations.remove(&source);
+            }
+        }
+
+        if let Some(source_account) = self.token_accounts.get_mut(&source) {
+            source_account.amount -= amount;
+        }
+
+        if let Some(destination_account) = self.token_accounts.get_mut(&destination) {
+            // The fee is withheld in the destination account, it is not part of its balance
+            destination_account.amount = destination_account
+                .amount
+                .checked_add(
+                    amount
+                        .checked_sub(fee)
+                        .ok_or(X402Error::InvalidTransactionState)?,
+                )
+                .ok_or(X402Error::InvalidTransactionState)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether `account` is passed to the instruction and signed the transaction
+fn is_signer(
+    message: &VersionedMessage,
+    instruction: &CompiledInstruction,
+    account: &Pubkey,
+) -> bool {
+    let account_keys = message.static_account_keys();
+
+    instruction.accounts.iter().any(|index| {
+        let index = usize::from(*index);
+
+        account_keys.get(index) == Some(account) && message.is_signer(index)
+    })
+}
+
+impl SolanaRpcTransport for InMemorySolanaLedger {
+    fn send_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> impl Future<Output = X402Result<Signature>> + Send {
+        let outcome = self.execute(transaction);
+
+        async move { outcome }
+    }
+
+    fn confirm_transaction(
+        &self,
+        signature: &Signature,
+        _commitment: Commitment,
+        _timeout: Duration,
+    ) -> impl Future<Output = X402Result<()>> + Send {
+        let outcome = self.state().and_then(|state| {
+            state
+                .processed
+                .contains(signature)
+                .then_some(())
+                .ok_or(X402Error::InvalidTransactionState)
+        });
+
+        async move { outcome }
+    }
+}
+
+impl SolanaRpc for InMemorySolanaLedger {
+    fn get_latest_blockhash(&self) -> impl Future<Output = X402Result<Hash>> + Send {
+        let outcome = self.state().and_then(|state| {
+            state
+                .blockhashes
+                .last()
+                .copied()
+                .ok_or(X402Error::RpcRequestError)
+        });
+
+        async move { outcome }
+    }
+
+    fn is_blockhash_valid(
+        &self,
+        blockhash: &Hash,
+    ) -> impl Future<Output = X402Result<bool>> + Send {
+        let outcome = self
+            .state()
+            .map(|state| state.blockhashes.contains(blockhash));
+
+        async move { outcome }
+    }
+
+    fn get_mint(
+        &self,
+        mint: &Pubkey,
+    ) -> impl Future<Output = X402Result<Option<SolanaMintAccount>>> + Send {
+        let outcome = self.state().map(|state| state.mints.get(mint).copied());
+
+        async move { outcome }
+    }
+
+    fn get_token_account(
+        &self,
+        address: &Pubkey,
+    ) -> impl Future<Output = X402Result<Option<SolanaTokenAccount>>> + Send {
+        let outcome = self
+            .state()
+            .map(|state| state.token_accounts.get(address).copied());
+
+        async move { outcome }
+    }
+
+    fn get_nonce_account(
+        &self,
+        address: &Pubkey,
+    ) -> impl Future<Output = X402Result<Option<SolanaNonceAccount>>> + Send {
+        let outcome = self
+            .state()
+            .map(|state| state.nonce_accounts.get(address).copied());
+
+        async move { outcome }
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{X402Error, X402Result};
+
+/// The Token-2022 extensions of a mint that change how x402 payments are made.
+/// Legacy SPL Token mints have none of them.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
+pub struct SolanaMintExtensions {
+    /// The transfer fee in effect for the current epoch, withheld from the
+    /// amount credited to the recipient
+    pub transfer_fee: Option<SolanaTransferFee>,
+    /// Whether transfers invoke a transfer hook program
+    pub transfer_hook: bool,
+    /// Whether the tokens are soulbound and cannot be transferred
+    pub non_transferable: bool,
+    /// Whether the mint allows confidential transfers. Payments transfer public
+    /// balances which the extension leaves untouched.
+    pub confidential_transfer: bool,
+}
+
+impl SolanaMintExtensions {
+    /// Whether transfers of the mint can be verified and settled by x402.
+    /// Transfer hooks need accounts that payments do not carry and run arbitrary
+    /// programs, so they are rejected like non-transferable mints.
+    pub fn ensure_transferable(&self) -> X402Result<()> {
+        if self.non_transferable {
+            return Err(X402Error::NonTransferableMint);
+        }
+
+        if self.transfer_hook {
+            return Err(X402Error::UnsupportedMintTransferHook);
+        }
+
+        Ok(())
+    }
+
+    /// The fee withheld when transferring `amount`, zero without a transfer fee
+    pub fn transfer_fee_of(&self, amount: u64) -> u64 {
+        self.transfer_fee
+            .map_or(0, |transfer_fee| transfer_fee.calculate_fee(amount))
+    }
+}
+
+/// A transfer fee of the Token-2022 transfer fee extension
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
+pub struct SolanaTransferFee {
+    /// Fee in hundredths of a percent of the amount transferred
+    pub basis_points: u16,
+    /// Maximum fee of a transfer in atomic token units
+    pub maximum_fee: u64,
+}
+
+impl SolanaTransferFee {
+    const ONE_IN_BASIS_POINTS: u128 = 10_000;
+
+    pub fn new(basis_points: u16, maximum_fee: u64) -> Self {
+        Self {
+            basis_points,
+            maximum_fee,
+        }
+    }
+
+    /// The fee withheld when transferring `amount`, rounded up like the token program
+    pub fn calculate_fee(&self, amount: u64) -> u64 {
+        if self.basis_points == 0 || amount == 0 {
+            return 0;
+        }
+
+        let fee = (u128::from(amount) * u128::from(self.basis_points))
+            .div_ceil(Self::ONE_IN_BASIS_POINTS);
+
+        u64::try_from(fee).map_or(self.maximum_fee, |fee| fee.min(self.maximum_fee))
+    }
+
+    /// The smallest amount to transfer for the recipient to be credited `received`,
+    /// `None` if it overflows
+    pub fn calculate_pre_fee_amount(&self, received: u64) -> Option<u64> {
+        let basis_points = u128::from(self.basis_points);
+
+        match (basis_points, received) {
+            (0, _) => Some(received),
+            (_, 0) => Some(0),
+            (Self::ONE_IN_BASIS_POINTS.., _) => received.checked_add(self.maximum_fee),
+            _ => {
+                let amount = (u128::from(received) * Self::ONE_IN_BASIS_POINTS)
+                    .div_ceil(Self::ONE_IN_BASIS_POINTS - basis_points);
+
+                if amount - u128::from(received) >= u128::from(self.maximum_fee) {
+                    received.checked_add(self.maximum_fee)
+                } else {
+                    u64::try_from(amount).ok()
+                }
+            }
+        }
+    }
+}
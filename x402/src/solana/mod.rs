@@ -0,0 +1,38 @@
+mod utils;
+pub use utils::*;
+
+mod exact_transaction_builder;
+pub use exact_transaction_builder::*;
+
+mod exact_verifier;
+pub use exact_verifier::*;
+
+mod rpc_transport;
+pub use rpc_transport::*;
+
+mod exact_settler;
+pub use exact_settler::*;
+
+mod rpc;
+pub use rpc::*;
+
+mod mint_extensions;
+pub use mint_extensions::*;
+
+mod compute_budget;
+pub use compute_budget::*;
+
+mod durable_nonce;
+pub use durable_nonce::*;
+
+mod in_memory_ledger;
+pub use in_memory_ledger::*;
+
+mod upto_transaction_builder;
+pub use upto_transaction_builder::*;
+
+mod upto_verifier;
+pub use upto_verifier::*;
+
+mod upto_settler;
+pub use upto_settler::*;
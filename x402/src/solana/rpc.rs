@@ -0,0 +1,222 @@
+use core::future::Future;
+use std::sync::Arc;
+
+use solana_hash::Hash;
+use solana_nonce::{state::State, versions::Versions};
+use solana_pubkey::Pubkey;
+use spl_token_2022::{
+    extension::{
+        BaseStateWithExtensions, StateWithExtensions,
+        confidential_transfer::ConfidentialTransferMint, non_transferable::NonTransferable,
+        transfer_fee::TransferFeeConfig, transfer_hook::TransferHook,
+    },
+    state::Mint,
+};
+
+use crate::{SolanaMintExtensions, SolanaRpcTransport, SolanaTransferFee, X402Error, X402Result};
+
+/// The chain state queried by facilitators when verifying and settling payments,
+/// on top of submitting transactions through [SolanaRpcTransport].
+pub trait SolanaRpc: SolanaRpcTransport {
+    /// The most recent blockhash of the cluster
+    fn get_latest_blockhash(&self) -> impl Future<Output = X402Result<Hash>> + Send;
+
+    /// Whether transactions using `blockhash` can still be processed
+    fn is_blockhash_valid(&self, blockhash: &Hash)
+    -> impl Future<Output = X402Result<bool>> + Send;
+
+    /// The mint account at `mint` with the transfer fee of the current epoch,
+    /// `None` if the account does not exist
+    fn get_mint(
+        &self,
+        mint: &Pubkey,
+    ) -> impl Future<Output = X402Result<Option<SolanaMintAccount>>> + Send;
+
+    /// The token account at `address`, `None` if the account does not exist
+    fn get_token_account(
+        &self,
+        address: &Pubkey,
+    ) -> impl Future<Output = X402Result<Option<SolanaTokenAccount>>> + Send;
+
+    /// The nonce account at `address`, `None` if the account does not exist
+    fn get_nonce_account(
+        &self,
+        address: &Pubkey,
+    ) -> impl Future<Output = X402Result<Option<SolanaNonceAccount>>> + Send;
+}
+
+impl<T: SolanaRpc + Sync + ?Sized> SolanaRpc for &T {
+    fn get_latest_blockhash(&self) -> impl Future<Output = X402Result<Hash>> + Send {
+        (**self).get_latest_blockhash()
+    }
+
+    fn is_blockhash_valid(
+        &self,
+        blockhash: &Hash,
+    ) -> impl Future<Output = X402Result<bool>> + Send {
+        (**self).is_blockhash_valid(blockhash)
+    }
+
+    fn get_mint(
+        &self,
+        mint: &Pubkey,
+    ) -> impl Future<Output = X402Result<Option<SolanaMintAccount>>> + Send {
+        (**self).get_mint(mint)
+    }
+
+    fn get_token_account(
+        &self,
+        address: &Pubkey,
+    ) -> impl Future<Output = X402Result<Option<SolanaTokenAccount>>> + Send {
+        (**self).get_token_account(address)
+    }
+
+    fn get_nonce_account(
+        &self,
+        address: &Pubkey,
+    ) -> impl Future<Output = X402Result<Option<SolanaNonceAccount>>> + Send {
+        (**self).get_nonce_account(address)
+    }
+}
+
+impl<T: SolanaRpc + Send + Sync + ?Sized> SolanaRpc for Arc<T> {
+    fn get_latest_blockhash(&self) -> impl Future<Output = X402Result<Hash>> + Send {
+        (**self).get_latest_blockhash()
+    }
+
+    fn is_blockhash_valid(
+        &self,
+        blockhash: &Hash,
+    ) -> impl Future<Output = X402Result<bool>> + Send {
+        (**self).is_blockhash_valid(blockhash)
+    }
+
+    fn get_mint(
+        &self,
+        mint: &Pubkey,
+    ) -> impl Future<Output = X402Result<Option<SolanaMintAccount>>> + Send {
+        (**self).get_mint(mint)
+    }
+
+    fn get_token_account(
+        &self,
+        address: &Pubkey,
+    ) -> impl Future<Output = X402Result<Option<SolanaTokenAccount>>> + Send {
+        (**self).get_token_account(address)
+    }
+
+    fn get_nonce_account(
+        &self,
+        address: &Pubkey,
+    ) -> impl Future<Output = X402Result<Option<SolanaNonceAccount>>> + Send {
+        (**self).get_nonce_account(address)
+    }
+}
+
+/// The fields of a mint account used by x402
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct SolanaMintAccount {
+    /// Number of decimals of the token
+    pub decimals: u8,
+    /// The token program owning the mint, SPL Token or Token-2022
+    pub token_program: Pubkey,
+    /// The Token-2022 extensions of the mint
+    pub extensions: SolanaMintExtensions,
+}
+
+impl SolanaMintAccount {
+    /// A mint without extensions
+    pub fn new(decimals: u8, token_program: Pubkey) -> Self {
+        Self {
+            decimals,
+            token_program,
+            extensions: SolanaMintExtensions::default(),
+        }
+    }
+
+    /// Decode the `data` of a mint account owned by `token_program`,
+    /// the transfer fee is the one in effect at `epoch`
+    pub fn unpack(token_program: &Pubkey, data: &[u8], epoch: u64) -> X402Result<Self> {
+        if *token_program != spl_token::id() && *token_program != spl_token_2022::id() {
+            return Err(X402Error::InvalidMintAccount);
+        }
+
+        let mint =
+            StateWithExtensions::<Mint>::unpack(data).map_err(|_| X402Error::InvalidMintAccount)?;
+
+        let transfer_fee = mint
+            .get_extension::<TransferFeeConfig>()
+            .ok()
+            .map(|config| {
+                let transfer_fee = config.get_epoch_fee(epoch);
+
+                SolanaTransferFee::new(
+                    transfer_fee.transfer_fee_basis_points.into(),
+                    transfer_fee.maximum_fee.into(),
+                )
+            });
+        let transfer_hook = mint
+            .get_extension::<TransferHook>()
+            .is_ok_and(|hook| Option::<Pubkey>::from(hook.program_id).is_some());
+
+        Ok(Self {
+            decimals: mint.base.decimals,
+            token_program: *token_program,
+            extensions: SolanaMintExtensions {
+                transfer_fee,
+                transfer_hook,
+                non_transferable: mint.get_extension::<NonTransferable>().is_ok(),
+                confidential_transfer: mint.get_extension::<ConfidentialTransferMint>().is_ok(),
+            },
+        })
+    }
+}
+
+/// The fields of a token account used by x402
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct SolanaTokenAccount {
+    /// The mint of the tokens held
+    pub mint: Pubkey,
+    /// The wallet allowed to transfer the tokens
+    pub owner: Pubkey,
+    /// The balance in atomic token units
+    pub amount: u64,
+    /// The token program owning the account, SPL Token or Token-2022
+    pub token_program: Pubkey,
+}
+
+/// The fields of a durable nonce account used by x402
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct SolanaNonceAccount {
+    /// The authority allowed to advance the nonce
+    pub authority: Pubkey,
+    /// The nonce transactions use as their recent blockhash
+    pub durable_nonce: Hash,
+}
+
+impl SolanaNonceAccount {
+    pub fn new(authority: Pubkey, durable_nonce: Hash) -> Self {
+        Self {
+            authority,
+            durable_nonce,
+        }
+    }
+
+    /// Decode the `data` of an initialized nonce account owned by `owner`, the system program
+    pub fn unpack(owner: &Pubkey, data: &[u8]) -> X402Result<Self> {
+        if *owner != solana_sdk_ids::system_program::id() {
+            return Err(X402Error::InvalidNonceAccount);
+        }
+
+        // Legacy nonce accounts cannot be used by transactions until they are upgraded
+        match bincode::deserialize(data) {
+            Ok(Versions::Current(state)) => match *state {
+                State::Initialized(data) => {
+                    Ok(Self::new(data.authority, *data.durable_nonce.as_hash()))
+                }
+                State::Uninitialized => Err(X402Error::InvalidNonceAccount),
+            },
+            _ => Err(X402Error::InvalidNonceAccount),
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use core::{future::Future, time::Duration};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use solana_signature::Signature;
+use solana_transaction::versioned::VersionedTransaction;
+
+use crate::{X402Error, X402Result};
+
+/// The commitment level a transaction has reached on the cluster
+#[derive(
+    Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Commitment {
+    /// The transaction was processed by the leader
+    Processed,
+    /// The block containing the transaction was voted on by a supermajority
+    #[default]
+    Confirmed,
+    /// The block containing the transaction is rooted
+    Finalized,
+}
+
+impl Commitment {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Processed => "processed",
+            Self::Confirmed => "confirmed",
+            Self::Finalized => "finalized",
+        }
+    }
+}
+
+/// The RPC calls used to submit payment transactions to a Solana cluster.
+/// Implement this for the RPC client of choice.
+pub trait SolanaRpcTransport {
+    /// Submit a fully signed transaction returning its signature
+    fn send_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> impl Future<Output = X402Result<Signature>> + Send;
+
+    /// Wait until the transaction with `signature` reaches `commitment`.
+    /// Returns [X402Error::InvalidTransactionState] if the transaction failed and
+    /// [X402Error::TransactionConfirmationTimeout] if it was not confirmed within `timeout`.
+    fn confirm_transaction(
+        &self,
+        signature: &Signature,
+        commitment: Commitment,
+        timeout: Duration,
+    ) -> impl Future<Output = X402Result<()>> + Send;
+}
+
+impl<T: SolanaRpcTransport + Sync + ?Sized> SolanaRpcTransport for &T {
+    fn send_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> impl Future<Output = X402Result<Signature>> + Send {
+        (**self).send_transaction(transaction)
+    }
+
+    fn confirm_transaction(
+        &self,
+        signature: &Signature,
+        commitment: Commitment,
+        timeout: Duration,
+    ) -> impl Future<Output = X402Result<()>> + Send {
+        (**self).confirm_transaction(signature, commitment, timeout)
+    }
+}
+
+impl<T: SolanaRpcTransport + Send + Sync + ?Sized> SolanaRpcTransport for Arc<T> {
+    fn send_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> impl Future<Output = X402Result<Signature>> + Send {
+        (**self).send_transaction(transaction)
+    }
+
+    fn confirm_transaction(
+        &self,
+        signature: &Signature,
+        commitment: Commitment,
+        timeout: Duration,
+    ) -> impl Future<Output = X402Result<()>> + Send {
+        (**self).confirm_transaction(signature, commitment, timeout)
+    }
+}
+
+/// An offline [SolanaRpcTransport] that records the transactions it receives
+/// and confirms them immediately. Transactions whose signatures are not all
+/// valid are rejected like the cluster would.
+#[derive(Debug)]
+pub struct MockSolanaRpcTransport {
+    sent: Mutex<Vec<VersionedTransaction>>,
+    send_error: Option<X402Error>,
+    confirmation_error: Option<X402Error>,
+    reached_commitment: Commitment,
+}
+
+impl MockSolanaRpcTransport {
+    pub fn new() -> Self {
+        Self {
+            sent: Mutex::default(),
+            send_error: Option::None,
+            confirmation_error: Option::None,
+            reached_commitment: Commitment::Finalized,
+        }
+    }
+
+    /// Fail every `send_transaction` call with `error`
+    pub fn fail_send(mut self, error: X402Error) -> Self {
+        self.send_error.replace(error);
+
+        self
+    }
+
+    /// Fail every `confirm_transaction` call with `error`
+    pub fn fail_confirmation(mut self, error: X402Error) -> Self {
+        self.confirmation_error.replace(error);
+
+        self
+    }
+
+    /// The highest commitment transactions reach, waiting for a higher one times out
+    pub fn set_reached_commitment(mut self, commitment: Commitment) -> Self {
+        self.reached_commitment = commitment;
+
+        self
+    }
+
+    /// The transactions submitted so far
+    pub fn sent_transactions(&self) -> Vec<VersionedTransaction> {
+        self.sent
+            .lock()
+            .map(|sent| sent.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl Default for MockSolanaRpcTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SolanaRpcTransport for MockSolanaRpcTransport {
+    fn send_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> impl Future<Output = X402Result<Signature>> + Send {
+        let outcome = match self.send_error.clone() {
+            Some(error) => Err(error),
+            None if !transaction.verify_with_results().iter().all(|valid| *valid) => {
+                Err(X402Error::InvalidTransactionState)
+            }
+            None => self
+                .sent
+                .lock()
+                .map_err(|_| X402Error::RpcRequestError)
+                .and_then(|mut sent| {
+                    let signature = transaction
+                        .signatures
+                        .first()
+                        .copied()
+                        .ok_or(X402Error::InvalidTransactionState)?;
+                    sent.push(transaction.clone());
+
+                    Ok(signature)
+                }),
+        };
+
+        async move { outcome }
+    }
+
+    fn confirm_transaction(
+        &self,
+        signature: &Signature,
+        commitment: Commitment,
+        _timeout: Duration,
+    ) -> impl Future<Output = X402Result<()>> + Send {
+        let is_sent = self.sent.lock().is_ok_and(|sent| {
+            sent.iter()
+                .any(|transaction| transaction.signatures.first() == Some(signature))
+        });
+
+        let outcome = match self.confirmation_error.clone() {
+            Some(error) => Err(error),
+            None if !is_sent => Err(X402Error::InvalidTransactionState),
+            None if commitment > self.reached_commitment => {
+                Err(X402Error::TransactionConfirmationTimeout)
+            }
+            None => Ok(()),
+        };
+
+        async move { outcome }
+    }
+}
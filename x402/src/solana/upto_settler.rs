@@ -0,0 +1,177 @@
+use core::time::Duration;
+
+use solana_message::Message;
+use solana_signature::Signature;
+use solana_signer::Signer;
+use solana_transaction::{Transaction, versioned::VersionedTransaction};
+
+use crate::{
+    Commitment, ComputeBudgetCaps, SettlementResponse, SolanaRpc, UptoSvmVerifier, VerifyPayload,
+    X402Error, X402Result, X402Timestamp, sign_as_fee_payer,
+};
+
+/// Settles `upto` scheme payments on Solana for a facilitator.
+///
+/// The approval of the payer is verified again, co-signed with the fee payer keypair of
+/// the facilitator (`extra.feePayer`) and submitted. The `settlementAmount` of the
+/// [VerifyPayload], required and at most the approved cap, is then transferred to `payTo`
+/// by the fee payer as the delegate of the approval. The part of the cap left unused is
+/// revoked by the fee payer in the same transaction for Token-2022 mints. The token program
+/// only lets the owner revoke a delegation so for its mints the unused part stays delegated
+/// to the fee payer until the payer revokes it or approves another payment.
+#[derive(Debug)]
+pub struct UptoSvmSettler<R, S> {
+    rpc: R,
+    fee_payer: S,
+    commitment: Commitment,
+    confirmation_timeout: Duration,
+    compute_budget_caps: ComputeBudgetCaps,
+}
+
+impl<R, S> UptoSvmSettler<R, S>
+where
+    R: SolanaRpc,
+    S: Signer,
+{
+    /// Time to wait for each transaction to reach the commitment level
+    pub const DEFAULT_CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(60);
+
+    pub fn new(rpc: R, fee_payer: S) -> Self {
+        Self {
+            rpc,
+            fee_payer,
+            commitment: Commitment::default(),
+            confirmation_timeout: Self::DEFAULT_CONFIRMATION_TIMEOUT,
+            compute_budget_caps: ComputeBudgetCaps::default(),
+        }
+    }
+
+    pub fn set_commitment(&mut self, commitment: Commitment) -> &mut Self {
+        self.commitment = commitment;
+
+        self
+    }
+
+    pub fn set_confirmation_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.confirmation_timeout = timeout;
+
+        self
+    }
+
+    /// Set the largest compute budget of the payments co-signed by the fee payer
+    pub fn set_compute_budget_caps(&mut self, caps: ComputeBudgetCaps) -> &mut Self {
+        self.compute_budget_caps = caps;
+
+        self
+    }
+
+    pub fn rpc(&self) -> &R {
+        &self.rpc
+    }
+
+    pub fn fee_payer(&self) -> &S {
+        &self.fee_payer
+    }
+
+    pub fn commitment(&self) -> Commitment {
+        self.commitment
+    }
+
+    pub fn confirmation_timeout(&self) -> Duration {
+        self.confirmation_timeout
+    }
+
+    pub fn compute_budget_caps(&self) -> ComputeBudgetCaps {
+        self.compute_budget_caps
+    }
+
+    /// Submit the approval, transfer the settlement amount and revoke the rest of the
+    /// delegation. The signature of the settlement transaction is returned, or of the
+    /// approval when nothing is transferred or revoked.
+    pub async fn settle(
+        &self,
+        payload: &VerifyPayload<'_>,
+    ) -> X402Result<SettlementResponse<'static, ()>> {
+        let verified = UptoSvmVerifier::new(payload)
+            .set_compute_budget_caps(self.compute_budget_caps)
+            .verify()?;
+        let fee_payer = self.fee_payer.pubkey();
+
+        if &fee_payer != verified.fee_payer() {
+            return Err(X402Error::FeePayerKeypairMismatch);
+        }
+
+        let amount = payload
+            .settlement_amount
+            .filter(|amount| *amount <= verified.cap())
+            .ok_or(X402Error::InvalidUptoSvmSettlementAmount)?;
+
+        let mut instructions = Vec::new();
+        if amount > 0 {
+            instructions.push(
+                spl_token_2022::instruction::transfer_checked(
+                    verified.token_program(),
+                    verified.source(),
+                    verified.mint(),
+                    verified.destination(),
+                    &fee_payer,
+                    &[],
+                    amount,
+                    verified.decimals(),
+                )
+                .map_err(|_| X402Error::InvalidTransferInstruction)?,
+            );
+        }
+
+        // Settling the whole cap already ends the delegation
+        if amount < verified.cap() && *verified.token_program() == spl_token_2022::id() {
+            instructions.push(
+                spl_token_2022::instruction::revoke(
+                    verified.token_program(),
+                    verified.source(),
+                    &fee_payer,
+                    &[],
+                )
+                .map_err(|_| X402Error::InvalidTransferInstruction)?,
+            );
+        }
+
+        let payer = verified.payer().to_string();
+        let mut approval = verified.transaction_take();
+        sign_as_fee_payer(&mut approval, &self.fee_payer)
+            .map_err(|_| X402Error::InvalidUptoSvmPayloadTransactionSignature)?;
+
+        let mut signature = self.submit(&approval).await?;
+
+        if !instructions.is_empty() {
+            let recent_blockhash = self.rpc.get_latest_blockhash().await?;
+            let message =
+                Message::new_with_blockhash(&instructions, Some(&fee_payer), &recent_blockhash);
+
+            let mut transaction = Transaction::new_unsigned(message);
+            transaction
+                .try_sign(&[&self.fee_payer], recent_blockhash)
+                .map_err(|_| X402Error::TransactionSigningError)?;
+
+            signature = self.submit(&transaction.into()).await?;
+        }
+
+        let mut response = SettlementResponse::new(true);
+        response
+            .set_transaction_signature(signature.to_string())
+            .set_network(payload.payment_requirements.network())
+            .set_payer(payer)
+            .set_timestamp(X402Timestamp::now());
+
+        Ok(response)
+    }
+
+    async fn submit(&self, transaction: &VersionedTransaction) -> X402Result<Signature> {
+        let signature = self.rpc.send_transaction(transaction).await?;
+        self.rpc
+            .confirm_transaction(&signature, self.commitment, self.confirmation_timeout)
+            .await?;
+
+        Ok(signature)
+    }
+}
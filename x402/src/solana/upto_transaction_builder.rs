@@ -0,0 +1,163 @@
+use solana_hash::Hash;
+use solana_instruction::Instruction;
+use solana_message::Message;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+
+use crate::{
+    ComputeBudget, ComputeBudgetStrategy, PaymentRequirements, PaymentScheme, SolanaDurableNonce,
+    X402Error, X402Result, XPaymentPayload, associated_token_address, parse_solana_pubkey,
+    token_program_id,
+};
+
+/// Builds the transaction for the `upto` scheme on Solana.
+///
+/// The transaction contains the compute budget instructions followed by a single
+/// `ApproveChecked` instruction delegating up to `maxAmountRequired` of the `asset`
+/// in the associated token account of the signer to `extra.feePayer`. The facilitator
+/// submits the approval when settling and then transfers the amount actually used,
+/// at most the approved cap, as the delegate. [Self::set_durable_nonce] starts the
+/// transaction with an `AdvanceNonceAccount` instruction so it stays valid for the
+/// whole `maxTimeoutSeconds`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct UptoSvmTransactionBuilder<'x> {
+    requirements: &'x PaymentRequirements<'x>,
+    recent_blockhash: Option<Hash>,
+    compute_unit_limit: u32,
+    compute_unit_price: u64,
+    nonce_account: Option<Pubkey>,
+}
+
+impl<'x> UptoSvmTransactionBuilder<'x> {
+    /// Compute units requested for the transaction
+    pub const DEFAULT_COMPUTE_UNIT_LIMIT: u32 = 30_000;
+    /// Priority fee in micro-lamports per compute unit
+    pub const DEFAULT_COMPUTE_UNIT_PRICE: u64 = 1;
+
+    pub fn new(requirements: &'x PaymentRequirements<'x>) -> Self {
+        Self {
+            requirements,
+            recent_blockhash: Option::default(),
+            compute_unit_limit: Self::DEFAULT_COMPUTE_UNIT_LIMIT,
+            compute_unit_price: Self::DEFAULT_COMPUTE_UNIT_PRICE,
+            nonce_account: Option::default(),
+        }
+    }
+
+    pub fn set_recent_blockhash(&mut self, recent_blockhash: Hash) -> &mut Self {
+        self.recent_blockhash.replace(recent_blockhash);
+
+        self
+    }
+
+    /// Use the current `nonce` of `nonce_account` as the recent blockhash so the
+    /// transaction does not expire. The signer of the payment must be the authority
+    /// of the nonce account.
+    pub fn set_durable_nonce(&mut self, nonce_account: Pubkey, nonce: Hash) -> &mut Self {
+        self.nonce_account.replace(nonce_account);
+        self.recent_blockhash.replace(nonce);
+
+        self
+    }
+
+    pub fn set_compute_unit_limit(&mut self, compute_unit_limit: u32) -> &mut Self {
+        self.compute_unit_limit = compute_unit_limit;
+
+        self
+    }
+
+    pub fn set_compute_unit_price(&mut self, micro_lamports: u64) -> &mut Self {
+        self.compute_unit_price = micro_lamports;
+
+        self
+    }
+
+    /// Set the compute unit limit and price picked by `strategy`
+    pub fn set_compute_budget(&mut self, strategy: ComputeBudgetStrategy) -> &mut Self {
+        let compute_budget = strategy.compute_budget();
+        self.compute_unit_limit = compute_budget.unit_limit;
+        self.compute_unit_price = compute_budget.unit_price;
+
+        self
+    }
+
+    /// The compute unit limit and price of the transaction
+    pub fn compute_budget(&self) -> ComputeBudget {
+        ComputeBudget::new(self.compute_unit_limit, self.compute_unit_price)
+    }
+
+    /// The payment requirements the transaction is built for
+    pub fn requirements(&self) -> &PaymentRequirements<'x> {
+        self.requirements
+    }
+
+    /// Build the instructions of the payment where `owner` is the wallet paying for the resource
+    pub fn instructions(&self, owner: &impl Signer) -> X402Result<Vec<Instruction>> {
+        let extra = self.requirements.extra();
+
+        let owner = owner.pubkey();
+        let mint = parse_solana_pubkey(self.requirements.asset())?;
+        let delegate = parse_solana_pubkey(extra.fee_payer())?;
+        let token_program = token_program_id(extra.token_extensions_mint());
+
+        let source = associated_token_address(&owner, &mint, &token_program);
+
+        let approve = spl_token_2022::instruction::approve_checked(
+            &token_program,
+            &source,
+            &mint,
+            &delegate,
+            &owner,
+            &[],
+            self.requirements.max_amount_required(),
+            extra.decimals(),
+        )
+        .map_err(|_| X402Error::InvalidTransferInstruction)?;
+
+        let mut instructions = self.durable_nonce_instructions(&owner);
+        instructions.extend(self.compute_budget().instructions());
+        instructions.push(approve);
+
+        Ok(instructions)
+    }
+
+    /// The `AdvanceNonceAccount` instruction of the durable nonce advanced by `owner`, if any
+    fn durable_nonce_instructions(&self, owner: &Pubkey) -> Vec<Instruction> {
+        self.nonce_account
+            .map(|nonce_account| SolanaDurableNonce::new(nonce_account, *owner).instruction())
+            .into_iter()
+            .collect()
+    }
+
+    /// Build the transaction and partially sign it with the `owner` of the tokens
+    pub fn build(&self, owner: &impl Signer) -> X402Result<Transaction> {
+        let recent_blockhash = self
+            .recent_blockhash
+            .ok_or(X402Error::RecentBlockhashIsMissing)?;
+        let fee_payer = parse_solana_pubkey(self.requirements.extra().fee_payer())?;
+
+        let message = Message::new_with_blockhash(
+            &self.instructions(owner)?,
+            Some(&fee_payer),
+            &recent_blockhash,
+        );
+
+        let mut transaction = Transaction::new_unsigned(message);
+        transaction
+            .try_partial_sign(&[owner], recent_blockhash)
+            .map_err(|_| X402Error::TransactionSigningError)?;
+
+        Ok(transaction)
+    }
+
+    /// Build the partially signed transaction and wrap it in the payload of the `X-PAYMENT` header
+    pub fn build_payload(&self, owner: &impl Signer) -> X402Result<XPaymentPayload<'static>> {
+        let transaction = bincode::serialize(&self.build(owner)?)
+            .map_err(|_| X402Error::TransactionSerializationError)?;
+
+        Ok(XPaymentPayload::new(transaction)
+            .set_scheme(PaymentScheme::Upto)
+            .set_network(self.requirements.network()))
+    }
+}
@@ -0,0 +1,348 @@
+use solana_compute_budget_interface::ComputeBudgetInstruction;
+use solana_pubkey::Pubkey;
+use solana_transaction::versioned::VersionedTransaction;
+use spl_token_2022::instruction::TokenInstruction;
+
+use crate::{
+    ComputeBudget, ComputeBudgetCaps, ExactSvmVerifier, PaymentScheme, SolanaDurableNonce,
+    SolanaRpc, VerifyPayload, VerifyPayloadResponse, X402Error, X402Result,
+    associated_token_address, decode_compute_budget, decode_svm_transaction,
+    ensure_fee_payer_not_in_instructions, instruction_accounts, is_signed_except_fee_payer,
+    parse_solana_pubkey, program_id, token_program_id,
+};
+
+/// Verifies the transaction of an `upto` scheme payment on Solana against the
+/// payment requirements it is paying for.
+///
+/// The transaction must contain, in order, a `SetComputeUnitLimit` instruction,
+/// a `SetComputeUnitPrice` instruction and a single `ApproveChecked` instruction
+/// delegating exactly the `maxAmountRequired` cap of the `asset` to `extra.feePayer`.
+/// It may be preceded by an `AdvanceNonceAccount` instruction whose nonce is the recent blockhash.
+/// The compute unit limit and price must be within the [ComputeBudgetCaps] of the facilitator.
+/// The fee payer must be `extra.feePayer`, which is not allowed in any other account of the
+/// instructions than the delegate, and every other signer must have signed.
+///
+/// [Self::verify] only checks the transaction itself, [Self::verify_with_rpc]
+/// also checks the chain state like balances and the validity of the blockhash or durable nonce.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct UptoSvmVerifier<'x> {
+    payload: &'x VerifyPayload<'x>,
+    caps: ComputeBudgetCaps,
+}
+
+impl<'x> UptoSvmVerifier<'x> {
+    /// Number of instructions of the transaction, not counting the
+    /// `AdvanceNonceAccount` instruction of a durable nonce
+    pub const INSTRUCTIONS: usize = 3;
+
+    pub fn new(payload: &'x VerifyPayload<'x>) -> Self {
+        Self {
+            payload,
+            caps: ComputeBudgetCaps::default(),
+        }
+    }
+
+    /// Set the largest compute unit limit and price accepted, [ComputeBudgetCaps::default] unless set
+    pub fn set_compute_budget_caps(mut self, caps: ComputeBudgetCaps) -> Self {
+        self.caps = caps;
+
+        self
+    }
+
+    /// Verify the payment and return the response of the `/verify` API
+    pub fn verify_response(&self) -> VerifyPayloadResponse<'static> {
+        match self.verify() {
+            Ok(verified) => VerifyPayloadResponse::valid(verified.payer().to_string()),
+            Err(error) => VerifyPayloadResponse::invalid(&error, self.payer_hint()),
+        }
+    }
+
+    /// Verify the payment returning the decoded transaction and approval details
+    pub fn verify(&self) -> X402Result<VerifiedUptoSvmPayment> {
+        let payment = &self.payload.payment_payload;
+        let requirements = &self.payload.payment_requirements;
+        let extra = requirements.extra();
+
+        if requirements.scheme() != PaymentScheme::Upto || payment.scheme() != requirements.scheme()
+        {
+            return Err(X402Error::InvalidScheme);
+        }
+
+        if !requirements.network().is_solana() || payment.network() != requirements.network() {
+            return Err(X402Error::InvalidNetwork);
+        }
+
+        let transaction = decode_svm_transaction(payment.transaction())
+            .map_err(|_| X402Error::InvalidUptoSvmPayloadTransaction)?;
+        let message = &transaction.message;
+
+        if message.sanitize().is_err()
+            || message
+                .address_table_lookups()
+                .is_some_and(|lookups| !lookups.is_empty())
+        {
+            return Err(X402Error::InvalidUptoSvmPayloadTransaction);
+        }
+
+        let account_keys = message.static_account_keys();
+        let fee_payer = parse_solana_pubkey(extra.fee_payer())?;
+
+        if account_keys.first() != Some(&fee_payer) {
+            return Err(X402Error::InvalidUptoSvmPayloadTransactionFeePayerMismatch);
+        }
+
+        // A durable nonce transaction starts with `AdvanceNonceAccount`, followed by the payment
+        let durable_nonce = message
+            .instructions()
+            .first()
+            .and_then(|instruction| SolanaDurableNonce::decode(account_keys, instruction));
+        let offset = usize::from(durable_nonce.is_some());
+        let instructions = &message.instructions()[offset..];
+        if instructions.len() != Self::INSTRUCTIONS {
+            return Err(X402Error::InvalidUptoSvmPayloadTransactionInstructionsLength);
+        }
+
+        let compute_budget = match (
+            decode_compute_budget(account_keys, &instructions[0]),
+            decode_compute_budget(account_keys, &instructions[1]),
+        ) {
+            (
+                Some(ComputeBudgetInstruction::SetComputeUnitLimit(unit_limit)),
+                Some(ComputeBudgetInstruction::SetComputeUnitPrice(unit_price)),
+            ) => ComputeBudget::new(unit_limit, unit_price),
+            _ => return Err(X402Error::InvalidUptoSvmPayloadTransactionComputeBudgetInstruction),
+        };
+        self.caps.ensure_within(&compute_budget)?;
+
+        let mint = parse_solana_pubkey(requirements.asset())?;
+        let pay_to = parse_solana_pubkey(requirements.pay_to())?;
+        let token_program = token_program_id(extra.token_extensions_mint());
+
+        // The fee payer may only be the delegate of the approval
+        ensure_fee_payer_not_in_instructions(
+            account_keys,
+            message.instructions(),
+            &fee_payer,
+            &[(offset + 2, &token_program, 2)],
+        )?;
+
+        let approve = &instructions[2];
+        if program_id(account_keys, approve) != Some(&token_program) {
+            return Err(X402Error::InvalidUptoSvmPayloadTransactionInstructionNotApproveChecked);
+        }
+
+        let (cap, decimals) = match TokenInstruction::unpack(&approve.data) {
+            Ok(TokenInstruction::ApproveChecked { amount, decimals }) => (amount, decimals),
+            _ => {
+                return Err(
+                    X402Error::InvalidUptoSvmPayloadTransactionInstructionNotApproveChecked,
+                );
+            }
+        };
+
+        let [source, approve_mint, delegate, owner] =
+            instruction_accounts::<4>(account_keys, approve)
+                .ok_or(X402Error::InvalidUptoSvmPayloadTransactionInstructionNotApproveChecked)?;
+
+        if approve_mint != mint {
+            return Err(X402Error::InvalidUptoSvmPayloadTransactionAssetMismatch);
+        }
+
+        if source == associated_token_address(&fee_payer, &mint, &token_program) {
+            return Err(X402Error::FeePayerNotAllowedInInstruction);
+        }
+
+        if delegate != fee_payer {
+            return Err(X402Error::InvalidUptoSvmPayloadTransactionDelegateMismatch);
+        }
+
+        if cap != requirements.max_amount_required() {
+            return Err(X402Error::InvalidUptoSvmPayloadTransactionCapMismatch);
+        }
+
+        if decimals != extra.decimals() {
+            return Err(X402Error::InvalidUptoSvmPayloadTransactionDecimalsMismatch);
+        }
+
+        if !is_signed_except_fee_payer(&transaction) {
+            return Err(X402Error::InvalidUptoSvmPayloadTransactionSignature);
+        }
+
+        Ok(VerifiedUptoSvmPayment {
+            transaction,
+            payer: owner,
+            fee_payer,
+            mint,
+            source,
+            destination: associated_token_address(&pay_to, &mint, &token_program),
+            cap,
+            decimals,
+            token_program,
+            durable_nonce,
+        })
+    }
+
+    /// Verify the payment and return the response of the `/verify` API,
+    /// checking the chain state through `rpc` as in [Self::verify_with_rpc]
+    pub async fn verify_response_with_rpc(
+        &self,
+        rpc: &impl SolanaRpc,
+    ) -> VerifyPayloadResponse<'static> {
+        match self.verify_with_rpc(rpc).await {
+            Ok(verified) => VerifyPayloadResponse::valid(verified.payer().to_string()),
+            Err(error) => VerifyPayloadResponse::invalid(&error, self.payer_hint()),
+        }
+    }
+
+    /// Verify the payment like [Self::verify] and check that it can be settled
+    /// against the current chain state: the blockhash is still valid or the durable
+    /// nonce is the current nonce of its account, the mint exists with the required
+    /// decimals and without a transfer fee or extensions preventing the transfer, the
+    /// payer holds at least the cap and the associated token account of `payTo` exists.
+    pub async fn verify_with_rpc(
+        &self,
+        rpc: &impl SolanaRpc,
+    ) -> X402Result<VerifiedUptoSvmPayment> {
+        let verified = self.verify()?;
+
+        let recent_blockhash = verified.transaction().message.recent_blockhash();
+        match verified.durable_nonce() {
+            Some(durable_nonce) => durable_nonce.verify_with_rpc(rpc, recent_blockhash).await?,
+            None if !rpc.is_blockhash_valid(recent_blockhash).await? => {
+                return Err(X402Error::InvalidTransactionState);
+            }
+            None => (),
+        }
+
+        let mint = rpc
+            .get_mint(verified.mint())
+            .await?
+            .ok_or(X402Error::InvalidUptoSvmPayloadTransactionAssetMismatch)?;
+
+        if mint.token_program != verified.token_program {
+            return Err(X402Error::InvalidUptoSvmPayloadTransactionAssetMismatch);
+        }
+
+        if mint.decimals != verified.decimals {
+            return Err(X402Error::InvalidUptoSvmPayloadTransactionDecimalsMismatch);
+        }
+
+        mint.extensions.ensure_transferable()?;
+
+        // The settlement amount is only known after the request, a fee would be withheld from it
+        if mint
+            .extensions
+            .transfer_fee
+            .is_some_and(|transfer_fee| transfer_fee.basis_points > 0)
+        {
+            return Err(X402Error::UnsupportedMintTransferFee);
+        }
+
+        let source = rpc.get_token_account(verified.source()).await?;
+
+        // A token account of the fee payer may not be debited, even by a delegate
+        if source
+            .as_ref()
+            .is_some_and(|source| &source.owner == verified.fee_payer())
+        {
+            return Err(X402Error::FeePayerNotAllowedInInstruction);
+        }
+
+        let source = source
+            .filter(|source| &source.mint == verified.mint() && &source.owner == verified.payer())
+            .ok_or(X402Error::InvalidUptoSvmPayloadTransactionSenderAtaNotFound)?;
+
+        if source.amount < verified.cap() {
+            return Err(X402Error::InsufficientFunds);
+        }
+
+        if rpc
+            .get_token_account(verified.destination())
+            .await?
+            .is_none()
+        {
+            return Err(X402Error::InvalidUptoSvmPayloadTransactionReceiverAtaNotFound);
+        }
+
+        Ok(verified)
+    }
+
+    /// The owner of the approval if it can be decoded, used to report the payer
+    /// of a payment that failed verification
+    pub(crate) fn payer_hint(&self) -> String {
+        // The owner is the fourth account of `ApproveChecked` like the authority of `TransferChecked`
+        ExactSvmVerifier::new(self.payload).payer_hint()
+    }
+}
+
+/// An `upto` payment transaction that passed verification
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct VerifiedUptoSvmPayment {
+    transaction: VersionedTransaction,
+    payer: Pubkey,
+    fee_payer: Pubkey,
+    mint: Pubkey,
+    source: Pubkey,
+    destination: Pubkey,
+    cap: u64,
+    decimals: u8,
+    token_program: Pubkey,
+    durable_nonce: Option<SolanaDurableNonce>,
+}
+
+impl VerifiedUptoSvmPayment {
+    /// The decoded approval transaction, only partially signed by the payer
+    pub fn transaction(&self) -> &VersionedTransaction {
+        &self.transaction
+    }
+
+    /// The decoded approval transaction but consumes self
+    pub fn transaction_take(self) -> VersionedTransaction {
+        self.transaction
+    }
+
+    /// The durable nonce used in place of a recent blockhash, if any
+    pub fn durable_nonce(&self) -> Option<&SolanaDurableNonce> {
+        self.durable_nonce.as_ref()
+    }
+
+    /// The owner of the tokens approving the delegation
+    pub fn payer(&self) -> &Pubkey {
+        &self.payer
+    }
+
+    /// The fee payer of the transaction (`extra.feePayer`) and delegate of the approval
+    pub fn fee_payer(&self) -> &Pubkey {
+        &self.fee_payer
+    }
+
+    /// The mint of the tokens approved
+    pub fn mint(&self) -> &Pubkey {
+        &self.mint
+    }
+
+    /// The token account the approval is for and the tokens are transferred from
+    pub fn source(&self) -> &Pubkey {
+        &self.source
+    }
+
+    /// The associated token account of `payTo`
+    pub fn destination(&self) -> &Pubkey {
+        &self.destination
+    }
+
+    /// The maximum amount approved in atomic token units
+    pub fn cap(&self) -> u64 {
+        self.cap
+    }
+
+    /// The decimals of the mint
+    pub fn decimals(&self) -> u8 {
+        self.decimals
+    }
+
+    /// The token program of the mint
+    pub fn token_program(&self) -> &Pubkey {
+        &self.token_program
+    }
+}
@@ -0,0 +1,157 @@
+use core::str::FromStr;
+
+use base64ct::{Base64, Encoding};
+use borsh::BorshDeserialize;
+use solana_compute_budget_interface::ComputeBudgetInstruction;
+use solana_message::compiled_instruction::CompiledInstruction;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+use solana_transaction::versioned::VersionedTransaction;
+
+use crate::{X402Error, X402Result, XPaymentTransaction};
+
+/// Parse a base58 encoded address into a Solana public key
+pub fn parse_solana_pubkey(address: &str) -> X402Result<Pubkey> {
+    Pubkey::from_str(address).map_err(|_| X402Error::InvalidSolanaPublicKey)
+}
+
+/// The token program that owns the mint. Token-2022 (token extensions) mints
+/// and legacy SPL Token mints live under different program ids.
+pub fn token_program_id(token_extensions_mint: bool) -> Pubkey {
+    if token_extensions_mint {
+        spl_token_2022::id()
+    } else {
+        spl_token::id()
+    }
+}
+
+/// Derive the associated token account of `wallet` for `mint` under the given token program
+pub fn associated_token_address(wallet: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address_with_program_id(
+        wallet,
+        mint,
+        token_program,
+    )
+}
+
+/// Decode the base64 encoded transaction of the `X-PAYMENT` payload
+pub fn decode_svm_transaction(
+    payload: &XPaymentTransaction<'_>,
+) -> X402Result<VersionedTransaction> {
+    let transaction = Base64::decode_vec(payload.transaction())
+        .map_err(|_| X402Error::InvalidExactSvmPayloadTransaction)?;
+
+    bincode::deserialize(&transaction).map_err(|_| X402Error::InvalidExactSvmPayloadTransaction)
+}
+
+/// The program invoked by a compiled instruction
+pub(crate) fn program_id<'a>(
+    account_keys: &'a [Pubkey],
+    instruction: &CompiledInstruction,
+) -> Option<&'a Pubkey> {
+    account_keys.get(usize::from(instruction.program_id_index))
+}
+
+/// Resolve the first `N` accounts of a compiled instruction
+pub(crate) fn instruction_accounts<const N: usize>(
+    account_keys: &[Pubkey],
+    instruction: &CompiledInstruction,
+) -> Option<[Pubkey; N]> {
+    let mut accounts = [Pubkey::default(); N];
+
+    for (account, index) in accounts.iter_mut().zip(instruction.accounts.get(..N)?) {
+        *account = *account_keys.get(usize::from(*index))?;
+    }
+
+    Some(accounts)
+}
+
+/// Decode a compute budget instruction, `None` for instructions of other programs
+pub(crate) fn decode_compute_budget(
+    account_keys: &[Pubkey],
+    instruction: &CompiledInstruction,
+) -> Option<ComputeBudgetInstruction> {
+    if program_id(account_keys, instruction) != Some(&solana_compute_budget_interface::id()) {
+        return None;
+    }
+
+    ComputeBudgetInstruction::try_from_slice(&instruction.data).ok()
+}
+
+/// Fails with [X402Error::FeePayerNotAllowedInInstruction] if `fee_payer` is the program or an
+/// account of an instruction, except at the `allowed` positions given as the index of the
+/// instruction, the program it invokes and the index of the account in the instruction.
+/// The fee payer signs the whole transaction at settlement, so it must never be able to
+/// authorize a transfer, an approval or the closure of one of its accounts.
+pub(crate) fn ensure_fee_payer_not_in_instructions(
+    account_keys: &[Pubkey],
+    instructions: &[CompiledInstruction],
+    fee_payer: &Pubkey,
+    allowed: &[(usize, &Pubkey, usize)],
+) -> X402Result<()> {
+    for (index, instruction) in instructions.iter().enumerate() {
+        let program = program_id(account_keys, instruction);
+
+        if program == Some(fee_payer) {
+            return Err(X402Error::FeePayerNotAllowedInInstruction);
+        }
+
+        for (position, account) in instruction.accounts.iter().enumerate() {
+            if account_keys.get(usize::from(*account)) != Some(fee_payer) {
+                continue;
+            }
+
+            let is_allowed =
+                allowed
+                    .iter()
+                    .any(|&(allowed_index, allowed_program, allowed_position)| {
+                        allowed_index == index
+                            && program == Some(allowed_program)
+                            && allowed_position == position
+                    });
+
+            if !is_allowed {
+                return Err(X402Error::FeePayerNotAllowedInInstruction);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether every required signer except the fee payer, which signs at settlement, has signed
+pub(crate) fn is_signed_except_fee_payer(transaction: &VersionedTransaction) -> bool {
+    let message = &transaction.message;
+    let required_signatures = usize::from(message.header().num_required_signatures);
+
+    if transaction.signatures.len() != required_signatures {
+        return false;
+    }
+
+    let message_data = message.serialize();
+
+    transaction
+        .signatures
+        .iter()
+        .zip(message.static_account_keys())
+        .skip(1)
+        .all(|(signature, signer)| signature.verify(signer.as_ref(), &message_data))
+}
+
+/// Add the signature of the `fee_payer` to a transaction partially signed by the payer.
+/// Fails with [X402Error::TransactionSigningError] if the transaction has no signatures.
+pub(crate) fn sign_as_fee_payer(
+    transaction: &mut VersionedTransaction,
+    fee_payer: &impl Signer,
+) -> X402Result<()> {
+    let fee_payer_signature = fee_payer
+        .try_sign_message(&transaction.message.serialize())
+        .map_err(|_| X402Error::TransactionSigningError)?;
+    let signature = transaction
+        .signatures
+        .first_mut()
+        .ok_or(X402Error::TransactionSigningError)?;
+    *signature = fee_payer_signature;
+
+    Ok(())
+}
@@ -11,10 +11,36 @@ pub struct SupportedBlockchains<'x> {
     kinds: Cow<'x, [BlockchainKind<'x>]>,
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Serialize, Deserialize)]
+impl<'x> SupportedBlockchains<'x> {
+    /// Convert into a list that owns its strings
+    pub fn into_owned(self) -> SupportedBlockchains<'static> {
+        SupportedBlockchains {
+            kinds: Cow::Owned(
+                self.kinds
+                    .into_owned()
+                    .into_iter()
+                    .map(BlockchainKind::into_owned)
+                    .collect(),
+            ),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Serialize, Deserialize)]
 pub struct BlockchainKind<'x> {
     x402_version: u8,
     scheme: PaymentScheme,
     #[serde(borrow)]
-    network: &'x str,
+    network: Cow<'x, str>,
+}
+
+impl<'x> BlockchainKind<'x> {
+    /// Convert into a kind that owns its network
+    pub fn into_owned(self) -> BlockchainKind<'static> {
+        BlockchainKind {
+            x402_version: self.x402_version,
+            scheme: self.scheme,
+            network: Cow::Owned(self.network.into_owned()),
+        }
+    }
 }
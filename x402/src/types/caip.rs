@@ -0,0 +1,353 @@
+use std::{borrow::Cow, fmt};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{BlockchainNetwork, X402Error, X402Network, X402Result, parse_solana_pubkey};
+
+/// A CAIP-2 chain id, `namespace:reference` (e.g. `solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp`, `eip155:8453`)
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+pub struct Caip2ChainId<'x> {
+    namespace: Cow<'x, str>,
+    reference: Cow<'x, str>,
+}
+
+impl<'x> Caip2ChainId<'x> {
+    pub const SOLANA_NAMESPACE: &'static str = "solana";
+    pub const EIP155_NAMESPACE: &'static str = "eip155";
+
+    pub fn new(
+        namespace: impl Into<Cow<'x, str>>,
+        reference: impl Into<Cow<'x, str>>,
+    ) -> X402Result<Self> {
+        let chain_id = Self {
+            namespace: namespace.into(),
+            reference: reference.into(),
+        };
+
+        if !is_caip_namespace(&chain_id.namespace) || !is_caip2_reference(&chain_id.reference) {
+            return Err(X402Error::InvalidCaip2ChainId);
+        }
+
+        Ok(chain_id)
+    }
+
+    pub fn parse(value: &'x str) -> X402Result<Self> {
+        let (namespace, reference) = value
+            .split_once(':')
+            .ok_or(X402Error::InvalidCaip2ChainId)?;
+
+        Self::new(namespace, reference)
+    }
+
+    pub fn namespace(&self) -> &str {
+        self.namespace.as_ref()
+    }
+
+    pub fn reference(&self) -> &str {
+        self.reference.as_ref()
+    }
+
+    pub fn is_solana(&self) -> bool {
+        self.namespace() == Self::SOLANA_NAMESPACE
+    }
+
+    pub fn is_eip155(&self) -> bool {
+        self.namespace() == Self::EIP155_NAMESPACE
+    }
+
+    /// The x402 network of the chain id, [X402Error::InvalidNetwork] for unsupported chains
+    pub fn network(&self) -> X402Result<X402Network> {
+        X402Network::try_from(self.to_string().as_str())
+    }
+
+    pub fn into_owned(self) -> Caip2ChainId<'static> {
+        Caip2ChainId {
+            namespace: Cow::Owned(self.namespace.into_owned()),
+            reference: Cow::Owned(self.reference.into_owned()),
+        }
+    }
+}
+
+impl From<X402Network> for Caip2ChainId<'static> {
+    fn from(network: X402Network) -> Self {
+        let (namespace, reference) = network.chain().split_once(':').unwrap_or_default();
+
+        Self {
+            namespace: Cow::Owned(namespace.to_string()),
+            reference: Cow::Owned(reference.to_string()),
+        }
+    }
+}
+
+impl TryFrom<&Caip2ChainId<'_>> for X402Network {
+    type Error = X402Error;
+
+    fn try_from(chain_id: &Caip2ChainId<'_>) -> Result<Self, Self::Error> {
+        chain_id.network()
+    }
+}
+
+impl<'x> TryFrom<&'x str> for Caip2ChainId<'x> {
+    type Error = X402Error;
+
+    fn try_from(value: &'x str) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl fmt::Display for Caip2ChainId<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.namespace, self.reference)
+    }
+}
+
+/// A CAIP-10 account id, `chain_id:address`
+/// (e.g. `eip155:8453:0xab16a96D359eC26a11e2C2b3d8f8B8942d5Bfcdb`)
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+pub struct Caip10AccountId<'x> {
+    chain_id: Caip2ChainId<'x>,
+    address: Cow<'x, str>,
+}
+
+impl<'x> Caip10AccountId<'x> {
+    /// The account id of `address` on `network`, the address is validated
+    /// as a Solana public key or a hex encoded EVM address
+    pub fn new(
+        network: impl Into<X402Network>,
+        address: impl Into<Cow<'x, str>>,
+    ) -> X402Result<Self> {
+        Self::from_parts(network.into().into(), address)
+    }
+
+    pub fn from_parts(
+        chain_id: Caip2ChainId<'x>,
+        address: impl Into<Cow<'x, str>>,
+    ) -> X402Result<Self> {
+        let address = address.into();
+
+        if !is_caip_address(&address) || !is_chain_address(&chain_id, &address) {
+            return Err(X402Error::InvalidCaip10AccountId);
+        }
+
+        Ok(Self { chain_id, address })
+    }
+
+    pub fn parse(value: &'x str) -> X402Result<Self> {
+        let (chain_id, address) = value
+            .rsplit_once(':')
+            .ok_or(X402Error::InvalidCaip10AccountId)?;
+        let chain_id =
+            Caip2ChainId::parse(chain_id).map_err(|_| X402Error::InvalidCaip10AccountId)?;
+
+        Self::from_parts(chain_id, address)
+    }
+
+    pub fn chain_id(&self) -> &Caip2ChainId<'x> {
+        &self.chain_id
+    }
+
+    pub fn address(&self) -> &str {
+        self.address.as_ref()
+    }
+
+    pub fn network(&self) -> X402Result<X402Network> {
+        self.chain_id.network()
+    }
+
+    pub fn into_owned(self) -> Caip10AccountId<'static> {
+        Caip10AccountId {
+            chain_id: self.chain_id.into_owned(),
+            address: Cow::Owned(self.address.into_owned()),
+        }
+    }
+}
+
+impl<'x> TryFrom<&'x str> for Caip10AccountId<'x> {
+    type Error = X402Error;
+
+    fn try_from(value: &'x str) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl fmt::Display for Caip10AccountId<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.chain_id, self.address)
+    }
+}
+
+/// A CAIP-19 asset id of a fungible asset, `chain_id/asset_namespace:asset_reference`
+/// (e.g. `solana:EtWTRABZaYq6iMfeYKouRu166VU2xqa1/token:4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU`)
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+pub struct Caip19AssetId<'x> {
+    chain_id: Caip2ChainId<'x>,
+    asset_namespace: Cow<'x, str>,
+    asset_reference: Cow<'x, str>,
+}
+
+impl<'x> Caip19AssetId<'x> {
+    /// The asset namespace of SPL Token and Token-2022 mints
+    pub const SOLANA_TOKEN_NAMESPACE: &'static str = "token";
+    /// The asset namespace of ERC-20 token contracts
+    pub const ERC20_NAMESPACE: &'static str = "erc20";
+
+    /// The asset id of the token `asset` on `network`, a mint address on Solana
+    /// and a token contract address on EVM chains
+    pub fn new(
+        network: impl Into<X402Network>,
+        asset: impl Into<Cow<'x, str>>,
+    ) -> X402Result<Self> {
+        let network = network.into();
+        let asset_namespace = match network {
+            X402Network::Solana(_) => Self::SOLANA_TOKEN_NAMESPACE,
+            X402Network::Evm(_) => Self::ERC20_NAMESPACE,
+        };
+
+        Self::from_parts(network.into(), asset_namespace, asset)
+    }
+
+    pub fn from_parts(
+        chain_id: Caip2ChainId<'x>,
+        asset_namespace: impl Into<Cow<'x, str>>,
+        asset_reference: impl Into<Cow<'x, str>>,
+    ) -> X402Result<Self> {
+        let asset_id = Self {
+            chain_id,
+            asset_namespace: asset_namespace.into(),
+            asset_reference: asset_reference.into(),
+        };
+
+        if !is_caip_namespace(&asset_id.asset_namespace)
+            || !is_caip_address(&asset_id.asset_reference)
+        {
+            return Err(X402Error::InvalidCaip19AssetId);
+        }
+
+        let is_token = (asset_id.chain_id.is_solana()
+            && asset_id.asset_namespace == Self::SOLANA_TOKEN_NAMESPACE)
+            || (asset_id.chain_id.is_eip155() && asset_id.asset_namespace == Self::ERC20_NAMESPACE);
+
+        if is_token && !is_chain_address(&asset_id.chain_id, &asset_id.asset_reference) {
+            return Err(X402Error::InvalidCaip19AssetId);
+        }
+
+        Ok(asset_id)
+    }
+
+    pub fn parse(value: &'x str) -> X402Result<Self> {
+        let (chain_id, asset) = value
+            .split_once('/')
+            .ok_or(X402Error::InvalidCaip19AssetId)?;
+        let (asset_namespace, asset_reference) = asset
+            .split_once(':')
+            .ok_or(X402Error::InvalidCaip19AssetId)?;
+        let chain_id =
+            Caip2ChainId::parse(chain_id).map_err(|_| X402Error::InvalidCaip19AssetId)?;
+
+        Self::from_parts(chain_id, asset_namespace, asset_reference)
+    }
+
+    pub fn chain_id(&self) -> &Caip2ChainId<'x> {
+        &self.chain_id
+    }
+
+    pub fn asset_namespace(&self) -> &str {
+        self.asset_namespace.as_ref()
+    }
+
+    /// The mint or token contract address for token assets
+    pub fn asset_reference(&self) -> &str {
+        self.asset_reference.as_ref()
+    }
+
+    pub fn network(&self) -> X402Result<X402Network> {
+        self.chain_id.network()
+    }
+
+    pub fn into_owned(self) -> Caip19AssetId<'static> {
+        Caip19AssetId {
+            chain_id: self.chain_id.into_owned(),
+            asset_namespace: Cow::Owned(self.asset_namespace.into_owned()),
+            asset_reference: Cow::Owned(self.asset_reference.into_owned()),
+        }
+    }
+}
+
+impl<'x> TryFrom<&'x str> for Caip19AssetId<'x> {
+    type Error = X402Error;
+
+    fn try_from(value: &'x str) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl fmt::Display for Caip19AssetId<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{}:{}",
+            self.chain_id, self.asset_namespace, self.asset_reference
+        )
+    }
+}
+
+macro_rules! caip_serde {
+    ($caip:ident) => {
+        impl Serialize for $caip<'_> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $caip<'static> {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = String::deserialize(deserializer)?;
+
+                $caip::parse(value.as_str())
+                    .map($caip::into_owned)
+                    .map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+caip_serde!(Caip2ChainId);
+caip_serde!(Caip10AccountId);
+caip_serde!(Caip19AssetId);
+
+/// `[-a-z0-9]{3,8}`
+fn is_caip_namespace(value: &str) -> bool {
+    (3..=8).contains(&value.len())
+        && value
+            .bytes()
+            .all(|byte| byte == b'-' || byte.is_ascii_lowercase() || byte.is_ascii_digit())
+}
+
+/// `[-_a-zA-Z0-9]{1,32}`
+fn is_caip2_reference(value: &str) -> bool {
+    (1..=32).contains(&value.len())
+        && value
+            .bytes()
+            .all(|byte| byte == b'-' || byte == b'_' || byte.is_ascii_alphanumeric())
+}
+
+/// `[-.%a-zA-Z0-9]{1,128}`
+fn is_caip_address(value: &str) -> bool {
+    (1..=128).contains(&value.len())
+        && value
+            .bytes()
+            .all(|byte| matches!(byte, b'-' | b'.' | b'%') || byte.is_ascii_alphanumeric())
+}
+
+/// Addresses of the Solana and EIP-155 namespaces are checked with the rules of the chain
+fn is_chain_address(chain_id: &Caip2ChainId<'_>, address: &str) -> bool {
+    if chain_id.is_solana() {
+        parse_solana_pubkey(address).is_ok()
+    } else if chain_id.is_eip155() {
+        address.len() == 42
+            && address.starts_with("0x")
+            && address[2..].bytes().all(|byte| byte.is_ascii_hexdigit())
+    } else {
+        true
+    }
+}
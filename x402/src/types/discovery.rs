@@ -2,23 +2,27 @@ use std::borrow::Cow;
 
 use serde::{Deserialize, Serialize};
 
-use crate::PaymentRequirements;
+use crate::{
+    PaymentRequirements, X402Timestamp, deserialize_unix_timestamp, serialize_unix_timestamp,
+};
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Serialize, Deserialize)]
 pub struct ResourceInfo<'x> {
     /// The resource URL or identifier being monetized
     #[serde(borrow)]
-    pub resource: &'x str,
+    pub resource: Cow<'x, str>,
     /// Resource type (currently "http" for HTTP endpoints)
     #[serde(borrow)]
-    pub r#type: Option<&'x str>,
+    pub r#type: Option<Cow<'x, str>>,
     /// Protocol version supported by the resource
     pub x402_version: u8,
     /// Array of PaymentRequirements objects specifying payment methods
     #[serde(borrow)]
     pub accepts: Cow<'x, [PaymentRequirements<'x>]>,
-    /// Unix timestamp of when the resource was last updated
-    pub last_updated: u64,
+    /// When the resource was last updated, as seconds since the Unix epoch on the wire
+    #[serde(serialize_with = "serialize_unix_timestamp")]
+    #[serde(deserialize_with = "deserialize_unix_timestamp")]
+    pub last_updated: X402Timestamp,
     /// Additional metadata (category, provider, etc.)
     #[serde(borrow)]
     pub metadata: Option<ResourceInfoMetadata<'x>>,
@@ -30,28 +34,79 @@ pub struct ResourceInfo<'x> {
     pub description: Option<Cow<'x, str>>,
 }
 
-#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Deserialize, Serialize)]
+impl<'x> ResourceInfo<'x> {
+    /// Convert into a resource that owns its strings
+    pub fn into_owned(self) -> ResourceInfo<'static> {
+        ResourceInfo {
+            resource: Cow::Owned(self.resource.into_owned()),
+            r#type: self.r#type.map(|r#type| Cow::Owned(r#type.into_owned())),
+            x402_version: self.x402_version,
+            accepts: Cow::Owned(
+                self.accepts
+                    .into_owned()
+                    .into_iter()
+                    .map(PaymentRequirements::into_owned)
+                    .collect(),
+            ),
+            last_updated: self.last_updated,
+            metadata: self.metadata.map(ResourceInfoMetadata::into_owned),
+            header_image: self
+                .header_image
+                .map(|header_image| Cow::Owned(header_image.into_owned())),
+            title: self.title.map(|title| Cow::Owned(title.into_owned())),
+            description: self
+                .description
+                .map(|description| Cow::Owned(description.into_owned())),
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ResourceInfoMetadata<'x> {
     #[serde(borrow)]
-    pub category: &'x str,
+    pub category: Cow<'x, str>,
     #[serde(borrow)]
-    pub provider: &'x str,
+    pub provider: Cow<'x, str>,
     #[serde(borrow)]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub agent_card: Option<&'x str>,
+    pub agent_card: Option<Cow<'x, str>>,
+}
+impl<'x> ResourceInfoMetadata<'x> {
+    /// Convert into metadata that owns its strings
+    pub fn into_owned(self) -> ResourceInfoMetadata<'static> {
+        ResourceInfoMetadata {
+            category: Cow::Owned(self.category.into_owned()),
+            provider: Cow::Owned(self.provider.into_owned()),
+            agent_card: self
+                .agent_card
+                .map(|agent_card| Cow::Owned(agent_card.into_owned())),
+        }
+    }
 }
-#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Serialize, Deserialize)]
+
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Serialize, Deserialize)]
 pub struct DiscoveryRequest<'x> {
     /// Filter by resource type (e.g., "http")
     #[serde(borrow)]
-    pub r#type: &'x str,
+    pub r#type: Cow<'x, str>,
     /// Maximum number of results to return (1-100); defaults to `20`
     pub limit: u64,
     /// Number of results to skip for pagination; defaults to `0`
     pub offset: Option<u64>,
 }
 
+impl<'x> DiscoveryRequest<'x> {
+    /// Convert into a request that owns its strings
+    pub fn into_owned(self) -> DiscoveryRequest<'static> {
+        DiscoveryRequest {
+            r#type: Cow::Owned(self.r#type.into_owned()),
+            limit: self.limit,
+            offset: self.offset,
+        }
+    }
+}
+
 /// GET /discovery/resources
 /// ## Example usage API
 /// ### Discover financial data APIs
@@ -67,6 +122,23 @@ pub struct DiscoveryPayload<'x> {
     pub pagination: PayloadPagination,
 }
 
+impl<'x> DiscoveryPayload<'x> {
+    /// Convert into a payload that owns its strings
+    pub fn into_owned(self) -> DiscoveryPayload<'static> {
+        DiscoveryPayload {
+            x402_version: self.x402_version,
+            items: Cow::Owned(
+                self.items
+                    .into_owned()
+                    .into_iter()
+                    .map(ResourceInfo::into_owned)
+                    .collect(),
+            ),
+            pagination: self.pagination,
+        }
+    }
+}
+
 #[derive(
     Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Serialize, Deserialize,
 )]
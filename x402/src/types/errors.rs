@@ -4,7 +4,7 @@ pub type X402Result<T> = Result<T, X402Error>;
 
 /// The x402 protocol defines standard error codes that may be returned by facilitators or resource servers.
 /// These error codes help clients understand why a payment failed and take appropriate action.
-#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+#[derive(Debug, PartialEq, Eq, Clone, thiserror::Error)]
 pub enum X402Error {
     #[error("The mime type is not supported. Only JSON and Binary")]
     UnsupportedX402MimeType,
@@ -53,6 +53,131 @@ pub enum X402Error {
     /// `unexpected_settle_error`: Unexpected error occurred during payment settlement
     #[error("`unexpected_settle_error`: Unexpected error occurred during payment settlement")]
     UnexpectedSettleError,
+    /// `invalid_exact_svm_payload_transaction` error. The transaction could not be decoded
+    #[error("The payment transaction could not be decoded")]
+    InvalidExactSvmPayloadTransaction,
+    /// `invalid_exact_svm_payload_transaction_instructions_length` error. The transaction contains an unexpected number of instructions
+    #[error("The payment transaction contains an unexpected number of instructions")]
+    InvalidExactSvmPayloadTransactionInstructionsLength,
+    /// `invalid_exact_svm_payload_transaction_instructions_compute_limit_instruction` error. The first instruction is not a valid `SetComputeUnitLimit`
+    #[error("The first instruction of the payment transaction is not `SetComputeUnitLimit`")]
+    InvalidExactSvmPayloadTransactionComputeLimitInstruction,
+    /// `invalid_exact_svm_payload_transaction_instructions_compute_price_instruction` error. The second instruction is not a valid `SetComputeUnitPrice`
+    #[error("The second instruction of the payment transaction is not `SetComputeUnitPrice`")]
+    InvalidExactSvmPayloadTransactionComputePriceInstruction,
+    /// `invalid_exact_svm_payload_transaction_create_ata_instruction` error. The associated token account instruction is invalid
+    #[error("The create associated token account instruction is invalid")]
+    InvalidExactSvmPayloadTransactionCreateAtaInstruction,
+    /// `invalid_exact_svm_payload_transaction_create_ata_instruction_incorrect_payee` error. The associated token account is not created for `payTo`
+    #[error("The associated token account is not created for the recipient of the payment")]
+    InvalidExactSvmPayloadTransactionCreateAtaInstructionIncorrectPayee,
+    /// `invalid_exact_svm_payload_transaction_create_ata_instruction_incorrect_asset` error. The associated token account is not created for the `asset`
+    #[error("The associated token account is not created for the asset of the payment")]
+    InvalidExactSvmPayloadTransactionCreateAtaInstructionIncorrectAsset,
+    /// `invalid_exact_svm_payload_transaction_instruction_not_spl_token_transfer_checked` error. The transfer is not an SPL Token `TransferChecked`
+    #[error("The transfer instruction is not an SPL Token `TransferChecked` instruction")]
+    InvalidExactSvmPayloadTransactionInstructionNotSplTokenTransferChecked,
+    /// `invalid_exact_svm_payload_transaction_instruction_not_token_2022_transfer_checked` error. The transfer is not a Token-2022 `TransferChecked`
+    #[error("The transfer instruction is not a Token-2022 `TransferChecked` instruction")]
+    InvalidExactSvmPayloadTransactionInstructionNotToken2022TransferChecked,
+    /// `invalid_exact_svm_payload_transaction_asset_mismatch` error. The mint of the transfer does not match the `asset`
+    #[error("The mint of the transfer does not match the asset of the payment requirements")]
+    InvalidExactSvmPayloadTransactionAssetMismatch,
+    /// `invalid_exact_svm_payload_transaction_transfer_to_incorrect_ata` error. The destination is not the associated token account of `payTo`
+    #[error("The transfer destination is not the associated token account of the recipient")]
+    InvalidExactSvmPayloadTransactionTransferToIncorrectAta,
+    /// `invalid_exact_svm_payload_transaction_amount_mismatch` error. The amount transferred is not `maxAmountRequired`
+    #[error("The amount transferred does not match the amount required")]
+    InvalidExactSvmPayloadTransactionAmountMismatch,
+    /// `invalid_exact_svm_payload_transaction_decimals_mismatch` error. The decimals of the transfer do not match `extra.decimals`
+    #[error("The decimals of the transfer do not match the decimals of the asset")]
+    InvalidExactSvmPayloadTransactionDecimalsMismatch,
+    /// `invalid_exact_svm_payload_transaction_transfer_fee_mismatch` error. The fee of the transfer is not the transfer fee of the `asset`, the recipient would not receive `maxAmountRequired`
+    #[error("The transfer fee does not match the transfer fee of the asset")]
+    InvalidExactSvmPayloadTransactionTransferFeeMismatch,
+    /// `invalid_exact_svm_payload_transaction_fee_payer_mismatch` error. The fee payer of the transaction is not `extra.feePayer`
+    #[error(
+        "The fee payer of the transaction does not match the fee payer of the payment requirements"
+    )]
+    InvalidExactSvmPayloadTransactionFeePayerMismatch,
+    /// `invalid_exact_svm_payload_transaction_signature` error. The transaction is not correctly signed by the payer
+    #[error("The payment transaction is not correctly signed by the payer")]
+    InvalidExactSvmPayloadTransactionSignature,
+    /// `invalid_exact_svm_payload_transaction_sender_ata_not_found` error. The token account the payment is transferred from does not exist
+    #[error("The token account of the payer does not exist")]
+    InvalidExactSvmPayloadTransactionSenderAtaNotFound,
+    /// `invalid_exact_svm_payload_transaction_receiver_ata_not_found` error. The associated token account of `payTo` does not exist and is not created by the transaction
+    #[error("The associated token account of the recipient does not exist")]
+    InvalidExactSvmPayloadTransactionReceiverAtaNotFound,
+    /// `invalid_upto_svm_payload_transaction` error. The transaction could not be decoded
+    #[error("The upto payment transaction could not be decoded")]
+    InvalidUptoSvmPayloadTransaction,
+    /// `invalid_upto_svm_payload_transaction_instructions_length` error. The transaction contains an unexpected number of instructions
+    #[error("The upto payment transaction contains an unexpected number of instructions")]
+    InvalidUptoSvmPayloadTransactionInstructionsLength,
+    /// `invalid_upto_svm_payload_transaction_compute_budget_instruction` error. The first two instructions are not `SetComputeUnitLimit` and `SetComputeUnitPrice`
+    #[error("The compute budget instructions of the upto payment transaction are invalid")]
+    InvalidUptoSvmPayloadTransactionComputeBudgetInstruction,
+    /// `invalid_upto_svm_payload_transaction_instruction_not_approve_checked` error. The approval is not an `ApproveChecked` of the token program of the `asset`
+    #[error("The approval instruction is not an `ApproveChecked` instruction of the token program")]
+    InvalidUptoSvmPayloadTransactionInstructionNotApproveChecked,
+    /// `invalid_upto_svm_payload_transaction_asset_mismatch` error. The mint of the approval does not match the `asset`
+    #[error("The mint of the approval does not match the asset of the payment requirements")]
+    InvalidUptoSvmPayloadTransactionAssetMismatch,
+    /// `invalid_upto_svm_payload_transaction_delegate_mismatch` error. The approval does not delegate to `extra.feePayer`
+    #[error(
+        "The approval does not delegate the tokens to the fee payer of the payment requirements"
+    )]
+    InvalidUptoSvmPayloadTransactionDelegateMismatch,
+    /// `invalid_upto_svm_payload_transaction_cap_mismatch` error. The amount approved is not the `maxAmountRequired` cap
+    #[error("The amount approved does not match the maximum amount of the payment requirements")]
+    InvalidUptoSvmPayloadTransactionCapMismatch,
+    /// `invalid_upto_svm_payload_transaction_decimals_mismatch` error. The decimals of the approval do not match `extra.decimals`
+    #[error("The decimals of the approval do not match the decimals of the asset")]
+    InvalidUptoSvmPayloadTransactionDecimalsMismatch,
+    /// `invalid_upto_svm_payload_transaction_fee_payer_mismatch` error. The fee payer of the transaction is not `extra.feePayer`
+    #[error(
+        "The fee payer of the upto payment transaction does not match the fee payer of the payment requirements"
+    )]
+    InvalidUptoSvmPayloadTransactionFeePayerMismatch,
+    /// `invalid_upto_svm_payload_transaction_signature` error. The transaction is not correctly signed by the payer
+    #[error("The upto payment transaction is not correctly signed by the payer")]
+    InvalidUptoSvmPayloadTransactionSignature,
+    /// `invalid_upto_svm_payload_transaction_sender_ata_not_found` error. The token account the approval is for does not exist
+    #[error("The token account of the payer does not exist")]
+    InvalidUptoSvmPayloadTransactionSenderAtaNotFound,
+    /// `invalid_upto_svm_payload_transaction_receiver_ata_not_found` error. The associated token account of `payTo` does not exist
+    #[error("The associated token account of the recipient does not exist")]
+    InvalidUptoSvmPayloadTransactionReceiverAtaNotFound,
+    /// `invalid_upto_svm_settlement_amount` error. The amount settled is missing or larger than the approved `maxAmountRequired` cap
+    #[error("The amount settled is missing or exceeds the maximum amount approved by the payer")]
+    InvalidUptoSvmSettlementAmount,
+    /// `payment_already_used` error. The payment was already used for another request
+    #[error("The payment was already used for another request")]
+    PaymentAlreadyUsed,
+    /// `non_transferable_mint` error. The tokens of the `asset` cannot be transferred
+    #[error("The tokens of the asset are non-transferable")]
+    NonTransferableMint,
+    /// `unsupported_mint_transfer_hook` error. Transfers of the `asset` invoke a transfer hook program
+    #[error("Transfers of the asset invoke a transfer hook which is not supported")]
+    UnsupportedMintTransferHook,
+    /// `unsupported_mint_transfer_fee` error. The `asset` charges a transfer fee the scheme cannot account for
+    #[error("The asset charges a transfer fee which is not supported by the scheme")]
+    UnsupportedMintTransferFee,
+    /// `compute_unit_limit_exceeded` error. The compute unit limit of the transaction is above the cap of the facilitator
+    #[error("The compute unit limit of the transaction exceeds the cap of the facilitator")]
+    ComputeUnitLimitExceeded,
+    /// `compute_unit_price_exceeded` error. The compute unit price of the transaction is above the cap of the facilitator
+    #[error("The compute unit price of the transaction exceeds the cap of the facilitator")]
+    ComputeUnitPriceExceeded,
+    /// `fee_payer_not_allowed_in_instruction` error. `extra.feePayer` is an account of an instruction
+    /// other than as the funder of the recipient token account or the delegate of an approval
+    #[error("The fee payer of the facilitator is not allowed as an account of the instruction")]
+    FeePayerNotAllowedInInstruction,
+    /// `invalid_durable_nonce` error. The nonce account of the transaction does not exist, has
+    /// another authority or its nonce was advanced past the one of the transaction
+    #[error("The durable nonce of the transaction is not the current nonce of its nonce account")]
+    InvalidDurableNonce,
     #[error("Unsupported error")]
     UnsupportedX402Error,
     #[error("The maximum amount required is missing. Unable to build the payment requirements.")]
@@ -81,6 +206,82 @@ pub enum X402Error {
     ExtraIsMissing,
     #[error("The status code received after the `settle` API was called is invalid")]
     UnsupportedX402StatusCodeError,
+    #[error("The address is not a valid base58 encoded Solana public key")]
+    InvalidSolanaPublicKey,
+    #[error("The recent blockhash is missing. Unable to build the payment transaction.")]
+    RecentBlockhashIsMissing,
+    #[error("Unable to construct the token transfer instruction for the payment transaction")]
+    InvalidTransferInstruction,
+    #[error("Unable to sign the payment transaction")]
+    TransactionSigningError,
+    #[error("Unable to serialize the payment transaction")]
+    TransactionSerializationError,
+    #[error("The fee payer keypair of the facilitator is not the fee payer of the transaction")]
+    FeePayerKeypairMismatch,
+    #[error("The payment transaction was not confirmed before the confirmation timeout")]
+    TransactionConfirmationTimeout,
+    #[error("The RPC request failed")]
+    RpcRequestError,
+    #[error("The base URL or headers of the facilitator client are invalid")]
+    InvalidFacilitatorClientConfig,
+    #[error("The request to the facilitator failed")]
+    FacilitatorRequestError,
+    #[error("The response of the facilitator could not be decoded")]
+    InvalidFacilitatorResponse,
+    #[error("The request to the paid resource failed")]
+    PaymentRequestError,
+    #[error("The payment requirements of the 402 response could not be decoded")]
+    InvalidPaymentRequirementsResponse,
+    #[error("None of the accepted payment requirements satisfy the payment policy")]
+    NoAcceptablePaymentRequirements,
+    #[error("The amount paid with the transfer fee of the token exceeds the payment policy")]
+    PaymentPolicyAmountExceeded,
+    #[error("The payment was rejected by the resource server")]
+    PaymentRejected,
+    #[error("The X-PAYMENT-RESPONSE header could not be decoded")]
+    InvalidPaymentResponseHeader,
+    #[error("The x402 header value is larger than the maximum header value length")]
+    HeaderValueTooLarge,
+    #[error("The x402 header value is not valid base64")]
+    InvalidHeaderValueBase64,
+    #[error("The x402 header value is not valid JSON of the expected type")]
+    InvalidHeaderValueJson,
+    #[error("The address is not a valid hex encoded EVM address")]
+    InvalidEvmAddress,
+    #[error("The private key is not a valid secp256k1 private key")]
+    InvalidEvmPrivateKey,
+    #[error(
+        "The name and version of the EIP-712 domain are missing from the payment requirements extras"
+    )]
+    Eip712DomainIsMissing,
+    #[error("The nonce is missing. Unable to build the payment authorization.")]
+    EvmNonceIsMissing,
+    #[error("The value is not a valid CAIP-2 chain id")]
+    InvalidCaip2ChainId,
+    #[error("The value is not a valid CAIP-10 account id")]
+    InvalidCaip10AccountId,
+    #[error("The value is not a valid CAIP-19 asset id")]
+    InvalidCaip19AssetId,
+    #[error("The replay guard was unable to read or record the payments already used")]
+    ReplayGuardError,
+    #[error("Only successful settlements with a transaction signature have a payment receipt")]
+    UnsettledPaymentReceipt,
+    #[error("The payment receipts could not be stored, read or exported")]
+    ReceiptStoreError,
+    #[error("The timestamp is not a valid RFC 3339 date and time after the Unix epoch")]
+    InvalidTimestamp,
+    #[error("The token amount is not a decimal number within the precision and range of the token")]
+    InvalidTokenAmount,
+    #[error(
+        "The decimals of the token amount do not match the decimals of the payment requirements"
+    )]
+    TokenAmountDecimalsMismatch,
+    #[error("The token is not a known asset on the network")]
+    UnknownAsset,
+    #[error("The account is not a mint of the SPL Token or Token-2022 program")]
+    InvalidMintAccount,
+    #[error("The account is not an initialized nonce account of the system program")]
+    InvalidNonceAccount,
 }
 
 impl TryFrom<&str> for X402Error {
@@ -111,6 +312,101 @@ impl TryFrom<&str> for X402Error {
             "invalid_transaction_state" => Self::InvalidTransactionState,
             "unexpected_verify_error" => Self::UnexpectedVerifyError,
             "unexpected_settle_error" => Self::UnexpectedSettleError,
+            "invalid_exact_svm_payload_transaction" => Self::InvalidExactSvmPayloadTransaction,
+            "invalid_exact_svm_payload_transaction_instructions_length" => {
+                Self::InvalidExactSvmPayloadTransactionInstructionsLength
+            }
+            "invalid_exact_svm_payload_transaction_instructions_compute_limit_instruction" => {
+                Self::InvalidExactSvmPayloadTransactionComputeLimitInstruction
+            }
+            "invalid_exact_svm_payload_transaction_instructions_compute_price_instruction" => {
+                Self::InvalidExactSvmPayloadTransactionComputePriceInstruction
+            }
+            "invalid_exact_svm_payload_transaction_create_ata_instruction" => {
+                Self::InvalidExactSvmPayloadTransactionCreateAtaInstruction
+            }
+            "invalid_exact_svm_payload_transaction_create_ata_instruction_incorrect_payee" => {
+                Self::InvalidExactSvmPayloadTransactionCreateAtaInstructionIncorrectPayee
+            }
+            "invalid_exact_svm_payload_transaction_create_ata_instruction_incorrect_asset" => {
+                Self::InvalidExactSvmPayloadTransactionCreateAtaInstructionIncorrectAsset
+            }
+            "invalid_exact_svm_payload_transaction_instruction_not_spl_token_transfer_checked" => {
+                Self::InvalidExactSvmPayloadTransactionInstructionNotSplTokenTransferChecked
+            }
+            "invalid_exact_svm_payload_transaction_instruction_not_token_2022_transfer_checked" => {
+                Self::InvalidExactSvmPayloadTransactionInstructionNotToken2022TransferChecked
+            }
+            "invalid_exact_svm_payload_transaction_asset_mismatch" => {
+                Self::InvalidExactSvmPayloadTransactionAssetMismatch
+            }
+            "invalid_exact_svm_payload_transaction_transfer_to_incorrect_ata" => {
+                Self::InvalidExactSvmPayloadTransactionTransferToIncorrectAta
+            }
+            "invalid_exact_svm_payload_transaction_amount_mismatch" => {
+                Self::InvalidExactSvmPayloadTransactionAmountMismatch
+            }
+            "invalid_exact_svm_payload_transaction_decimals_mismatch" => {
+                Self::InvalidExactSvmPayloadTransactionDecimalsMismatch
+            }
+            "invalid_exact_svm_payload_transaction_transfer_fee_mismatch" => {
+                Self::InvalidExactSvmPayloadTransactionTransferFeeMismatch
+            }
+            "invalid_exact_svm_payload_transaction_fee_payer_mismatch" => {
+                Self::InvalidExactSvmPayloadTransactionFeePayerMismatch
+            }
+            "invalid_exact_svm_payload_transaction_signature" => {
+                Self::InvalidExactSvmPayloadTransactionSignature
+            }
+            "invalid_exact_svm_payload_transaction_sender_ata_not_found" => {
+                Self::InvalidExactSvmPayloadTransactionSenderAtaNotFound
+            }
+            "invalid_exact_svm_payload_transaction_receiver_ata_not_found" => {
+                Self::InvalidExactSvmPayloadTransactionReceiverAtaNotFound
+            }
+            "invalid_upto_svm_payload_transaction" => Self::InvalidUptoSvmPayloadTransaction,
+            "invalid_upto_svm_payload_transaction_instructions_length" => {
+                Self::InvalidUptoSvmPayloadTransactionInstructionsLength
+            }
+            "invalid_upto_svm_payload_transaction_compute_budget_instruction" => {
+                Self::InvalidUptoSvmPayloadTransactionComputeBudgetInstruction
+            }
+            "invalid_upto_svm_payload_transaction_instruction_not_approve_checked" => {
+                Self::InvalidUptoSvmPayloadTransactionInstructionNotApproveChecked
+            }
+            "invalid_upto_svm_payload_transaction_asset_mismatch" => {
+                Self::InvalidUptoSvmPayloadTransactionAssetMismatch
+            }
+            "invalid_upto_svm_payload_transaction_delegate_mismatch" => {
+                Self::InvalidUptoSvmPayloadTransactionDelegateMismatch
+            }
+            "invalid_upto_svm_payload_transaction_cap_mismatch" => {
+                Self::InvalidUptoSvmPayloadTransactionCapMismatch
+            }
+            "invalid_upto_svm_payload_transaction_decimals_mismatch" => {
+                Self::InvalidUptoSvmPayloadTransactionDecimalsMismatch
+            }
+            "invalid_upto_svm_payload_transaction_fee_payer_mismatch" => {
+                Self::InvalidUptoSvmPayloadTransactionFeePayerMismatch
+            }
+            "invalid_upto_svm_payload_transaction_signature" => {
+                Self::InvalidUptoSvmPayloadTransactionSignature
+            }
+            "invalid_upto_svm_payload_transaction_sender_ata_not_found" => {
+                Self::InvalidUptoSvmPayloadTransactionSenderAtaNotFound
+            }
+            "invalid_upto_svm_payload_transaction_receiver_ata_not_found" => {
+                Self::InvalidUptoSvmPayloadTransactionReceiverAtaNotFound
+            }
+            "invalid_upto_svm_settlement_amount" => Self::InvalidUptoSvmSettlementAmount,
+            "payment_already_used" => Self::PaymentAlreadyUsed,
+            "non_transferable_mint" => Self::NonTransferableMint,
+            "unsupported_mint_transfer_hook" => Self::UnsupportedMintTransferHook,
+            "unsupported_mint_transfer_fee" => Self::UnsupportedMintTransferFee,
+            "compute_unit_limit_exceeded" => Self::ComputeUnitLimitExceeded,
+            "compute_unit_price_exceeded" => Self::ComputeUnitPriceExceeded,
+            "fee_payer_not_allowed_in_instruction" => Self::FeePayerNotAllowedInInstruction,
+            "invalid_durable_nonce" => Self::InvalidDurableNonce,
             _ => return Err(Self::UnsupportedX402Error),
         };
 
@@ -118,6 +414,175 @@ impl TryFrom<&str> for X402Error {
     }
 }
 
+impl X402Error {
+    /// The x402 error code of the error (e.g. `insufficient_funds`) as returned
+    /// in the `invalidReason` or `errorReason` fields. Returns `None` for errors
+    /// that are internal to this crate and have no protocol error code.
+    pub fn code(&self) -> Option<&'static str> {
+        let code = match self {
+            Self::InsufficientFunds => "insufficient_funds",
+            Self::InvalidExactSvmPayloadAuthorizationValidAfter => {
+                "invalid_exact_evm_payload_authorization_valid_after"
+            }
+            Self::InvalidExactSvmPayloadAuthorizationValidBefore => {
+                "invalid_exact_evm_payload_authorization_valid_before"
+            }
+            Self::InvalidExactSvmPayloadAuthorizationValue => {
+                "invalid_exact_evm_payload_authorization_value"
+            }
+            Self::InvalidExactSvmPayloadSignature => "invalid_exact_evm_payload_signature",
+            Self::InvalidExactSvmPayloadRecipientMismatch => {
+                "invalid_exact_evm_payload_recipient_mismatch"
+            }
+            Self::InvalidNetwork => "invalid_network",
+            Self::InvalidPayload => "invalid_payload",
+            Self::InvalidPaymentRequirements => "invalid_payment_requirements",
+            Self::InvalidScheme => "invalid_scheme",
+            Self::UnsupportedScheme => "unsupported_scheme",
+            Self::InvalidX402Version => "invalid_x402_version",
+            Self::InvalidTransactionState => "invalid_transaction_state",
+            Self::UnexpectedVerifyError => "unexpected_verify_error",
+            Self::UnexpectedSettleError => "unexpected_settle_error",
+            Self::InvalidExactSvmPayloadTransaction => "invalid_exact_svm_payload_transaction",
+            Self::InvalidExactSvmPayloadTransactionInstructionsLength => {
+                "invalid_exact_svm_payload_transaction_instructions_length"
+            }
+            Self::InvalidExactSvmPayloadTransactionComputeLimitInstruction => {
+                "invalid_exact_svm_payload_transaction_instructions_compute_limit_instruction"
+            }
+            Self::InvalidExactSvmPayloadTransactionComputePriceInstruction => {
+                "invalid_exact_svm_payload_transaction_instructions_compute_price_instruction"
+            }
+            Self::InvalidExactSvmPayloadTransactionCreateAtaInstruction => {
+                "invalid_exact_svm_payload_transaction_create_ata_instruction"
+            }
+            Self::InvalidExactSvmPayloadTransactionCreateAtaInstructionIncorrectPayee => {
+                "invalid_exact_svm_payload_transaction_create_ata_instruction_incorrect_payee"
+            }
+            Self::InvalidExactSvmPayloadTransactionCreateAtaInstructionIncorrectAsset => {
+                "invalid_exact_svm_payload_transaction_create_ata_instruction_incorrect_asset"
+            }
+            Self::InvalidExactSvmPayloadTransactionInstructionNotSplTokenTransferChecked => {
+                "invalid_exact_svm_payload_transaction_instruction_not_spl_token_transfer_checked"
+            }
+            Self::InvalidExactSvmPayloadTransactionInstructionNotToken2022TransferChecked => {
+                "invalid_exact_svm_payload_transaction_instruction_not_token_2022_transfer_checked"
+            }
+            Self::InvalidExactSvmPayloadTransactionAssetMismatch => {
+                "invalid_exact_svm_payload_transaction_asset_mismatch"
+            }
+            Self::InvalidExactSvmPayloadTransactionTransferToIncorrectAta => {
+                "invalid_exact_svm_payload_transaction_transfer_to_incorrect_ata"
+            }
+            Self::InvalidExactSvmPayloadTransactionAmountMismatch => {
+                "invalid_exact_svm_payload_transaction_amount_mismatch"
+            }
+            Self::InvalidExactSvmPayloadTransactionDecimalsMismatch => {
+                "invalid_exact_svm_payload_transaction_decimals_mismatch"
+            }
+            Self::InvalidExactSvmPayloadTransactionTransferFeeMismatch => {
+                "invalid_exact_svm_payload_transaction_transfer_fee_mismatch"
+            }
+            Self::InvalidExactSvmPayloadTransactionFeePayerMismatch => {
+                "invalid_exact_svm_payload_transaction_fee_payer_mismatch"
+            }
+            Self::InvalidExactSvmPayloadTransactionSignature => {
+                "invalid_exact_svm_payload_transaction_signature"
+            }
+            Self::InvalidExactSvmPayloadTransactionSenderAtaNotFound => {
+                "invalid_exact_svm_payload_transaction_sender_ata_not_found"
+            }
+            Self::InvalidExactSvmPayloadTransactionReceiverAtaNotFound => {
+                "invalid_exact_svm_payload_transaction_receiver_ata_not_found"
+            }
+            Self::InvalidUptoSvmPayloadTransaction => "invalid_upto_svm_payload_transaction",
+            Self::InvalidUptoSvmPayloadTransactionInstructionsLength => {
+                "invalid_upto_svm_payload_transaction_instructions_length"
+            }
+            Self::InvalidUptoSvmPayloadTransactionComputeBudgetInstruction => {
+                "invalid_upto_svm_payload_transaction_compute_budget_instruction"
+            }
+            Self::InvalidUptoSvmPayloadTransactionInstructionNotApproveChecked => {
+                "invalid_upto_svm_payload_transaction_instruction_not_approve_checked"
+            }
+            Self::InvalidUptoSvmPayloadTransactionAssetMismatch => {
+                "invalid_upto_svm_payload_transaction_asset_mismatch"
+            }
+            Self::InvalidUptoSvmPayloadTransactionDelegateMismatch => {
+                "invalid_upto_svm_payload_transaction_delegate_mismatch"
+            }
+            Self::InvalidUptoSvmPayloadTransactionCapMismatch => {
+                "invalid_upto_svm_payload_transaction_cap_mismatch"
+            }
+            Self::InvalidUptoSvmPayloadTransactionDecimalsMismatch => {
+                "invalid_upto_svm_payload_transaction_decimals_mismatch"
+            }
+            Self::InvalidUptoSvmPayloadTransactionFeePayerMismatch => {
+                "invalid_upto_svm_payload_transaction_fee_payer_mismatch"
+            }
+            Self::InvalidUptoSvmPayloadTransactionSignature => {
+                "invalid_upto_svm_payload_transaction_signature"
+            }
+            Self::InvalidUptoSvmPayloadTransactionSenderAtaNotFound => {
+                "invalid_upto_svm_payload_transaction_sender_ata_not_found"
+            }
+            Self::InvalidUptoSvmPayloadTransactionReceiverAtaNotFound => {
+                "invalid_upto_svm_payload_transaction_receiver_ata_not_found"
+            }
+            Self::InvalidUptoSvmSettlementAmount => "invalid_upto_svm_settlement_amount",
+            Self::PaymentAlreadyUsed => "payment_already_used",
+            Self::NonTransferableMint => "non_transferable_mint",
+            Self::UnsupportedMintTransferHook => "unsupported_mint_transfer_hook",
+            Self::UnsupportedMintTransferFee => "unsupported_mint_transfer_fee",
+            Self::ComputeUnitLimitExceeded => "compute_unit_limit_exceeded",
+            Self::ComputeUnitPriceExceeded => "compute_unit_price_exceeded",
+            Self::FeePayerNotAllowedInInstruction => "fee_payer_not_allowed_in_instruction",
+            Self::InvalidDurableNonce => "invalid_durable_nonce",
+            _ => return None,
+        };
+
+        Some(code)
+    }
+
+    /// The HTTP status reported for the error by facilitators and resource servers.
+    /// Malformed payloads are [X402PaymentErrorStatusCode::InvalidPayment],
+    /// failures of the facilitator itself are [X402PaymentErrorStatusCode::ServerError]
+    /// and every other error is [X402PaymentErrorStatusCode::PaymentFailed].
+    pub fn status_code(&self) -> X402PaymentErrorStatusCode {
+        match self {
+            Self::InvalidPayload
+            | Self::InvalidPaymentRequirements
+            | Self::InvalidX402Version
+            | Self::InvalidExactSvmPayloadTransaction
+            | Self::InvalidUptoSvmPayloadTransaction
+            | Self::InvalidSolanaPublicKey
+            | Self::InvalidEvmAddress
+            | Self::UnsupportedX402MimeType
+            | Self::HeaderValueTooLarge
+            | Self::InvalidHeaderValueBase64
+            | Self::InvalidHeaderValueJson
+            | Self::InvalidCaip2ChainId
+            | Self::InvalidCaip10AccountId
+            | Self::InvalidCaip19AssetId
+            | Self::InvalidTimestamp
+            | Self::InvalidTokenAmount => X402PaymentErrorStatusCode::InvalidPayment,
+            Self::UnexpectedVerifyError
+            | Self::UnexpectedSettleError
+            | Self::FeePayerKeypairMismatch
+            | Self::TransactionSigningError
+            | Self::TransactionSerializationError
+            | Self::TransactionConfirmationTimeout
+            | Self::RpcRequestError
+            | Self::InvalidFacilitatorClientConfig
+            | Self::FacilitatorRequestError
+            | Self::InvalidFacilitatorResponse
+            | Self::ReplayGuardError
+            | Self::ReceiptStoreError => X402PaymentErrorStatusCode::ServerError,
+            _ => X402PaymentErrorStatusCode::PaymentFailed,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
 pub enum X402PaymentErrorStatusCode {
     /// Payment needed to access resource
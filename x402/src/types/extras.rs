@@ -1,37 +1,39 @@
+use std::borrow::Cow;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PaymentRequestExtras<'x> {
     #[serde(borrow)]
-    name: Option<&'x str>,
+    name: Option<Cow<'x, str>>,
     #[serde(borrow)]
-    version: Option<&'x str>,
+    version: Option<Cow<'x, str>>,
     #[serde(borrow)]
-    fee_payer: &'x str,
+    fee_payer: Cow<'x, str>,
     token_extensions_mint: bool,
     decimals: u8,
     #[serde(borrow)]
-    authority: Option<&'x str>,
+    authority: Option<Cow<'x, str>>,
 }
 
 impl<'x> PaymentRequestExtras<'x> {
-    pub fn new(fee_payer: &'x str) -> Self {
+    pub fn new(fee_payer: impl Into<Cow<'x, str>>) -> Self {
         Self {
-            fee_payer,
+            fee_payer: fee_payer.into(),
             token_extensions_mint: true,
             ..Default::default()
         }
     }
 
-    pub fn set_name(mut self, name: &'x str) -> Self {
-        self.name.replace(name);
+    pub fn set_name(mut self, name: impl Into<Cow<'x, str>>) -> Self {
+        self.name.replace(name.into());
 
         self
     }
 
-    pub fn set_version(mut self, version: &'x str) -> Self {
-        self.version.replace(version);
+    pub fn set_version(mut self, version: impl Into<Cow<'x, str>>) -> Self {
+        self.version.replace(version.into());
 
         self
     }
@@ -48,8 +50,8 @@ impl<'x> PaymentRequestExtras<'x> {
         self
     }
 
-    pub fn set_authority(mut self, authority: &'x str) -> Self {
-        self.authority.replace(authority);
+    pub fn set_authority(mut self, authority: impl Into<Cow<'x, str>>) -> Self {
+        self.authority.replace(authority.into());
 
         self
     }
@@ -61,15 +63,15 @@ impl<'x> PaymentRequestExtras<'x> {
     }
 
     pub fn fee_payer(&self) -> &str {
-        self.fee_payer
+        self.fee_payer.as_ref()
     }
 
     pub fn name(&self) -> Option<&str> {
-        self.name
+        self.name.as_deref()
     }
 
     pub fn version(&self) -> Option<&str> {
-        self.version
+        self.version.as_deref()
     }
 
     pub fn token_extensions_mint(&self) -> bool {
@@ -81,6 +83,20 @@ impl<'x> PaymentRequestExtras<'x> {
     }
 
     pub fn authority(&self) -> Option<&str> {
-        self.authority
+        self.authority.as_deref()
+    }
+
+    /// Convert into extras that own their strings
+    pub fn into_owned(self) -> PaymentRequestExtras<'static> {
+        PaymentRequestExtras {
+            name: self.name.map(|name| Cow::Owned(name.into_owned())),
+            version: self.version.map(|version| Cow::Owned(version.into_owned())),
+            fee_payer: Cow::Owned(self.fee_payer.into_owned()),
+            token_extensions_mint: self.token_extensions_mint,
+            decimals: self.decimals,
+            authority: self
+                .authority
+                .map(|authority| Cow::Owned(authority.into_owned())),
+        }
     }
 }
@@ -0,0 +1,53 @@
+use base64ct::{Base64, Encoding};
+use serde::{Deserialize, Serialize};
+
+use crate::{X402Error, X402Result, X402Version};
+
+/// The maximum length of an encoded x402 header value, 8 KiB like the
+/// default request header limit of most HTTP servers
+pub const X402_HEADER_VALUE_MAX_LENGTH: usize = 8 * 1024;
+
+/// Encode `value` as the base64 JSON of an x402 header
+pub(crate) fn encode_header_value(value: &impl Serialize) -> X402Result<String> {
+    let json = serde_json::to_vec(value).map_err(|_| X402Error::InvalidHeaderValueJson)?;
+
+    if Base64::encoded_len(&json) > X402_HEADER_VALUE_MAX_LENGTH {
+        return Err(X402Error::HeaderValueTooLarge);
+    }
+
+    Ok(Base64::encode_string(&json))
+}
+
+/// Decode the base64 JSON of an x402 header into `buffer` and deserialize it.
+/// The `x402Version` of types of a `version` is checked first so other versions
+/// are reported as [X402Error::InvalidX402Version] instead of invalid JSON.
+pub(crate) fn decode_header_value<'x, T: Deserialize<'x>>(
+    value: &str,
+    buffer: &'x mut Vec<u8>,
+    version: Option<X402Version>,
+) -> X402Result<T> {
+    let value = value.trim();
+    if value.len() > X402_HEADER_VALUE_MAX_LENGTH {
+        return Err(X402Error::HeaderValueTooLarge);
+    }
+
+    *buffer = Base64::decode_vec(value).map_err(|_| X402Error::InvalidHeaderValueBase64)?;
+    let json: &'x [u8] = buffer;
+
+    if let Some(version) = version {
+        let probe = serde_json::from_slice::<X402VersionProbe>(json)
+            .map_err(|_| X402Error::InvalidHeaderValueJson)?;
+
+        if probe.x402_version != version as u64 {
+            return Err(X402Error::InvalidX402Version);
+        }
+    }
+
+    serde_json::from_slice(json).map_err(|_| X402Error::InvalidHeaderValueJson)
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct X402VersionProbe {
+    x402_version: u64,
+}
@@ -1,2 +1,9 @@
 pub const X_PAYMENT_HEADER_KEY: &str = "X-PAYMENT";
 pub const X_PAYMENT_RESPONSE_HEADER_KEY: &str = "X-PAYMENT-RESPONSE";
+
+/// The x402 v2 request header carrying the payment
+pub const PAYMENT_SIGNATURE_HEADER_KEY: &str = "PAYMENT-SIGNATURE";
+/// The x402 v2 header of `402 Payment Required` responses carrying the payment requirements
+pub const PAYMENT_REQUIRED_HEADER_KEY: &str = "PAYMENT-REQUIRED";
+/// The x402 v2 response header carrying the settlement
+pub const PAYMENT_RESPONSE_HEADER_KEY: &str = "PAYMENT-RESPONSE";
@@ -0,0 +1,223 @@
+use core::fmt;
+
+use crate::{
+    Caip19AssetId, PaymentRequestExtras, TokenAmount, X402Error, X402EvmNetworkInfo, X402Network,
+    X402Result, X402SolanaNetworkInfo,
+};
+
+/// The stablecoins of the [KnownAsset] registry
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum KnownToken {
+    Usdc,
+    Usdt,
+    Pyusd,
+    Eurc,
+}
+
+impl KnownToken {
+    pub const ALL: [Self; 4] = [Self::Usdc, Self::Usdt, Self::Pyusd, Self::Eurc];
+
+    /// The ticker symbol of the token
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Usdc => "USDC",
+            Self::Usdt => "USDT",
+            Self::Pyusd => "PYUSD",
+            Self::Eurc => "EURC",
+        }
+    }
+}
+
+impl fmt::Display for KnownToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
+/// How a [KnownAsset] is transferred
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum AssetStandard {
+    /// A mint of the legacy SPL Token program
+    SplToken,
+    /// A mint of the Token-2022 program
+    SplToken2022,
+    /// An ERC-20 contract supporting EIP-3009 with the EIP-712 domain `name` and `version`
+    Eip3009 {
+        name: &'static str,
+        version: &'static str,
+    },
+}
+
+/// A token issued on a network, with the address, decimals and standard
+/// needed to request payments in it
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct KnownAsset {
+    token: KnownToken,
+    network: X402Network,
+    address: &'static str,
+    decimals: u8,
+    standard: AssetStandard,
+}
+
+impl KnownAsset {
+    /// The registry of the known assets, a token is only listed on the networks its issuer deploys it to
+    pub const ALL: [Self; 9] = [
+        Self::solana(
+            KnownToken::Usdc,
+            X402SolanaNetworkInfo::Mainnet,
+            "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+            AssetStandard::SplToken,
+        ),
+        Self::solana(
+            KnownToken::Usdc,
+            X402SolanaNetworkInfo::Devnet,
+            "4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU",
+            AssetStandard::SplToken,
+        ),
+        Self::solana(
+            KnownToken::Usdt,
+            X402SolanaNetworkInfo::Mainnet,
+            "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB",
+            AssetStandard::SplToken,
+        ),
+        Self::solana(
+            KnownToken::Pyusd,
+            X402SolanaNetworkInfo::Mainnet,
+            "2b1kV6DkPAnxd5ixfnxCpjxmKwqjjaYmCZfHsFu24GXo",
+            AssetStandard::SplToken2022,
+        ),
+        Self::solana(
+            KnownToken::Pyusd,
+            X402SolanaNetworkInfo::Devnet,
+            "CXk2AMBfi3TwaEL2468s6zP8xq9NxTXjp9gjMgzeUynM",
+            AssetStandard::SplToken2022,
+        ),
+        Self::solana(
+            KnownToken::Eurc,
+            X402SolanaNetworkInfo::Mainnet,
+            "HzwqbKZw8HxMN6bF2yFZNrht3c2iXXzpKcFu7uBEDKtr",
+            AssetStandard::SplToken,
+        ),
+        Self::solana(
+            KnownToken::Eurc,
+            X402SolanaNetworkInfo::Devnet,
+            "HzwqbKZw8HxMN6bF2yFZNrht3c2iXXzpKcFu7uBEDKtr",
+            AssetStandard::SplToken,
+        ),
+        Self::evm(
+            KnownToken::Usdc,
+            X402EvmNetworkInfo::Base,
+            "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913",
+            "USD Coin",
+        ),
+        Self::evm(
+            KnownToken::Usdc,
+            X402EvmNetworkInfo::BaseSepolia,
+            "0x036CbD53842c5426634e7929541eC2318f3dCF7e",
+            "USDC",
+        ),
+    ];
+
+    const fn solana(
+        token: KnownToken,
+        network: X402SolanaNetworkInfo,
+        address: &'static str,
+        standard: AssetStandard,
+    ) -> Self {
+        Self {
+            token,
+            network: X402Network::Solana(network),
+            address,
+            decimals: 6,
+            standard,
+        }
+    }
+
+    const fn evm(
+        token: KnownToken,
+        network: X402EvmNetworkInfo,
+        address: &'static str,
+        name: &'static str,
+    ) -> Self {
+        Self {
+            token,
+            network: X402Network::Evm(network),
+            address,
+            decimals: 6,
+            standard: AssetStandard::Eip3009 { name, version: "2" },
+        }
+    }
+
+    /// The `token` issued on `network`
+    pub fn get(token: KnownToken, network: impl Into<X402Network>) -> X402Result<Self> {
+        let network = network.into();
+
+        Self::ALL
+            .into_iter()
+            .find(|asset| asset.token == token && asset.network == network)
+            .ok_or(X402Error::UnknownAsset)
+    }
+
+    /// The known asset with the mint or token contract `address` on `network`,
+    /// EVM addresses are compared case insensitively
+    pub fn from_address(network: impl Into<X402Network>, address: &str) -> X402Result<Self> {
+        let network = network.into();
+
+        Self::ALL
+            .into_iter()
+            .find(|asset| {
+                asset.network == network
+                    && match network {
+                        X402Network::Solana(_) => asset.address == address,
+                        X402Network::Evm(_) => asset.address.eq_ignore_ascii_case(address),
+                    }
+            })
+            .ok_or(X402Error::UnknownAsset)
+    }
+
+    pub fn token(&self) -> KnownToken {
+        self.token
+    }
+
+    pub fn network(&self) -> X402Network {
+        self.network
+    }
+
+    /// Mint or token contract address
+    pub fn address(&self) -> &'static str {
+        self.address
+    }
+
+    pub fn decimals(&self) -> u8 {
+        self.decimals
+    }
+
+    pub fn standard(&self) -> AssetStandard {
+        self.standard
+    }
+
+    /// Whether the mint is owned by the Token-2022 program
+    pub fn is_token_2022(&self) -> bool {
+        self.standard == AssetStandard::SplToken2022
+    }
+
+    pub fn asset_id(&self) -> X402Result<Caip19AssetId<'static>> {
+        Caip19AssetId::new(self.network, self.address)
+    }
+
+    /// Parse a decimal amount like `"0.01"` of the asset
+    pub fn amount(&self, value: &str) -> X402Result<TokenAmount> {
+        TokenAmount::parse(value, self.decimals)
+    }
+
+    /// Set the decimals, token program and EIP-712 domain of the asset on `extra`
+    pub fn apply_to<'x>(&self, extra: PaymentRequestExtras<'x>) -> PaymentRequestExtras<'x> {
+        let extra = extra.set_decimals(self.decimals);
+
+        match self.standard {
+            AssetStandard::SplToken => extra.set_legacy_token_mint(),
+            AssetStandard::SplToken2022 => extra.set_token_extensions_mint(),
+            AssetStandard::Eip3009 { name, version } => extra.set_name(name).set_version(version),
+        }
+    }
+}
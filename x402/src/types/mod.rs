@@ -7,6 +7,9 @@ pub use version::*;
 mod payment_requirements;
 pub use payment_requirements::*;
 
+mod network;
+pub use network::*;
+
 mod extras;
 pub use extras::*;
 
@@ -34,8 +37,26 @@ pub use x_payment_header::*;
 mod headers_constants;
 pub use headers_constants::*;
 
+mod header_value;
+pub use header_value::*;
+
 mod verify;
 pub use verify::*;
 
 mod supported;
 pub use supported::*;
+
+mod caip;
+pub use caip::*;
+
+mod payment_v2;
+pub use payment_v2::*;
+
+mod timestamp;
+pub use timestamp::*;
+
+mod token_amount;
+pub use token_amount::*;
+
+mod known_asset;
+pub use known_asset::*;
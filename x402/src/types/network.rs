@@ -0,0 +1,217 @@
+use crate::{BlockchainNetwork, X402Error, X402SolanaNetworkInfo};
+
+/// The network a payment is made on, a Solana cluster or an EVM chain.
+/// Parsed from the legacy x402 network names and from CAIP-2 chain ids,
+/// serialized with the x402 network name.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum X402Network {
+    Solana(X402SolanaNetworkInfo),
+    Evm(X402EvmNetworkInfo),
+}
+
+impl X402Network {
+    /// The Solana cluster, `None` for EVM chains
+    pub fn solana(&self) -> Option<X402SolanaNetworkInfo> {
+        match self {
+            Self::Solana(network) => Some(*network),
+            Self::Evm(_) => Option::None,
+        }
+    }
+
+    /// The EVM chain, `None` for Solana clusters
+    pub fn evm(&self) -> Option<X402EvmNetworkInfo> {
+        match self {
+            Self::Solana(_) => Option::None,
+            Self::Evm(network) => Some(*network),
+        }
+    }
+
+    pub fn is_solana(&self) -> bool {
+        matches!(self, Self::Solana(_))
+    }
+
+    pub fn is_evm(&self) -> bool {
+        matches!(self, Self::Evm(_))
+    }
+}
+
+impl Default for X402Network {
+    fn default() -> Self {
+        Self::Solana(X402SolanaNetworkInfo::default())
+    }
+}
+
+impl From<X402SolanaNetworkInfo> for X402Network {
+    fn from(network: X402SolanaNetworkInfo) -> Self {
+        Self::Solana(network)
+    }
+}
+
+impl From<X402EvmNetworkInfo> for X402Network {
+    fn from(network: X402EvmNetworkInfo) -> Self {
+        Self::Evm(network)
+    }
+}
+
+impl BlockchainNetwork for X402Network {
+    fn identifier(&self) -> &str {
+        match self {
+            Self::Solana(network) => network.identifier(),
+            Self::Evm(network) => network.identifier(),
+        }
+    }
+
+    fn chain(&self) -> &str {
+        match self {
+            Self::Solana(network) => network.chain(),
+            Self::Evm(network) => network.chain(),
+        }
+    }
+
+    fn x402_identifier(&self) -> &str {
+        match self {
+            Self::Solana(network) => network.x402_identifier(),
+            Self::Evm(network) => network.x402_identifier(),
+        }
+    }
+}
+
+impl TryFrom<&str> for X402Network {
+    type Error = X402Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        X402SolanaNetworkInfo::try_from(value)
+            .map(Self::Solana)
+            .or_else(|_| X402EvmNetworkInfo::try_from(value).map(Self::Evm))
+    }
+}
+
+/// The EVM chains with tokens supporting EIP-3009 payments
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum X402EvmNetworkInfo {
+    Base,
+    BaseSepolia,
+    Avalanche,
+    AvalancheFuji,
+    Polygon,
+    PolygonAmoy,
+    Sei,
+    SeiTestnet,
+    Iotex,
+}
+
+impl X402EvmNetworkInfo {
+    pub const BASE: &str = "base";
+    pub const BASE_SEPOLIA: &str = "base-sepolia";
+    pub const AVALANCHE: &str = "avalanche";
+    pub const AVALANCHE_FUJI: &str = "avalanche-fuji";
+    pub const POLYGON: &str = "polygon";
+    pub const POLYGON_AMOY: &str = "polygon-amoy";
+    pub const SEI: &str = "sei";
+    pub const SEI_TESTNET: &str = "sei-testnet";
+    pub const IOTEX: &str = "iotex";
+
+    pub const BASE_CHAIN: &str = "eip155:8453";
+    pub const BASE_SEPOLIA_CHAIN: &str = "eip155:84532";
+    pub const AVALANCHE_CHAIN: &str = "eip155:43114";
+    pub const AVALANCHE_FUJI_CHAIN: &str = "eip155:43113";
+    pub const POLYGON_CHAIN: &str = "eip155:137";
+    pub const POLYGON_AMOY_CHAIN: &str = "eip155:80002";
+    pub const SEI_CHAIN: &str = "eip155:1329";
+    pub const SEI_TESTNET_CHAIN: &str = "eip155:1328";
+    pub const IOTEX_CHAIN: &str = "eip155:4689";
+
+    /// All the supported chains
+    pub const ALL: [Self; 9] = [
+        Self::Base,
+        Self::BaseSepolia,
+        Self::Avalanche,
+        Self::AvalancheFuji,
+        Self::Polygon,
+        Self::PolygonAmoy,
+        Self::Sei,
+        Self::SeiTestnet,
+        Self::Iotex,
+    ];
+
+    /// The EIP-155 chain id
+    pub fn chain_id(&self) -> u64 {
+        match self {
+            Self::Base => 8453,
+            Self::BaseSepolia => 84532,
+            Self::Avalanche => 43114,
+            Self::AvalancheFuji => 43113,
+            Self::Polygon => 137,
+            Self::PolygonAmoy => 80002,
+            Self::Sei => 1329,
+            Self::SeiTestnet => 1328,
+            Self::Iotex => 4689,
+        }
+    }
+
+    /// The chain with the EIP-155 `chain_id`
+    pub fn from_chain_id(chain_id: u64) -> Result<Self, X402Error> {
+        Self::ALL
+            .into_iter()
+            .find(|network| network.chain_id() == chain_id)
+            .ok_or(X402Error::InvalidNetwork)
+    }
+
+    pub fn is_testnet(&self) -> bool {
+        matches!(
+            self,
+            Self::BaseSepolia | Self::AvalancheFuji | Self::PolygonAmoy | Self::SeiTestnet
+        )
+    }
+}
+
+impl BlockchainNetwork for X402EvmNetworkInfo {
+    fn identifier(&self) -> &str {
+        self.x402_identifier()
+    }
+
+    fn chain(&self) -> &str {
+        match self {
+            Self::Base => Self::BASE_CHAIN,
+            Self::BaseSepolia => Self::BASE_SEPOLIA_CHAIN,
+            Self::Avalanche => Self::AVALANCHE_CHAIN,
+            Self::AvalancheFuji => Self::AVALANCHE_FUJI_CHAIN,
+            Self::Polygon => Self::POLYGON_CHAIN,
+            Self::PolygonAmoy => Self::POLYGON_AMOY_CHAIN,
+            Self::Sei => Self::SEI_CHAIN,
+            Self::SeiTestnet => Self::SEI_TESTNET_CHAIN,
+            Self::Iotex => Self::IOTEX_CHAIN,
+        }
+    }
+
+    fn x402_identifier(&self) -> &str {
+        match self {
+            Self::Base => Self::BASE,
+            Self::BaseSepolia => Self::BASE_SEPOLIA,
+            Self::Avalanche => Self::AVALANCHE,
+            Self::AvalancheFuji => Self::AVALANCHE_FUJI,
+            Self::Polygon => Self::POLYGON,
+            Self::PolygonAmoy => Self::POLYGON_AMOY,
+            Self::Sei => Self::SEI,
+            Self::SeiTestnet => Self::SEI_TESTNET,
+            Self::Iotex => Self::IOTEX,
+        }
+    }
+}
+
+impl TryFrom<&str> for X402EvmNetworkInfo {
+    type Error = X402Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        if let Some(chain_id) = value.strip_prefix("eip155:") {
+            let chain_id = chain_id.parse().map_err(|_| X402Error::InvalidNetwork)?;
+
+            return Self::from_chain_id(chain_id);
+        }
+
+        Self::ALL
+            .into_iter()
+            .find(|network| network.x402_identifier() == value)
+            .ok_or(X402Error::InvalidNetwork)
+    }
+}
@@ -4,8 +4,9 @@ use std::borrow::Cow;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::{
-    MimeType, PaymentRequestExtras, PaymentScheme, X402Error, X402Result, deserialize_mime,
-    serialize_mime,
+    AmountRequired, Caip10AccountId, Caip19AssetId, KnownAsset, KnownToken, MimeType,
+    PaymentRequestExtras, PaymentScheme, TokenAmount, X402Error, X402Network, X402Result,
+    deserialize_mime, serialize_mime,
 };
 
 #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Serialize, Deserialize)]
@@ -16,21 +17,21 @@ pub struct PaymentRequirements<'x> {
     /// Blockchain network identifier (e.g., "base-sepolia", "ethereum-mainnet")
     #[serde(serialize_with = "serialize_network")]
     #[serde(deserialize_with = "deserialize_network")]
-    network: X402SolanaNetworkInfo,
+    network: X402Network,
     /// Required payment amount in atomic token units
     max_amount_required: u64,
     /// Token contract address
     #[serde(borrow)]
-    asset: &'x str,
+    asset: Cow<'x, str>,
     /// Recipient wallet address for the payment
     #[serde(borrow)]
-    pay_to: &'x str,
+    pay_to: Cow<'x, str>,
     /// URL of the protected resource
     #[serde(borrow)]
-    resource: &'x str,
+    resource: Cow<'x, str>,
     /// Human-readable description of the resource
     #[serde(borrow)]
-    description: &'x str,
+    description: Cow<'x, str>,
     /// MIME type of the expected response
     #[serde(serialize_with = "serialize_mime")]
     #[serde(deserialize_with = "deserialize_mime")]
@@ -52,7 +53,7 @@ impl<'x> PaymentRequirements<'x> {
     }
 
     /// Blockchain network identifier (e.g., "solana", "solana-devnet")
-    pub fn network(&self) -> X402SolanaNetworkInfo {
+    pub fn network(&self) -> X402Network {
         self.network
     }
 
@@ -61,24 +62,39 @@ impl<'x> PaymentRequirements<'x> {
         self.max_amount_required
     }
 
+    /// Required payment amount with the `decimals` of the extras
+    pub fn max_amount(&self) -> TokenAmount {
+        TokenAmount::new(self.max_amount_required, self.extra.decimals())
+    }
+
     /// Token contract address
     pub fn asset(&self) -> &str {
-        self.asset
+        self.asset.as_ref()
     }
 
     /// Recipient wallet address for the payment
     pub fn pay_to(&self) -> &str {
-        self.pay_to
+        self.pay_to.as_ref()
+    }
+
+    /// CAIP-10 account id of the recipient on the network of the requirements
+    pub fn pay_to_account_id(&self) -> X402Result<Caip10AccountId<'_>> {
+        Caip10AccountId::new(self.network, self.pay_to.as_ref())
+    }
+
+    /// CAIP-19 asset id of the token on the network of the requirements
+    pub fn asset_id(&self) -> X402Result<Caip19AssetId<'_>> {
+        Caip19AssetId::new(self.network, self.asset.as_ref())
     }
 
     /// URL of the protected resource
     pub fn resource(&self) -> &str {
-        self.resource
+        self.resource.as_ref()
     }
 
     /// Human-readable description of the resource
     pub fn description(&self) -> &str {
-        self.description
+        self.description.as_ref()
     }
 
     /// MIME type of the expected response
@@ -105,6 +121,25 @@ impl<'x> PaymentRequirements<'x> {
     pub fn extra_take(self) -> PaymentRequestExtras<'x> {
         self.extra
     }
+
+    /// Convert into requirements that own their strings
+    pub fn into_owned(self) -> PaymentRequirements<'static> {
+        PaymentRequirements {
+            scheme: self.scheme,
+            network: self.network,
+            max_amount_required: self.max_amount_required,
+            asset: Cow::Owned(self.asset.into_owned()),
+            pay_to: Cow::Owned(self.pay_to.into_owned()),
+            resource: Cow::Owned(self.resource.into_owned()),
+            description: Cow::Owned(self.description.into_owned()),
+            mime_type: self.mime_type,
+            output_schema: self
+                .output_schema
+                .map(|output_schema| Cow::Owned(output_schema.into_owned())),
+            max_timeout_seconds: self.max_timeout_seconds,
+            extra: self.extra.into_owned(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
@@ -112,17 +147,17 @@ pub struct PaymentRequirementsBuilder<'x> {
     /// Payment scheme identifier (e.g., "exact")
     scheme: PaymentScheme,
     /// Blockchain network identifier (e.g., "base-sepolia", "ethereum-mainnet")
-    network: X402SolanaNetworkInfo,
-    /// Required payment amount in atomic token units
-    max_amount_required: Option<u64>,
+    network: X402Network,
+    /// Required payment amount
+    max_amount_required: Option<AmountRequired>,
     /// Token contract address
-    asset: Option<&'x str>,
+    asset: Option<Cow<'x, str>>,
     /// Recipient wallet address for the payment
-    pay_to: Option<&'x str>,
+    pay_to: Option<Cow<'x, str>>,
     /// URL of the protected resource
-    resource: Option<&'x str>,
+    resource: Option<Cow<'x, str>>,
     /// Human-readable description of the resource
-    description: Option<&'x str>,
+    description: Option<Cow<'x, str>>,
     /// MIME type of the expected response
     mime_type: Option<MimeType>,
     /// Optional JSON schema describing the response format
@@ -131,6 +166,8 @@ pub struct PaymentRequirementsBuilder<'x> {
     max_timeout_seconds: Option<u64>,
     /// Scheme-specific additional information
     extra: Option<PaymentRequestExtras<'x>>,
+    /// Token whose [KnownAsset] on the network fills the asset and extras
+    known_token: Option<KnownToken>,
 }
 
 impl<'x> PaymentRequirementsBuilder<'x> {
@@ -138,56 +175,96 @@ impl<'x> PaymentRequirementsBuilder<'x> {
         Self::default()
     }
 
+    /// The scheme of the payment, `exact` unless set
+    pub fn set_scheme(&mut self, scheme: PaymentScheme) -> &mut Self {
+        self.scheme = scheme;
+
+        self
+    }
+
+    /// The network the payment is made on, a Solana cluster or an EVM chain
+    pub fn set_network(&mut self, network: impl Into<X402Network>) -> &mut Self {
+        self.network = network.into();
+
+        self
+    }
+
     pub fn use_mainnet(&mut self) -> &mut Self {
-        self.network = X402SolanaNetworkInfo::Mainnet;
+        self.network = X402SolanaNetworkInfo::Mainnet.into();
 
         self
     }
 
     pub fn use_testnet(&mut self) -> &mut Self {
-        self.network = X402SolanaNetworkInfo::Testnet;
+        self.network = X402SolanaNetworkInfo::Testnet.into();
 
         self
     }
 
     pub fn use_devnet(&mut self) -> &mut Self {
-        self.network = X402SolanaNetworkInfo::Devnet;
+        self.network = X402SolanaNetworkInfo::Devnet.into();
 
         self
     }
 
     pub fn use_localhost(&mut self) -> &mut Self {
-        self.network = X402SolanaNetworkInfo::Localnet;
+        self.network = X402SolanaNetworkInfo::Localnet.into();
 
         self
     }
 
-    pub fn set_amount(&mut self, amount: u64) -> &mut Self {
-        self.max_amount_required.replace(amount);
+    /// The required amount in atomic token units or as a [TokenAmount]
+    pub fn set_amount(&mut self, amount: impl Into<AmountRequired>) -> &mut Self {
+        self.max_amount_required.replace(amount.into());
 
         self
     }
 
-    pub fn set_asset(&mut self, asset: &'x str) -> &mut Self {
-        self.asset.replace(asset);
+    pub fn set_asset(&mut self, asset: impl Into<Cow<'x, str>>) -> &mut Self {
+        self.asset.replace(asset.into());
+        self.known_token = Option::None;
 
         self
     }
 
-    pub fn set_recipient(&mut self, recipient: &'x str) -> &mut Self {
-        self.pay_to.replace(recipient);
+    /// Request the [KnownAsset] of `token` on the network of the requirements,
+    /// its address, decimals, token program and EIP-712 domain are set when building
+    pub fn use_token(&mut self, token: KnownToken) -> &mut Self {
+        self.known_token.replace(token);
 
         self
     }
 
-    pub fn set_resource(&mut self, resource: &'x str) -> &mut Self {
-        self.resource.replace(resource);
+    pub fn use_usdc(&mut self) -> &mut Self {
+        self.use_token(KnownToken::Usdc)
+    }
+
+    pub fn use_usdt(&mut self) -> &mut Self {
+        self.use_token(KnownToken::Usdt)
+    }
+
+    pub fn use_pyusd(&mut self) -> &mut Self {
+        self.use_token(KnownToken::Pyusd)
+    }
+
+    pub fn use_eurc(&mut self) -> &mut Self {
+        self.use_token(KnownToken::Eurc)
+    }
+
+    pub fn set_recipient(&mut self, recipient: impl Into<Cow<'x, str>>) -> &mut Self {
+        self.pay_to.replace(recipient.into());
+
+        self
+    }
+
+    pub fn set_resource(&mut self, resource: impl Into<Cow<'x, str>>) -> &mut Self {
+        self.resource.replace(resource.into());
 
         self
     }
 
-    pub fn set_description(&mut self, description: &'x str) -> &mut Self {
-        self.description.replace(description);
+    pub fn set_description(&mut self, description: impl Into<Cow<'x, str>>) -> &mut Self {
+        self.description.replace(description.into());
 
         self
     }
@@ -223,13 +300,31 @@ impl<'x> PaymentRequirementsBuilder<'x> {
     }
 
     pub fn build(self) -> X402Result<PaymentRequirements<'x>> {
+        let max_amount_required = self
+            .max_amount_required
+            .ok_or(X402Error::MaxAmountIsMissing)?;
+        let mut asset = self.asset;
+        let mut extra = self.extra.ok_or(X402Error::ExtraIsMissing)?;
+
+        if let Some(token) = self.known_token {
+            let known_asset = KnownAsset::get(token, self.network)?;
+
+            asset.replace(known_asset.address().into());
+            extra = known_asset.apply_to(extra);
+        }
+
+        if max_amount_required
+            .decimals()
+            .is_some_and(|decimals| decimals != extra.decimals())
+        {
+            return Err(X402Error::TokenAmountDecimalsMismatch);
+        }
+
         Ok(PaymentRequirements {
-            scheme: PaymentScheme::Exact,
+            scheme: self.scheme,
             network: self.network,
-            max_amount_required: self
-                .max_amount_required
-                .ok_or(X402Error::MaxAmountIsMissing)?,
-            asset: self.asset.ok_or(X402Error::AssetIsMissing)?,
+            max_amount_required: max_amount_required.atomic(),
+            asset: asset.ok_or(X402Error::AssetIsMissing)?,
             pay_to: self.pay_to.ok_or(X402Error::PayToIsMissing)?,
             resource: self.resource.ok_or(X402Error::ResourceIsMissing)?,
             description: self.description.ok_or(X402Error::DescriptionIsMissing)?,
@@ -238,7 +333,7 @@ impl<'x> PaymentRequirementsBuilder<'x> {
             max_timeout_seconds: self
                 .max_timeout_seconds
                 .ok_or(X402Error::MaxTimeoutIsMissing)?,
-            extra: self.extra.ok_or(X402Error::ExtraIsMissing)?,
+            extra,
         })
     }
 }
@@ -247,7 +342,7 @@ impl<'x> Default for PaymentRequirementsBuilder<'x> {
     fn default() -> Self {
         Self {
             scheme: PaymentScheme::Exact,
-            network: X402SolanaNetworkInfo::default(),
+            network: X402Network::default(),
             max_amount_required: Option::default(),
             asset: Option::default(),
             pay_to: Option::default(),
@@ -256,6 +351,7 @@ impl<'x> Default for PaymentRequirementsBuilder<'x> {
             mime_type: Option::default(),
             output_schema: Option::None,
             max_timeout_seconds: Option::default(),
+            known_token: Option::None,
             extra: Option::default(),
         }
     }
@@ -276,11 +372,28 @@ impl X402SolanaNetworkInfo {
     pub const DEVNET: &str = "devnet";
     pub const LOCALNET: &str = "localnet";
 
-    pub const MAINNET_CHAIN: &str = "solana:mainnet";
-    pub const TESTNET_CHAIN: &str = "solana:testnet";
-    pub const DEVNET_CHAIN: &str = "solana:devnet";
+    /// The CAIP-2 chain ids of Solana use the first 32 characters of the genesis hash.
+    /// Local validators have no fixed genesis hash and use `localnet` instead.
+    pub const MAINNET_CHAIN: &str = "solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp";
+    pub const TESTNET_CHAIN: &str = "solana:4uhcVJyU9pJkvQyS88uRDiswHXSCkY3z";
+    pub const DEVNET_CHAIN: &str = "solana:EtWTRABZaYq6iMfeYKouRu166VU2xqa1";
     pub const LOCALNET_CHAIN: &str = "solana:localnet";
 
+    #[deprecated(note = "use `MAINNET_CHAIN`, which is now the CAIP-2 chain id")]
+    pub const MAINNET_CAIP2: &str = Self::MAINNET_CHAIN;
+    #[deprecated(note = "use `TESTNET_CHAIN`, which is now the CAIP-2 chain id")]
+    pub const TESTNET_CAIP2: &str = Self::TESTNET_CHAIN;
+    #[deprecated(note = "use `DEVNET_CHAIN`, which is now the CAIP-2 chain id")]
+    pub const DEVNET_CAIP2: &str = Self::DEVNET_CHAIN;
+
+    /// Chain names used before CAIP-2 chain ids, still accepted when parsing
+    pub const MAINNET_LEGACY_CHAIN: &str = "solana:mainnet";
+    pub const TESTNET_LEGACY_CHAIN: &str = "solana:testnet";
+    pub const DEVNET_LEGACY_CHAIN: &str = "solana:devnet";
+
+    /// The x402 name of Solana mainnet used by other implementations
+    pub const MAINNET_X402_ALIAS: &str = "solana";
+
     pub const MAINNET_X402: &str = "solana-mainnet";
     pub const TESTNET_X402: &str = "solana-testnet";
     pub const DEVNET_X402: &str = "solana-devnet";
@@ -322,11 +435,15 @@ impl TryFrom<&str> for X402SolanaNetworkInfo {
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         let network = match value {
             Self::MAINNET_X402 => Self::Mainnet,
+            Self::MAINNET_X402_ALIAS => Self::Mainnet,
             Self::MAINNET_CHAIN => Self::Mainnet,
+            Self::MAINNET_LEGACY_CHAIN => Self::Mainnet,
             Self::TESTNET_X402 => Self::Testnet,
             Self::TESTNET_CHAIN => Self::Testnet,
+            Self::TESTNET_LEGACY_CHAIN => Self::Testnet,
             Self::DEVNET_X402 => Self::Devnet,
             Self::DEVNET_CHAIN => Self::Devnet,
+            Self::DEVNET_LEGACY_CHAIN => Self::Devnet,
             Self::LOCALNET_X402 => Self::Localnet,
             Self::LOCALNET_CHAIN => Self::Localnet,
             _ => return Err(X402Error::InvalidNetwork),
@@ -336,31 +453,47 @@ impl TryFrom<&str> for X402SolanaNetworkInfo {
     }
 }
 
-pub fn serialize_network<S>(
-    network: &X402SolanaNetworkInfo,
-    serializer: S,
-) -> Result<S::Ok, S::Error>
+pub fn serialize_network<S, N>(network: &N, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
+    N: BlockchainNetwork,
 {
     serializer.serialize_str(network.x402_identifier())
 }
 
-pub fn deserialize_network<'de, D>(deserializer: D) -> Result<X402SolanaNetworkInfo, D::Error>
+/// Serialize a network as its CAIP-2 chain id like the x402 v2 wire format
+pub fn serialize_caip2_network<S, N>(network: &N, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    N: BlockchainNetwork,
+{
+    serializer.serialize_str(network.chain())
+}
+
+/// Deserialize a network from its x402 name or its CAIP-2 chain id
+pub fn deserialize_network<'de, D, N>(deserializer: D) -> Result<N, D::Error>
 where
     D: Deserializer<'de>,
+    N: for<'a> TryFrom<&'a str, Error = X402Error>,
 {
     let s = String::deserialize(deserializer)?;
 
-    s.as_str().try_into().map_err(serde::de::Error::custom)
+    N::try_from(s.as_str()).map_err(serde::de::Error::custom)
 }
 
 pub trait BlockchainNetwork {
     fn identifier(&self) -> &str;
 
+    /// The CAIP-2 chain id of the network
     fn chain(&self) -> &str;
 
     fn x402_identifier(&self) -> &str;
+
+    /// The CAIP-2 chain id of the network
+    #[deprecated(note = "use `chain`, which is now the CAIP-2 chain id")]
+    fn caip2_identifier(&self) -> &str {
+        self.chain()
+    }
 }
 
 impl PartialEq for &'_ dyn BlockchainNetwork {
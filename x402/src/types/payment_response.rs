@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 
-use crate::{PaymentRequirements, X402Version, deserialize_x402_version, serialize_x402_version};
+use crate::{
+    PaymentRequirements, X402Result, X402Version, decode_header_value, deserialize_x402_version,
+    encode_header_value, serialize_x402_version,
+};
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -12,7 +15,7 @@ pub struct PaymentRequirementsResponse<'x> {
     x402_version: X402Version,
     /// Human-readable error message explaining why payment is required
     #[serde(borrow)]
-    error: &'x str,
+    error: Cow<'x, str>,
     /// Array of payment requirement objects defining acceptable payment methods
     #[serde(borrow)]
     accepts: Cow<'x, [PaymentRequirements<'x>]>,
@@ -25,8 +28,8 @@ impl<'x> PaymentRequirementsResponse<'x> {
         Self::default()
     }
 
-    pub fn set_error_reason(&mut self, error_reason: &'x str) -> &mut Self {
-        self.error = error_reason;
+    pub fn set_error_reason(&mut self, error_reason: impl Into<Cow<'x, str>>) -> &mut Self {
+        self.error = error_reason.into();
 
         self
     }
@@ -47,24 +50,50 @@ impl<'x> PaymentRequirementsResponse<'x> {
         serde_json::to_string(self)
     }
 
+    /// Encode as the base64 JSON value of a header
+    pub fn to_header_value(&self) -> X402Result<String> {
+        encode_header_value(self)
+    }
+
+    /// Decode the base64 JSON value of a header.
+    /// The JSON is decoded into `buffer` which the response borrows from.
+    pub fn from_header_value(value: &str, buffer: &'x mut Vec<u8>) -> X402Result<Self> {
+        decode_header_value(value, buffer, Some(X402Version::V1))
+    }
+
     pub fn x402_version(&self) -> X402Version {
         self.x402_version
     }
 
     pub fn error_reason(&self) -> &str {
-        self.error
+        self.error.as_ref()
     }
 
     pub fn accepts(&self) -> &[PaymentRequirements<'_>] {
         self.accepts.as_ref()
     }
+
+    /// Convert into a response that owns its strings
+    pub fn into_owned(self) -> PaymentRequirementsResponse<'static> {
+        PaymentRequirementsResponse {
+            x402_version: self.x402_version,
+            error: Cow::Owned(self.error.into_owned()),
+            accepts: Cow::Owned(
+                self.accepts
+                    .into_owned()
+                    .into_iter()
+                    .map(PaymentRequirements::into_owned)
+                    .collect(),
+            ),
+        }
+    }
 }
 
 impl<'x> Default for PaymentRequirementsResponse<'x> {
     fn default() -> Self {
         Self {
             x402_version: X402Version::V1,
-            error: Self::ERROR,
+            error: Cow::Borrowed(Self::ERROR),
             accepts: Cow::Borrowed(&[]),
         }
     }
@@ -0,0 +1,480 @@
+use core::time::Duration;
+use std::borrow::Cow;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    MimeType, PaymentRequestExtras, PaymentRequirements, PaymentRequirementsBuilder, PaymentScheme,
+    VerifyPayload, X402Error, X402Network, X402Result, X402Version, XPaymentPayload,
+    XPaymentTransaction, decode_header_value, deserialize_mime, deserialize_network,
+    deserialize_x402_version, encode_header_value, serialize_caip2_network, serialize_mime,
+    serialize_x402_version,
+};
+
+/// The `extensions` of x402 v2 payloads and 402 responses keyed by extension name
+pub type X402Extensions = serde_json::Map<String, serde_json::Value>;
+
+/// The resource paid for, split out of the payment requirements in x402 v2
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentResource<'x> {
+    /// URL of the protected resource
+    #[serde(borrow)]
+    url: Cow<'x, str>,
+    /// Human-readable description of the resource
+    #[serde(borrow)]
+    #[serde(default)]
+    description: Cow<'x, str>,
+    /// MIME type of the expected response
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "serialize_mime")]
+    #[serde(deserialize_with = "deserialize_mime")]
+    mime_type: Option<MimeType>,
+}
+
+impl<'x> PaymentResource<'x> {
+    pub fn new(url: impl Into<Cow<'x, str>>) -> Self {
+        Self {
+            url: url.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn set_description(mut self, description: impl Into<Cow<'x, str>>) -> Self {
+        self.description = description.into();
+
+        self
+    }
+
+    pub fn set_mime_type(mut self, mime_type: MimeType) -> Self {
+        self.mime_type.replace(mime_type);
+
+        self
+    }
+
+    pub fn url(&self) -> &str {
+        self.url.as_ref()
+    }
+
+    pub fn description(&self) -> &str {
+        self.description.as_ref()
+    }
+
+    pub fn mime_type(&self) -> Option<MimeType> {
+        self.mime_type
+    }
+
+    /// Convert into a resource that owns its strings
+    pub fn into_owned(self) -> PaymentResource<'static> {
+        PaymentResource {
+            url: Cow::Owned(self.url.into_owned()),
+            description: Cow::Owned(self.description.into_owned()),
+            mime_type: self.mime_type,
+        }
+    }
+}
+
+impl<'a> From<&'a PaymentRequirements<'_>> for PaymentResource<'a> {
+    fn from(requirements: &'a PaymentRequirements<'_>) -> Self {
+        Self {
+            url: Cow::Borrowed(requirements.resource()),
+            description: Cow::Borrowed(requirements.description()),
+            mime_type: requirements.mime_type(),
+        }
+    }
+}
+
+/// The x402 v2 payment requirements. The network is a CAIP-2 chain id and
+/// the `amount` replaces `maxAmountRequired`.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentRequirementsV2<'x> {
+    /// Payment scheme identifier (e.g., "exact")
+    scheme: PaymentScheme,
+    /// CAIP-2 chain id of the network
+    #[serde(serialize_with = "serialize_caip2_network")]
+    #[serde(deserialize_with = "deserialize_network")]
+    network: X402Network,
+    /// Required payment amount in atomic token units
+    #[serde(serialize_with = "serialize_amount")]
+    #[serde(deserialize_with = "deserialize_amount")]
+    amount: u64,
+    /// Token contract address
+    #[serde(borrow)]
+    asset: Cow<'x, str>,
+    /// Recipient wallet address for the payment
+    #[serde(borrow)]
+    pay_to: Cow<'x, str>,
+    /// Maximum time allowed for payment completion
+    max_timeout_seconds: u64,
+    /// Scheme-specific additional information
+    #[serde(borrow)]
+    extra: PaymentRequestExtras<'x>,
+}
+
+impl<'x> PaymentRequirementsV2<'x> {
+    pub fn scheme(&self) -> PaymentScheme {
+        self.scheme
+    }
+
+    pub fn network(&self) -> X402Network {
+        self.network
+    }
+
+    pub fn amount(&self) -> u64 {
+        self.amount
+    }
+
+    pub fn asset(&self) -> &str {
+        self.asset.as_ref()
+    }
+
+    pub fn pay_to(&self) -> &str {
+        self.pay_to.as_ref()
+    }
+
+    pub fn max_timeout_seconds(&self) -> u64 {
+        self.max_timeout_seconds
+    }
+
+    pub fn extra(&self) -> &PaymentRequestExtras<'_> {
+        &self.extra
+    }
+
+    /// Join with the `resource` into the x402 v1 payment requirements
+    pub fn into_v1(self, resource: &PaymentResource<'x>) -> X402Result<PaymentRequirements<'x>> {
+        let mut builder = PaymentRequirementsBuilder::new();
+        builder
+            .set_scheme(self.scheme)
+            .set_network(self.network)
+            .set_amount(self.amount)
+            .set_asset(self.asset)
+            .set_recipient(self.pay_to)
+            .set_resource(resource.url.clone())
+            .set_description(resource.description.clone())
+            .set_max_timeout_seconds(Duration::from_secs(self.max_timeout_seconds))
+            .set_extra(self.extra);
+
+        match resource.mime_type {
+            Some(MimeType::Json) => builder.set_mime_as_json(),
+            Some(MimeType::Binary) => builder.set_mime_as_binary(),
+            None => &mut builder,
+        };
+
+        builder.build()
+    }
+
+    /// Convert into requirements that own their strings
+    pub fn into_owned(self) -> PaymentRequirementsV2<'static> {
+        PaymentRequirementsV2 {
+            scheme: self.scheme,
+            network: self.network,
+            amount: self.amount,
+            asset: Cow::Owned(self.asset.into_owned()),
+            pay_to: Cow::Owned(self.pay_to.into_owned()),
+            max_timeout_seconds: self.max_timeout_seconds,
+            extra: self.extra.into_owned(),
+        }
+    }
+}
+
+impl<'a> From<&'a PaymentRequirements<'_>> for PaymentRequirementsV2<'a> {
+    fn from(requirements: &'a PaymentRequirements<'_>) -> Self {
+        Self {
+            scheme: requirements.scheme(),
+            network: requirements.network(),
+            amount: requirements.max_amount_required(),
+            asset: Cow::Borrowed(requirements.asset()),
+            pay_to: Cow::Borrowed(requirements.pay_to()),
+            max_timeout_seconds: requirements.max_timeout_seconds(),
+            extra: requirements.extra().clone(),
+        }
+    }
+}
+
+/// The body of the x402 v2 `PAYMENT-REQUIRED` header of `402 Payment Required` responses
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentRequired<'x> {
+    /// Protocol version identifier
+    #[serde(deserialize_with = "deserialize_x402_version")]
+    #[serde(serialize_with = "serialize_x402_version")]
+    x402_version: X402Version,
+    /// Human-readable error message explaining why payment is required
+    #[serde(borrow)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<Cow<'x, str>>,
+    /// The resource the payment is for
+    #[serde(borrow)]
+    resource: PaymentResource<'x>,
+    /// Payment requirements defining acceptable payment methods
+    #[serde(borrow)]
+    accepts: Vec<PaymentRequirementsV2<'x>>,
+    #[serde(default, skip_serializing_if = "X402Extensions::is_empty")]
+    extensions: X402Extensions,
+}
+
+impl<'x> PaymentRequired<'x> {
+    pub const ERROR: &'static str = "PAYMENT-SIGNATURE header is required";
+
+    pub fn new(resource: PaymentResource<'x>) -> Self {
+        Self {
+            x402_version: X402Version::V2,
+            error: Some(Cow::Borrowed(Self::ERROR)),
+            resource,
+            accepts: Vec::new(),
+            extensions: X402Extensions::new(),
+        }
+    }
+
+    pub fn set_error_reason(&mut self, error_reason: impl Into<Cow<'x, str>>) -> &mut Self {
+        self.error.replace(error_reason.into());
+
+        self
+    }
+
+    pub fn add_payment_requirement(&mut self, value: PaymentRequirementsV2<'x>) -> &mut Self {
+        self.accepts.push(value);
+
+        self
+    }
+
+    pub fn set_extension(
+        &mut self,
+        name: impl Into<String>,
+        value: serde_json::Value,
+    ) -> &mut Self {
+        self.extensions.insert(name.into(), value);
+
+        self
+    }
+
+    /// Encode as the base64 JSON value of the `PAYMENT-REQUIRED` header
+    pub fn to_header_value(&self) -> X402Result<String> {
+        encode_header_value(self)
+    }
+
+    /// Decode the base64 JSON value of the `PAYMENT-REQUIRED` header.
+    /// The JSON is decoded into `buffer` which the response borrows from.
+    pub fn from_header_value(value: &str, buffer: &'x mut Vec<u8>) -> X402Result<Self> {
+        decode_header_value(value, buffer, Some(X402Version::V2))
+    }
+
+    pub fn x402_version(&self) -> X402Version {
+        self.x402_version
+    }
+
+    pub fn error_reason(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub fn resource(&self) -> &PaymentResource<'x> {
+        &self.resource
+    }
+
+    pub fn accepts(&self) -> &[PaymentRequirementsV2<'x>] {
+        self.accepts.as_slice()
+    }
+
+    pub fn extensions(&self) -> &X402Extensions {
+        &self.extensions
+    }
+
+    /// The accepted payment requirements in the x402 v1 format
+    pub fn requirements(&self) -> X402Result<Vec<PaymentRequirements<'x>>> {
+        self.accepts
+            .iter()
+            .cloned()
+            .map(|accepted| accepted.into_v1(&self.resource))
+            .collect()
+    }
+
+    /// Convert into a response that owns its strings
+    pub fn into_owned(self) -> PaymentRequired<'static> {
+        PaymentRequired {
+            x402_version: self.x402_version,
+            error: self.error.map(|error| Cow::Owned(error.into_owned())),
+            resource: self.resource.into_owned(),
+            accepts: self
+                .accepts
+                .into_iter()
+                .map(PaymentRequirementsV2::into_owned)
+                .collect(),
+            extensions: self.extensions,
+        }
+    }
+}
+
+/// The body of the x402 v2 `PAYMENT-SIGNATURE` header, the payment and the
+/// requirements it accepted
+#[derive(Debug, Default, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentPayloadV2<'x> {
+    #[serde(deserialize_with = "deserialize_x402_version")]
+    #[serde(serialize_with = "serialize_x402_version")]
+    x402_version: X402Version,
+    #[serde(borrow)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    resource: Option<PaymentResource<'x>>,
+    #[serde(borrow)]
+    accepted: PaymentRequirementsV2<'x>,
+    #[serde(borrow)]
+    payload: XPaymentTransaction<'x>,
+    #[serde(default, skip_serializing_if = "X402Extensions::is_empty")]
+    extensions: X402Extensions,
+}
+
+impl<'x> PaymentPayloadV2<'x> {
+    pub fn new(accepted: PaymentRequirementsV2<'x>, transaction: impl AsRef<[u8]>) -> Self {
+        Self {
+            x402_version: X402Version::V2,
+            accepted,
+            payload: XPaymentTransaction::new(transaction),
+            ..Default::default()
+        }
+    }
+
+    pub fn set_resource(mut self, resource: PaymentResource<'x>) -> Self {
+        self.resource.replace(resource);
+
+        self
+    }
+
+    pub fn set_extension(mut self, name: impl Into<String>, value: serde_json::Value) -> Self {
+        self.extensions.insert(name.into(), value);
+
+        self
+    }
+
+    pub fn x402_version(&self) -> X402Version {
+        self.x402_version
+    }
+
+    pub fn resource(&self) -> Option<&PaymentResource<'x>> {
+        self.resource.as_ref()
+    }
+
+    pub fn accepted(&self) -> &PaymentRequirementsV2<'x> {
+        &self.accepted
+    }
+
+    pub fn transaction(&self) -> &XPaymentTransaction<'x> {
+        &self.payload
+    }
+
+    pub fn extensions(&self) -> &X402Extensions {
+        &self.extensions
+    }
+
+    /// The payment as an x402 v1 `X-PAYMENT` payload, as verified and
+    /// settled by the facilitator APIs
+    pub fn to_v1(&self) -> XPaymentPayload<'x> {
+        XPaymentPayload::from_transaction(self.payload.clone())
+            .change_x402_version(X402Version::V1)
+            .set_scheme(self.accepted.scheme)
+            .set_network(self.accepted.network)
+    }
+
+    /// Encode as the base64 JSON value of the `PAYMENT-SIGNATURE` header
+    pub fn to_header_value(&self) -> X402Result<String> {
+        encode_header_value(self)
+    }
+
+    /// Decode the base64 JSON value of the `PAYMENT-SIGNATURE` header.
+    /// The JSON is decoded into `buffer` which the payload borrows from.
+    pub fn from_header_value(value: &str, buffer: &'x mut Vec<u8>) -> X402Result<Self> {
+        decode_header_value(value, buffer, Some(X402Version::V2))
+    }
+
+    /// Convert into a payload that owns its strings
+    pub fn into_owned(self) -> PaymentPayloadV2<'static> {
+        PaymentPayloadV2 {
+            x402_version: self.x402_version,
+            resource: self.resource.map(PaymentResource::into_owned),
+            accepted: self.accepted.into_owned(),
+            payload: self.payload.into_owned(),
+            extensions: self.extensions,
+        }
+    }
+}
+
+/// A payment from the `X-PAYMENT` header of x402 v1 or the `PAYMENT-SIGNATURE`
+/// header of x402 v2, letting a resource server accept both versions on the same route.
+/// Both are verified and settled as the x402 v1 [VerifyPayload] of the facilitator APIs.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum VersionedPaymentPayload<'x> {
+    V1(XPaymentPayload<'x>),
+    V2(Box<PaymentPayloadV2<'x>>),
+}
+
+impl<'x> VersionedPaymentPayload<'x> {
+    /// Decode the value of the payment header of `version`
+    pub fn from_header_value(
+        version: X402Version,
+        value: &str,
+        buffer: &'x mut Vec<u8>,
+    ) -> X402Result<Self> {
+        match version {
+            X402Version::V1 => XPaymentPayload::from_header_value(value, buffer).map(Self::V1),
+            X402Version::V2 => PaymentPayloadV2::from_header_value(value, buffer)
+                .map(Box::new)
+                .map(Self::V2),
+        }
+    }
+
+    pub fn x402_version(&self) -> X402Version {
+        match self {
+            Self::V1(payload) => payload.x402_version(),
+            Self::V2(payload) => payload.x402_version(),
+        }
+    }
+
+    /// The payload of the `/verify` and `/settle` APIs paying for `requirements`.
+    /// A v2 payment must have accepted exactly the `requirements` otherwise
+    /// [X402Error::InvalidPaymentRequirements] is returned.
+    pub fn verify_payload(
+        &self,
+        requirements: &PaymentRequirements<'x>,
+    ) -> X402Result<VerifyPayload<'x>> {
+        let payment_payload = match self {
+            Self::V1(payload) => payload.clone(),
+            Self::V2(payload) => {
+                if payload.accepted != PaymentRequirementsV2::from(requirements) {
+                    return Err(X402Error::InvalidPaymentRequirements);
+                }
+
+                payload.to_v1()
+            }
+        };
+
+        Ok(VerifyPayload {
+            payment_payload,
+            payment_requirements: requirements.clone(),
+            settlement_amount: Option::None,
+        })
+    }
+
+    /// Convert into a payload that owns its strings
+    pub fn into_owned(self) -> VersionedPaymentPayload<'static> {
+        match self {
+            Self::V1(payload) => VersionedPaymentPayload::V1(payload.into_owned()),
+            Self::V2(payload) => VersionedPaymentPayload::V2(Box::new(payload.into_owned())),
+        }
+    }
+}
+
+fn serialize_amount<S>(amount: &u64, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_str(amount)
+}
+
+fn deserialize_amount<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(serde::de::Error::custom)
+}
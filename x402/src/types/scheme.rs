@@ -3,15 +3,19 @@ use serde::{Deserialize, Serialize};
 /// The "exact" scheme uses for solana
 /// ```
 #[derive(Debug, PartialEq, Eq, Default, PartialOrd, Ord, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum PaymentScheme {
     #[default]
     Exact,
+    /// Authorizes up to `maxAmountRequired` and settles the amount actually used
+    Upto,
 }
 
 impl PaymentScheme {
     pub fn as_str(&self) -> &str {
         match self {
             Self::Exact => "exact",
+            Self::Upto => "upto",
         }
     }
 }
@@ -3,26 +3,36 @@ use std::borrow::Cow;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    PaymentRequirements, PaymentRequirementsResponse, X402PaymentErrorStatusCode,
-    X402SolanaNetworkInfo, X402Version, deserialize_network, serialize_network,
+    Caip10AccountId, PaymentRequirements, PaymentRequirementsResponse, X402Network,
+    X402PaymentErrorStatusCode, X402Result, X402SolanaNetworkInfo, X402Timestamp, X402Version,
+    deserialize_network, deserialize_optional_timestamp, serialize_network,
 };
 
 #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SettlementResponse<'x, T: Clone> {
     /// Indicates whether the payment settlement was successful
     success: bool,
+    /// Error reason if settlement failed (omitted if successful)
+    #[serde(borrow)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error_reason: Option<Cow<'x, str>>,
     /// Blockchain transaction hash (empty string if settlement failed)
     #[serde(borrow)]
-    transaction: Option<&'x str>,
+    transaction: Option<Cow<'x, str>>,
     /// Blockchain network identifier (solana-devnet, solana-mainnet, solana-testnet, solana-localnet)
     #[serde(deserialize_with = "deserialize_network")]
     #[serde(serialize_with = "serialize_network")]
-    network: X402SolanaNetworkInfo,
+    network: X402Network,
     /// Address of the payer's wallet
     #[serde(borrow)]
-    payer: &'x str,
+    payer: Cow<'x, str>,
+    #[serde(default)]
     data: T,
-    timestamp: &'x str, // Example "2024-01-15T10:30:00Z"
+    /// Time of the settlement, e.g. `2024-01-15T10:30:00Z`
+    #[serde(default, deserialize_with = "deserialize_optional_timestamp")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timestamp: Option<X402Timestamp>,
 }
 
 impl<'x, T> SettlementResponse<'x, T>
@@ -36,8 +46,17 @@ where
         }
     }
 
-    pub fn set_transaction_signature(&mut self, transaction_signature: &'x str) -> &mut Self {
-        self.transaction.replace(transaction_signature);
+    pub fn set_transaction_signature(
+        &mut self,
+        transaction_signature: impl Into<Cow<'x, str>>,
+    ) -> &mut Self {
+        self.transaction.replace(transaction_signature.into());
+
+        self
+    }
+
+    pub fn set_error_reason(&mut self, error_reason: impl Into<Cow<'x, str>>) -> &mut Self {
+        self.error_reason.replace(error_reason.into());
 
         self
     }
@@ -48,39 +67,44 @@ where
         self
     }
 
-    // TODO use rust time formats
-    pub fn set_timestamp(&mut self, timestamp: &'x str) -> &mut Self {
-        self.timestamp = timestamp;
+    pub fn set_timestamp(&mut self, timestamp: X402Timestamp) -> &mut Self {
+        self.timestamp.replace(timestamp);
 
         self
     }
 
-    pub fn set_payer(&mut self, fee_payer: &'x str) -> &mut Self {
-        self.payer = fee_payer;
+    pub fn set_payer(&mut self, fee_payer: impl Into<Cow<'x, str>>) -> &mut Self {
+        self.payer = fee_payer.into();
+
+        self
+    }
+
+    pub fn set_network(&mut self, network: impl Into<X402Network>) -> &mut Self {
+        self.network = network.into();
 
         self
     }
 
     pub fn set_mainnet(&mut self) -> &mut Self {
-        self.network = X402SolanaNetworkInfo::Mainnet;
+        self.network = X402SolanaNetworkInfo::Mainnet.into();
 
         self
     }
 
     pub fn set_testnet(&mut self) -> &mut Self {
-        self.network = X402SolanaNetworkInfo::Testnet;
+        self.network = X402SolanaNetworkInfo::Testnet.into();
 
         self
     }
 
     pub fn set_devnet(&mut self) -> &mut Self {
-        self.network = X402SolanaNetworkInfo::Devnet;
+        self.network = X402SolanaNetworkInfo::Devnet.into();
 
         self
     }
 
     pub fn set_localnet(&mut self) -> &mut Self {
-        self.network = X402SolanaNetworkInfo::Localnet;
+        self.network = X402SolanaNetworkInfo::Localnet.into();
 
         self
     }
@@ -90,21 +114,33 @@ where
         self.success
     }
 
+    /// Error reason if settlement failed (omitted if successful)
+    pub fn error_reason(&self) -> Option<&str> {
+        self.error_reason.as_deref()
+    }
+
     /// Blockchain transaction hash (empty string if settlement failed)
     pub fn transaction(&self) -> Option<&str> {
-        self.transaction
+        self.transaction.as_deref()
     }
 
     /// Blockchain network identifier (solana-devnet, solana-mainnet, solana-testnet, solana-localnet)
-    pub fn network(&self) -> X402SolanaNetworkInfo {
+    pub fn network(&self) -> X402Network {
         self.network
     }
 
     /// Address of the payer's wallet
     pub fn payer(&self) -> &str {
-        self.payer
+        self.payer.as_ref()
     }
-    pub fn timestamp(&self) -> &str {
+
+    /// CAIP-10 account id of the payer on the settlement network
+    pub fn payer_account_id(&self) -> X402Result<Caip10AccountId<'_>> {
+        Caip10AccountId::new(self.network, self.payer.as_ref())
+    }
+
+    /// Time of the settlement
+    pub fn timestamp(&self) -> Option<X402Timestamp> {
         self.timestamp
     }
 
@@ -113,6 +149,28 @@ where
     }
 }
 
+impl<'x, T> SettlementResponse<'x, T>
+where
+    T: Clone + 'static,
+{
+    /// Convert into a response that owns its strings
+    pub fn into_owned(self) -> SettlementResponse<'static, T> {
+        SettlementResponse {
+            success: self.success,
+            error_reason: self
+                .error_reason
+                .map(|reason| Cow::Owned(reason.into_owned())),
+            transaction: self
+                .transaction
+                .map(|transaction| Cow::Owned(transaction.into_owned())),
+            network: self.network,
+            payer: Cow::Owned(self.payer.into_owned()),
+            data: self.data,
+            timestamp: self.timestamp,
+        }
+    }
+}
+
 #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub struct SettlementResponseError<'x> {
     payload: PaymentRequirementsResponse<'x>,
@@ -135,7 +193,7 @@ impl<'x> SettlementResponseError<'x> {
         self.payload
     }
 
-    pub fn set_error_reason(mut self, error: &'x str) -> Self {
+    pub fn set_error_reason(mut self, error: impl Into<Cow<'x, str>>) -> Self {
         self.payload.set_error_reason(error);
 
         self
@@ -174,4 +232,12 @@ impl<'x> SettlementResponseError<'x> {
     pub fn error_reason(&self) -> &str {
         self.payload.error_reason()
     }
+
+    /// Convert into an error that owns its strings
+    pub fn into_owned(self) -> SettlementResponseError<'static> {
+        SettlementResponseError {
+            payload: self.payload.into_owned(),
+            status_code: self.status_code,
+        }
+    }
 }
@@ -1,8 +1,8 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    PaymentScheme, X402SolanaNetworkInfo, X402Version, deserialize_network,
-    deserialize_x402_version, serialize_network, serialize_x402_version,
+    PaymentScheme, X402Error, X402Network, X402Result, X402Version, XPaymentPayload,
+    deserialize_network, deserialize_x402_version, serialize_network, serialize_x402_version,
 };
 
 #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Serialize, Deserialize)]
@@ -32,9 +32,41 @@ impl SupportedSchemes {
     pub fn kinds(&self) -> &[SchemeKind] {
         self.kinds.as_slice()
     }
+
+    /// Whether payments of `kind` are supported
+    pub fn supports(&self, kind: &SchemeKind) -> bool {
+        self.kinds.contains(kind)
+    }
+
+    /// Check that payments of `kind` are supported returning the error
+    /// describing the first mismatch otherwise
+    pub fn ensure_supported(&self, kind: &SchemeKind) -> X402Result<()> {
+        if self.supports(kind) {
+            return Ok(());
+        }
+
+        let same_version = |supported: &&SchemeKind| supported.x402_version == kind.x402_version;
+        let same_scheme = |supported: &&SchemeKind| supported.scheme == kind.scheme;
+
+        let error = if !self.kinds.iter().any(|supported| same_version(&supported)) {
+            X402Error::InvalidX402Version
+        } else if !self
+            .kinds
+            .iter()
+            .filter(same_version)
+            .any(|supported| same_scheme(&supported))
+        {
+            X402Error::UnsupportedScheme
+        } else {
+            X402Error::InvalidNetwork
+        };
+
+        Err(error)
+    }
 }
 
 #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SchemeKind {
     #[serde(deserialize_with = "deserialize_x402_version")]
     #[serde(serialize_with = "serialize_x402_version")]
@@ -42,5 +74,16 @@ pub struct SchemeKind {
     pub scheme: PaymentScheme,
     #[serde(deserialize_with = "deserialize_network")]
     #[serde(serialize_with = "serialize_network")]
-    pub network: X402SolanaNetworkInfo,
+    pub network: X402Network,
+}
+
+impl SchemeKind {
+    /// The kind of payment of an `X-PAYMENT` payload
+    pub fn of_payload(payload: &XPaymentPayload<'_>) -> Self {
+        Self {
+            x402_version: payload.x402_version(),
+            scheme: payload.scheme(),
+            network: payload.network(),
+        }
+    }
 }
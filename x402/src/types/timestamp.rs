@@ -0,0 +1,314 @@
+use core::{fmt, str::FromStr, time::Duration};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{X402Error, X402Result};
+
+/// A point in time in UTC, not earlier than the Unix epoch, formatted as an RFC 3339
+/// date and time (e.g. `2024-01-15T10:30:00Z`)
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
+pub struct X402Timestamp {
+    since_epoch: Duration,
+}
+
+impl X402Timestamp {
+    const SECONDS_PER_DAY: u64 = 86_400;
+
+    /// `1970-01-01T00:00:00Z`
+    pub const UNIX_EPOCH: Self = Self {
+        since_epoch: Duration::ZERO,
+    };
+
+    pub fn now() -> Self {
+        Self::try_from(SystemTime::now()).unwrap_or_default()
+    }
+
+    pub fn from_unix_seconds(seconds: u64) -> Self {
+        Self {
+            since_epoch: Duration::from_secs(seconds),
+        }
+    }
+
+    pub fn from_duration_since_epoch(since_epoch: Duration) -> Self {
+        Self { since_epoch }
+    }
+
+    /// Whole seconds since the Unix epoch
+    pub fn unix_seconds(&self) -> u64 {
+        self.since_epoch.as_secs()
+    }
+
+    pub fn duration_since_epoch(&self) -> Duration {
+        self.since_epoch
+    }
+
+    /// The timestamp without its fraction of a second
+    pub fn trunc_seconds(&self) -> Self {
+        Self::from_unix_seconds(self.unix_seconds())
+    }
+
+    /// Parse an RFC 3339 date and time with any UTC offset, e.g.
+    /// `2024-01-15T10:30:00Z` or `2024-01-15T12:30:00.5+02:00`
+    pub fn parse_rfc3339(value: &str) -> X402Result<Self> {
+        let bytes = value.as_bytes();
+
+        let separators = bytes.len() >= 20
+            && bytes[4] == b'-'
+            && bytes[7] == b'-'
+            && matches!(bytes[10], b'T' | b't' | b' ')
+            && bytes[13] == b':'
+            && bytes[16] == b':';
+        if !separators {
+            return Err(X402Error::InvalidTimestamp);
+        }
+
+        let year = digits(&bytes[0..4])?;
+        let month = digits(&bytes[5..7])?;
+        let day = digits(&bytes[8..10])?;
+        let hour = digits(&bytes[11..13])?;
+        let minute = digits(&bytes[14..16])?;
+        // 60 is a leap second, counted as the first second of the next minute
+        let second = digits(&bytes[17..19])?;
+
+        if !(1..=12).contains(&month)
+            || day == 0
+            || day > days_in_month(year, month)
+            || hour > 23
+            || minute > 59
+            || second > 60
+        {
+            return Err(X402Error::InvalidTimestamp);
+        }
+
+        let mut rest = &bytes[19..];
+        let mut nanos = 0u32;
+
+        if let Some(fraction) = rest.strip_prefix(b".") {
+            let length = fraction
+                .iter()
+                .take_while(|byte| byte.is_ascii_digit())
+                .count();
+            if length == 0 {
+                return Err(X402Error::InvalidTimestamp);
+            }
+
+            // Digits beyond nanoseconds are truncated
+            nanos = fraction[..length]
+                .iter()
+                .chain(core::iter::repeat(&b'0'))
+                .take(9)
+                .fold(0, |nanos, digit| nanos * 10 + u32::from(digit - b'0'));
+            rest = &fraction[length..];
+        }
+
+        let offset_seconds = match rest {
+            [b'Z' | b'z'] => 0i64,
+            [sign @ (b'+' | b'-'), offset_hour @ .., b':', m1, m2] if offset_hour.len() == 2 => {
+                let offset_hour = digits(offset_hour)?;
+                let offset_minute = digits(&[*m1, *m2])?;
+                if offset_hour > 23 || offset_minute > 59 {
+                    return Err(X402Error::InvalidTimestamp);
+                }
+
+                let offset = i64::from(offset_hour * 3_600 + offset_minute * 60);
+                if *sign == b'+' { offset } else { -offset }
+            }
+            _ => return Err(X402Error::InvalidTimestamp),
+        };
+
+        let seconds = days_from_civil(year, month, day) * Self::SECONDS_PER_DAY as i64
+            + i64::from(hour * 3_600 + minute * 60 + second)
+            - offset_seconds;
+
+        let seconds = u64::try_from(seconds).map_err(|_| X402Error::InvalidTimestamp)?;
+
+        Ok(Self {
+            since_epoch: Duration::new(seconds, nanos),
+        })
+    }
+
+    /// Format as an RFC 3339 date and time in UTC, the fraction of a second is only
+    /// included when it is not zero
+    pub fn to_rfc3339(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl TryFrom<X402Timestamp> for SystemTime {
+    type Error = X402Error;
+
+    /// Fails when the timestamp is past the latest time of the platform
+    fn try_from(timestamp: X402Timestamp) -> Result<Self, Self::Error> {
+        UNIX_EPOCH
+            .checked_add(timestamp.since_epoch)
+            .ok_or(X402Error::InvalidTimestamp)
+    }
+}
+
+impl TryFrom<SystemTime> for X402Timestamp {
+    type Error = X402Error;
+
+    fn try_from(time: SystemTime) -> Result<Self, Self::Error> {
+        time.duration_since(UNIX_EPOCH)
+            .map(Self::from_duration_since_epoch)
+            .map_err(|_| X402Error::InvalidTimestamp)
+    }
+}
+
+impl TryFrom<&str> for X402Timestamp {
+    type Error = X402Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::parse_rfc3339(value)
+    }
+}
+
+impl FromStr for X402Timestamp {
+    type Err = X402Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse_rfc3339(value)
+    }
+}
+
+impl fmt::Display for X402Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let seconds = self.since_epoch.as_secs();
+        let (year, month, day) = civil_from_days(seconds / Self::SECONDS_PER_DAY);
+        let time = seconds % Self::SECONDS_PER_DAY;
+
+        write!(
+            f,
+            "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}",
+            time / 3_600,
+            time % 3_600 / 60,
+            time % 60
+        )?;
+
+        let nanos = self.since_epoch.subsec_nanos();
+        if nanos != 0 {
+            let fraction = format!("{nanos:09}");
+            write!(f, ".{}", fraction.trim_end_matches('0'))?;
+        }
+
+        f.write_str("Z")
+    }
+}
+
+impl Serialize for X402Timestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for X402Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+
+        Self::parse_rfc3339(&value).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Deserialize an optional RFC 3339 timestamp where an empty string is no timestamp
+pub fn deserialize_optional_timestamp<'de, D>(
+    deserializer: D,
+) -> Result<Option<X402Timestamp>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = <Option<std::borrow::Cow<'de, str>>>::deserialize(deserializer)?;
+
+    value
+        .filter(|value| !value.is_empty())
+        .map(|value| X402Timestamp::parse_rfc3339(&value).map_err(serde::de::Error::custom))
+        .transpose()
+}
+
+/// Serialize a timestamp as whole seconds since the Unix epoch
+pub fn serialize_unix_timestamp<S>(
+    timestamp: &X402Timestamp,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_u64(timestamp.unix_seconds())
+}
+
+/// Deserialize a timestamp from seconds since the Unix epoch or an RFC 3339 string
+pub fn deserialize_unix_timestamp<'de, D>(deserializer: D) -> Result<X402Timestamp, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum UnixOrRfc3339<'x> {
+        Unix(u64),
+        #[serde(borrow)]
+        Rfc3339(std::borrow::Cow<'x, str>),
+    }
+
+    match UnixOrRfc3339::deserialize(deserializer)? {
+        UnixOrRfc3339::Unix(seconds) => Ok(X402Timestamp::from_unix_seconds(seconds)),
+        UnixOrRfc3339::Rfc3339(value) => {
+            X402Timestamp::parse_rfc3339(&value).map_err(serde::de::Error::custom)
+        }
+    }
+}
+
+/// Parse ASCII digits
+fn digits(bytes: &[u8]) -> X402Result<u32> {
+    bytes.iter().try_fold(0u32, |value, byte| {
+        if byte.is_ascii_digit() {
+            Ok(value * 10 + u32::from(byte - b'0'))
+        } else {
+            Err(X402Error::InvalidTimestamp)
+        }
+    })
+}
+
+fn is_leap_year(year: u32) -> bool {
+    year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since the Unix epoch of a proleptic Gregorian date (Howard Hinnant's `days_from_civil`)
+fn days_from_civil(year: u32, month: u32, day: u32) -> i64 {
+    let year = i64::from(year) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The proleptic Gregorian date of days since the Unix epoch (Howard Hinnant's `civil_from_days`)
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+
+    (year_of_era + era * 400 + u64::from(month <= 2), month, day)
+}
@@ -0,0 +1,150 @@
+use core::fmt;
+
+use crate::{X402Error, X402Result};
+
+/// An amount of a token in atomic units together with the decimals of the token,
+/// `TokenAmount::parse("0.01", 6)` is `10_000` atomic units of a 6 decimals token.
+/// Amounts are ordered by their atomic units, only compare amounts of the same token.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
+pub struct TokenAmount {
+    atomic: u64,
+    decimals: u8,
+}
+
+impl TokenAmount {
+    pub fn new(atomic: u64, decimals: u8) -> Self {
+        Self { atomic, decimals }
+    }
+
+    /// Parse a decimal amount like `"12"` or `"0.01"` into atomic units of a token
+    /// with `decimals`. More fractional digits than `decimals`, other than trailing zeros,
+    /// are rejected instead of rounded.
+    pub fn parse(value: &str, decimals: u8) -> X402Result<Self> {
+        let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+        let fraction = fraction.trim_end_matches('0');
+
+        let is_decimal = !whole.is_empty()
+            && whole.bytes().all(|byte| byte.is_ascii_digit())
+            && fraction.bytes().all(|byte| byte.is_ascii_digit())
+            && !value.ends_with('.');
+        if !is_decimal || fraction.len() > usize::from(decimals) {
+            return Err(X402Error::InvalidTokenAmount);
+        }
+
+        let atomic = whole
+            .bytes()
+            .chain(fraction.bytes())
+            .map(|digit| u64::from(digit - b'0'))
+            .chain((fraction.len()..usize::from(decimals)).map(|_| 0))
+            .try_fold(0u64, |atomic, digit| {
+                atomic.checked_mul(10)?.checked_add(digit)
+            })
+            .ok_or(X402Error::InvalidTokenAmount)?;
+
+        Ok(Self { atomic, decimals })
+    }
+
+    /// Amount in atomic token units
+    pub fn atomic(&self) -> u64 {
+        self.atomic
+    }
+
+    pub fn decimals(&self) -> u8 {
+        self.decimals
+    }
+
+    /// The sum of amounts of the same token, `None` on overflow or if the decimals differ
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.same_decimals(other)?
+            .atomic
+            .checked_add(other.atomic)
+            .map(|atomic| Self::new(atomic, self.decimals))
+    }
+
+    /// The difference of amounts of the same token, `None` on underflow or if the decimals differ
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.same_decimals(other)?
+            .atomic
+            .checked_sub(other.atomic)
+            .map(|atomic| Self::new(atomic, self.decimals))
+    }
+
+    /// The amount multiplied by `factor`, `None` on overflow
+    pub fn checked_mul(self, factor: u64) -> Option<Self> {
+        self.atomic
+            .checked_mul(factor)
+            .map(|atomic| Self::new(atomic, self.decimals))
+    }
+
+    /// The amount divided by `divisor` rounded down, `None` if `divisor` is zero
+    pub fn checked_div(self, divisor: u64) -> Option<Self> {
+        self.atomic
+            .checked_div(divisor)
+            .map(|atomic| Self::new(atomic, self.decimals))
+    }
+
+    fn same_decimals(self, other: Self) -> Option<Self> {
+        (self.decimals == other.decimals).then_some(self)
+    }
+}
+
+impl From<TokenAmount> for u64 {
+    fn from(amount: TokenAmount) -> Self {
+        amount.atomic
+    }
+}
+
+/// Formats the decimal amount without trailing zeros, `10_000` atomic units
+/// of a 6 decimals token is `0.01`
+impl fmt::Display for TokenAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let decimals = usize::from(self.decimals);
+        let digits = format!("{:0>width$}", self.atomic, width = decimals + 1);
+        let (whole, fraction) = digits.split_at(digits.len() - decimals);
+        let fraction = fraction.trim_end_matches('0');
+
+        if fraction.is_empty() {
+            f.write_str(whole)
+        } else {
+            write!(f, "{whole}.{fraction}")
+        }
+    }
+}
+
+/// The `maxAmountRequired` of [crate::PaymentRequirementsBuilder::set_amount], raw atomic
+/// units or a [TokenAmount] whose decimals must match the `decimals` of the extras
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum AmountRequired {
+    Atomic(u64),
+    Token(TokenAmount),
+}
+
+impl AmountRequired {
+    /// Amount in atomic token units
+    pub fn atomic(&self) -> u64 {
+        match self {
+            Self::Atomic(atomic) => *atomic,
+            Self::Token(amount) => amount.atomic(),
+        }
+    }
+
+    /// The decimals of the amount, only known for a [TokenAmount]
+    pub fn decimals(&self) -> Option<u8> {
+        match self {
+            Self::Atomic(_) => Option::None,
+            Self::Token(amount) => Some(amount.decimals()),
+        }
+    }
+}
+
+impl From<u64> for AmountRequired {
+    fn from(atomic: u64) -> Self {
+        Self::Atomic(atomic)
+    }
+}
+
+impl From<TokenAmount> for AmountRequired {
+    fn from(amount: TokenAmount) -> Self {
+        Self::Token(amount)
+    }
+}
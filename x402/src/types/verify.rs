@@ -2,7 +2,7 @@ use std::borrow::Cow;
 
 use serde::{Deserialize, Serialize};
 
-use crate::{PaymentRequirements, XPaymentPayload};
+use crate::{PaymentRequirements, X402Error, XPaymentPayload};
 
 #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -11,6 +11,10 @@ pub struct VerifyPayload<'x> {
     pub payment_payload: XPaymentPayload<'x>,
     #[serde(borrow)]
     pub payment_requirements: PaymentRequirements<'x>,
+    /// The amount settled of `upto` payments, at most `maxAmountRequired`. Required
+    /// to settle `upto` payments and ignored by `exact` payments.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settlement_amount: Option<u64>,
 }
 
 #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Serialize, Deserialize)]
@@ -18,5 +22,61 @@ pub struct VerifyPayload<'x> {
 pub struct VerifyPayloadResponse<'x> {
     pub is_valid: bool,
     pub invalid_reason: Option<Cow<'x, str>>,
-    pub payer: &'x str,
+    #[serde(borrow)]
+    pub payer: Cow<'x, str>,
+}
+
+impl<'x> VerifyPayload<'x> {
+    /// Convert into a payload that owns its strings
+    pub fn into_owned(self) -> VerifyPayload<'static> {
+        VerifyPayload {
+            payment_payload: self.payment_payload.into_owned(),
+            payment_requirements: self.payment_requirements.into_owned(),
+            settlement_amount: self.settlement_amount,
+        }
+    }
+}
+
+impl<'x> VerifyPayloadResponse<'x> {
+    /// The payment is valid and was authorized by `payer`
+    pub fn valid(payer: impl Into<Cow<'x, str>>) -> Self {
+        Self {
+            is_valid: true,
+            invalid_reason: Option::None,
+            payer: payer.into(),
+        }
+    }
+
+    /// The payment is invalid. The `invalid_reason` is the x402 error code of `error`,
+    /// errors without a protocol error code are reported as `unexpected_verify_error`
+    pub fn invalid(error: &X402Error, payer: impl Into<Cow<'x, str>>) -> Self {
+        let reason = error
+            .code()
+            .or(X402Error::UnexpectedVerifyError.code())
+            .map(Cow::Borrowed);
+
+        Self {
+            is_valid: false,
+            invalid_reason: reason,
+            payer: payer.into(),
+        }
+    }
+
+    /// Convert into a response that owns its strings
+    pub fn into_owned(self) -> VerifyPayloadResponse<'static> {
+        VerifyPayloadResponse {
+            is_valid: self.is_valid,
+            invalid_reason: self
+                .invalid_reason
+                .map(|reason| Cow::Owned(reason.into_owned())),
+            payer: Cow::Owned(self.payer.into_owned()),
+        }
+    }
+
+    /// The reason the payment is invalid parsed into an [X402Error]
+    pub fn invalid_reason_error(&self) -> Option<X402Error> {
+        self.invalid_reason.as_ref().map(|reason| {
+            X402Error::try_from(reason.as_ref()).unwrap_or(X402Error::UnsupportedX402Error)
+        })
+    }
 }
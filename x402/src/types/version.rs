@@ -1,6 +1,9 @@
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::X402Error;
+use crate::{
+    PAYMENT_RESPONSE_HEADER_KEY, PAYMENT_SIGNATURE_HEADER_KEY, X_PAYMENT_HEADER_KEY,
+    X_PAYMENT_RESPONSE_HEADER_KEY, X402Error,
+};
 
 #[derive(
     Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default, Serialize, Deserialize,
@@ -9,6 +12,25 @@ use crate::X402Error;
 pub enum X402Version {
     #[default]
     V1 = 1,
+    V2 = 2,
+}
+
+impl X402Version {
+    /// The request header carrying the payment of the version
+    pub fn payment_header_key(&self) -> &'static str {
+        match self {
+            Self::V1 => X_PAYMENT_HEADER_KEY,
+            Self::V2 => PAYMENT_SIGNATURE_HEADER_KEY,
+        }
+    }
+
+    /// The response header carrying the settlement of the version
+    pub fn payment_response_header_key(&self) -> &'static str {
+        match self {
+            Self::V1 => X_PAYMENT_RESPONSE_HEADER_KEY,
+            Self::V2 => PAYMENT_RESPONSE_HEADER_KEY,
+        }
+    }
 }
 
 impl TryFrom<u8> for X402Version {
@@ -17,6 +39,7 @@ impl TryFrom<u8> for X402Version {
     fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
             1 => Ok(Self::V1),
+            2 => Ok(Self::V2),
             _ => Err(X402Error::InvalidX402Version),
         }
     }
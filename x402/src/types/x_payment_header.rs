@@ -4,8 +4,9 @@ use base64ct::{Base64, Encoding};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    PaymentScheme, X402SolanaNetworkInfo, X402Version, deserialize_network,
-    deserialize_x402_version, serialize_network, serialize_x402_version,
+    PaymentScheme, X402Network, X402Result, X402SolanaNetworkInfo, X402Version,
+    decode_header_value, deserialize_network, deserialize_x402_version, encode_header_value,
+    serialize_caip2_network, serialize_network, serialize_x402_version,
 };
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize, Clone, Default)]
@@ -17,18 +18,18 @@ pub struct XPaymentPayload<'x> {
     scheme: PaymentScheme,
     #[serde(deserialize_with = "deserialize_network")]
     #[serde(serialize_with = "serialize_network")]
-    network: X402SolanaNetworkInfo,
+    network: X402Network,
     #[serde(borrow)]
     payload: XPaymentTransaction<'x>,
 }
 
 impl<'x> XPaymentPayload<'x> {
     pub fn new(transaction: impl AsRef<[u8]>) -> Self {
-        let payload = Base64::encode_string(transaction.as_ref());
-        let payload = XPaymentTransaction {
-            transaction: payload.into(),
-        };
+        Self::from_transaction(XPaymentTransaction::new(transaction))
+    }
 
+    /// A payload of an already encoded transaction
+    pub fn from_transaction(payload: XPaymentTransaction<'x>) -> Self {
         Self {
             payload,
             ..Default::default()
@@ -41,26 +42,38 @@ impl<'x> XPaymentPayload<'x> {
         self
     }
 
+    pub fn set_scheme(mut self, scheme: PaymentScheme) -> Self {
+        self.scheme = scheme;
+
+        self
+    }
+
+    pub fn set_network(mut self, network: impl Into<X402Network>) -> Self {
+        self.network = network.into();
+
+        self
+    }
+
     pub fn use_mainnet(mut self) -> Self {
-        self.network = X402SolanaNetworkInfo::Mainnet;
+        self.network = X402SolanaNetworkInfo::Mainnet.into();
 
         self
     }
 
     pub fn use_testnet(mut self) -> Self {
-        self.network = X402SolanaNetworkInfo::Testnet;
+        self.network = X402SolanaNetworkInfo::Testnet.into();
 
         self
     }
 
     pub fn use_devnet(mut self) -> Self {
-        self.network = X402SolanaNetworkInfo::Devnet;
+        self.network = X402SolanaNetworkInfo::Devnet.into();
 
         self
     }
 
     pub fn use_localnet(mut self) -> Self {
-        self.network = X402SolanaNetworkInfo::Localnet;
+        self.network = X402SolanaNetworkInfo::Localnet.into();
 
         self
     }
@@ -69,7 +82,7 @@ impl<'x> XPaymentPayload<'x> {
         self.x402_version
     }
 
-    pub fn network(&self) -> X402SolanaNetworkInfo {
+    pub fn network(&self) -> X402Network {
         self.network
     }
 
@@ -84,27 +97,90 @@ impl<'x> XPaymentPayload<'x> {
     pub fn transaction_signature(&self) -> &str {
         self.payload.transaction.as_ref()
     }
+
+    /// Encode as the base64 JSON value of the `X-PAYMENT` header
+    pub fn to_header_value(&self) -> X402Result<String> {
+        encode_header_value(self)
+    }
+
+    /// Decode the base64 JSON value of the `X-PAYMENT` header.
+    /// The JSON is decoded into `buffer` which the payload borrows from.
+    pub fn from_header_value(value: &str, buffer: &'x mut Vec<u8>) -> X402Result<Self> {
+        decode_header_value(value, buffer, Some(X402Version::V1))
+    }
+
+    /// Convert into a payload that owns its transaction
+    pub fn into_owned(self) -> XPaymentPayload<'static> {
+        XPaymentPayload {
+            x402_version: self.x402_version,
+            scheme: self.scheme,
+            network: self.network,
+            payload: self.payload.into_owned(),
+        }
+    }
 }
 
+/// The scheme specific payload of a payment, a serialized Solana transaction
+/// or the signed EIP-3009 authorization of an EVM payment
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize, Clone, Default)]
 pub struct XPaymentTransaction<'x> {
+    #[serde(default, skip_serializing_if = "str::is_empty")]
     transaction: Cow<'x, str>,
+    #[cfg(feature = "evm")]
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    evm: Option<Box<crate::ExactEvmPayload>>,
 }
 
 impl<'x> XPaymentTransaction<'x> {
+    /// Base64 encode the serialized `transaction`
+    pub fn new(transaction: impl AsRef<[u8]>) -> Self {
+        Self {
+            transaction: Base64::encode_string(transaction.as_ref()).into(),
+            #[cfg(feature = "evm")]
+            evm: Option::None,
+        }
+    }
+
+    /// The payload of an EVM payment
+    #[cfg(feature = "evm")]
+    pub fn from_evm_payload(payload: crate::ExactEvmPayload) -> Self {
+        Self {
+            evm: Some(Box::new(payload)),
+            ..Default::default()
+        }
+    }
+
+    /// The base64 serialized Solana transaction, empty for EVM payments
     pub fn transaction(&self) -> &str {
         self.transaction.as_ref()
     }
+
+    /// The signed authorization of an EVM payment
+    #[cfg(feature = "evm")]
+    pub fn evm_payload(&self) -> Option<&crate::ExactEvmPayload> {
+        self.evm.as_deref()
+    }
+
+    /// Convert into a transaction that owns its base64 string
+    pub fn into_owned(self) -> XPaymentTransaction<'static> {
+        XPaymentTransaction {
+            transaction: Cow::Owned(self.transaction.into_owned()),
+            #[cfg(feature = "evm")]
+            evm: self.evm,
+        }
+    }
 }
 
 #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Deserialize, Serialize)]
 pub struct XPaymentResponse<'x> {
     success: bool,
-    transaction: &'x str,
+    #[serde(borrow)]
+    transaction: Cow<'x, str>,
     #[serde(deserialize_with = "deserialize_network")]
     #[serde(serialize_with = "serialize_network")]
-    network: X402SolanaNetworkInfo,
-    payer: &'x str,
+    network: X402Network,
+    #[serde(borrow)]
+    payer: Cow<'x, str>,
 }
 
 impl<'x> XPaymentResponse<'x> {
@@ -124,38 +200,44 @@ impl<'x> XPaymentResponse<'x> {
         self
     }
 
+    pub fn set_network(mut self, network: impl Into<X402Network>) -> Self {
+        self.network = network.into();
+
+        self
+    }
+
     pub fn set_mainnet(mut self) -> Self {
-        self.network = X402SolanaNetworkInfo::Mainnet;
+        self.network = X402SolanaNetworkInfo::Mainnet.into();
 
         self
     }
 
     pub fn set_testnet(mut self) -> Self {
-        self.network = X402SolanaNetworkInfo::Testnet;
+        self.network = X402SolanaNetworkInfo::Testnet.into();
 
         self
     }
 
     pub fn set_devnet(mut self) -> Self {
-        self.network = X402SolanaNetworkInfo::Devnet;
+        self.network = X402SolanaNetworkInfo::Devnet.into();
 
         self
     }
 
     pub fn set_localnet(mut self) -> Self {
-        self.network = X402SolanaNetworkInfo::Localnet;
+        self.network = X402SolanaNetworkInfo::Localnet.into();
 
         self
     }
 
-    pub fn set_transaction_signature(mut self, signature: &'x str) -> Self {
-        self.transaction = signature;
+    pub fn set_transaction_signature(mut self, signature: impl Into<Cow<'x, str>>) -> Self {
+        self.transaction = signature.into();
 
         self
     }
 
-    pub fn set_payer(mut self, fee_payer: &'x str) -> Self {
-        self.payer = fee_payer;
+    pub fn set_payer(mut self, fee_payer: impl Into<Cow<'x, str>>) -> Self {
+        self.payer = fee_payer.into();
 
         self
     }
@@ -165,14 +247,55 @@ impl<'x> XPaymentResponse<'x> {
     }
 
     pub fn transaction(&self) -> &str {
-        self.transaction
+        self.transaction.as_ref()
     }
 
-    pub fn network(&self) -> X402SolanaNetworkInfo {
+    pub fn network(&self) -> X402Network {
         self.network
     }
 
     pub fn fee_payer(&self) -> &str {
-        self.payer
+        self.payer.as_ref()
     }
+
+    /// Encode as the base64 JSON value of the `X-PAYMENT-RESPONSE` header
+    pub fn to_header_value(&self) -> X402Result<String> {
+        encode_header_value(self)
+    }
+
+    /// Encode as the base64 JSON value of the x402 v2 `PAYMENT-RESPONSE` header
+    /// which identifies the network by its CAIP-2 chain id
+    pub fn to_v2_header_value(&self) -> X402Result<String> {
+        encode_header_value(&PaymentResponseV2 {
+            success: self.success,
+            transaction: &self.transaction,
+            network: self.network,
+            payer: &self.payer,
+        })
+    }
+
+    /// Decode the base64 JSON value of the `X-PAYMENT-RESPONSE` or `PAYMENT-RESPONSE` header.
+    /// The JSON is decoded into `buffer` which the response borrows from.
+    pub fn from_header_value(value: &str, buffer: &'x mut Vec<u8>) -> X402Result<Self> {
+        decode_header_value(value, buffer, None)
+    }
+
+    /// Convert into a response that owns its strings
+    pub fn into_owned(self) -> XPaymentResponse<'static> {
+        XPaymentResponse {
+            success: self.success,
+            transaction: Cow::Owned(self.transaction.into_owned()),
+            network: self.network,
+            payer: Cow::Owned(self.payer.into_owned()),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct PaymentResponseV2<'a> {
+    success: bool,
+    transaction: &'a str,
+    #[serde(serialize_with = "serialize_caip2_network")]
+    network: X402Network,
+    payer: &'a str,
 }
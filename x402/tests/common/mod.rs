@@ -0,0 +1,75 @@
+//! Fixtures shared by the integration tests
+#![allow(dead_code)]
+
+use core::time::Duration;
+
+use rusty_x402::{
+    PaymentRequestExtras, PaymentRequirements, PaymentRequirementsBuilder, PaymentScheme,
+    VerifyPayload, X402Network, XPaymentPayload,
+};
+use solana_pubkey::Pubkey;
+use solana_transaction::Transaction;
+
+/// Atomic token units required by [requirements_builder]
+pub const AMOUNT: u64 = 10_000;
+
+/// Decimals of the mints of the fixtures
+pub const DECIMALS: u8 = 6;
+
+/// Requirements paying [AMOUNT] of a new mint owned by `token_program` to a new recipient
+/// on `network`, with `fee_payer` as `extra.feePayer`. The setters of the builder override
+/// these defaults.
+pub fn requirements_builder(
+    scheme: PaymentScheme,
+    network: impl Into<X402Network>,
+    token_program: &Pubkey,
+    fee_payer: &Pubkey,
+) -> PaymentRequirementsBuilder<'static> {
+    let extra = PaymentRequestExtras::new(fee_payer.to_string()).set_decimals(DECIMALS);
+    let extra = if *token_program == spl_token_2022::id() {
+        extra.set_token_extensions_mint()
+    } else {
+        extra.set_legacy_token_mint()
+    };
+
+    let mut builder = PaymentRequirementsBuilder::new();
+    builder
+        .set_scheme(scheme)
+        .set_network(network)
+        .set_amount(AMOUNT)
+        .set_asset(Pubkey::new_unique().to_string())
+        .set_recipient(Pubkey::new_unique().to_string())
+        .set_resource("https://example.com/resource")
+        .set_description("Integration test resource")
+        .set_max_timeout_seconds(Duration::from_secs(60))
+        .set_extra(extra);
+
+    builder
+}
+
+/// The requirements of [requirements_builder]
+pub fn requirements(
+    scheme: PaymentScheme,
+    network: impl Into<X402Network>,
+    token_program: &Pubkey,
+    fee_payer: &Pubkey,
+) -> PaymentRequirements<'static> {
+    requirements_builder(scheme, network, token_program, fee_payer)
+        .build()
+        .unwrap()
+}
+
+/// The payload of the `/verify` and `/settle` APIs paying `requirements`
+/// with the serialized `transaction`
+pub fn verify_payload<'x>(
+    requirements: &PaymentRequirements<'x>,
+    transaction: &Transaction,
+) -> VerifyPayload<'x> {
+    VerifyPayload {
+        payment_payload: XPaymentPayload::new(bincode::serialize(transaction).unwrap())
+            .set_scheme(requirements.scheme())
+            .set_network(requirements.network()),
+        payment_requirements: requirements.clone(),
+        settlement_amount: None,
+    }
+}
@@ -0,0 +1,148 @@
+//! Compute budgets requested by payment transactions and the caps of the
+//! `exact` and `upto` verifiers bounding the priority fees of the fee payer
+
+mod common;
+
+use rusty_x402::{
+    ComputeBudget, ComputeBudgetCaps, ComputeBudgetStrategy, ExactSvmTransactionBuilder,
+    ExactSvmVerifier, PaymentScheme, UptoSvmTransactionBuilder, UptoSvmVerifier, X402Error,
+    X402Network, X402Result,
+};
+use solana_hash::Hash;
+use solana_keypair::Keypair;
+use solana_pubkey::Pubkey;
+
+/// Verify a payment of `scheme` requesting `compute_budget` against `caps`
+fn verify(
+    scheme: PaymentScheme,
+    compute_budget: ComputeBudget,
+    caps: ComputeBudgetCaps,
+) -> X402Result<()> {
+    let requirements = common::requirements(
+        scheme,
+        X402Network::default(),
+        &spl_token::id(),
+        &Pubkey::new_unique(),
+    );
+    let payer = Keypair::new();
+    let strategy = ComputeBudgetStrategy::Fixed(compute_budget);
+
+    let transaction = match scheme {
+        PaymentScheme::Exact => {
+            let mut builder = ExactSvmTransactionBuilder::new(&requirements);
+            builder
+                .set_recent_blockhash(Hash::new_unique())
+                .set_compute_budget(strategy);
+            builder.build(&payer)?
+        }
+        PaymentScheme::Upto => {
+            let mut builder = UptoSvmTransactionBuilder::new(&requirements);
+            builder
+                .set_recent_blockhash(Hash::new_unique())
+                .set_compute_budget(strategy);
+            builder.build(&payer)?
+        }
+    };
+    let payload = common::verify_payload(&requirements, &transaction);
+
+    match scheme {
+        PaymentScheme::Exact => ExactSvmVerifier::new(&payload)
+            .set_compute_budget_caps(caps)
+            .verify()
+            .map(|_| ()),
+        PaymentScheme::Upto => UptoSvmVerifier::new(&payload)
+            .set_compute_budget_caps(caps)
+            .verify()
+            .map(|_| ()),
+    }
+}
+
+#[test]
+fn compute_budgets_above_the_caps_are_rejected() {
+    let caps = ComputeBudgetCaps::new(100_000, 1_000);
+
+    for (compute_budget, expected) in [
+        (ComputeBudget::new(100_000, 1_000), Ok(())),
+        (ComputeBudget::new(0, 0), Ok(())),
+        (
+            ComputeBudget::new(100_001, 1_000),
+            Err(X402Error::ComputeUnitLimitExceeded),
+        ),
+        (
+            ComputeBudget::new(100_000, 1_001),
+            Err(X402Error::ComputeUnitPriceExceeded),
+        ),
+        // The limit is checked first
+        (
+            ComputeBudget::new(u32::MAX, u64::MAX),
+            Err(X402Error::ComputeUnitLimitExceeded),
+        ),
+    ] {
+        assert_eq!(caps.ensure_within(&compute_budget), expected);
+
+        for scheme in [PaymentScheme::Exact, PaymentScheme::Upto] {
+            assert_eq!(
+                verify(scheme, compute_budget, caps),
+                expected,
+                "{scheme:?} {compute_budget:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn default_caps_bound_the_priority_fee() {
+    let caps = ComputeBudgetCaps::default();
+    assert_eq!(caps.max_priority_fee(), 1_000_000);
+
+    for scheme in [PaymentScheme::Exact, PaymentScheme::Upto] {
+        let above_limit = ComputeBudget::new(caps.max_unit_limit + 1, 1);
+        let above_price = ComputeBudget::new(60_000, caps.max_unit_price + 1);
+
+        assert_eq!(
+            verify(scheme, above_limit, caps),
+            Err(X402Error::ComputeUnitLimitExceeded)
+        );
+        assert_eq!(
+            verify(scheme, above_price, caps),
+            Err(X402Error::ComputeUnitPriceExceeded)
+        );
+    }
+}
+
+#[test]
+fn priority_fees_are_rounded_up_to_the_lamport() {
+    assert_eq!(ComputeBudget::new(60_000, 1).priority_fee(), 1);
+    assert_eq!(ComputeBudget::new(1_000_000, 1).priority_fee(), 1);
+    assert_eq!(ComputeBudget::new(1_000_001, 1).priority_fee(), 2);
+    assert_eq!(ComputeBudget::new(0, u64::MAX).priority_fee(), 0);
+    assert_eq!(
+        ComputeBudget::new(u32::MAX, u64::MAX).priority_fee(),
+        u64::MAX
+    );
+}
+
+#[test]
+fn priority_fee_strategies_pay_at_most_the_max_priority_fee() {
+    for (unit_limit, max_priority_fee, unit_price) in [
+        (60_000, 6, 100),
+        // The unit price is rounded down so that the fee stays within the maximum
+        (3, 1, 333_333),
+        (60_000, 0, 0),
+        // Without compute units there is no price to pay
+        (0, 1_000, 0),
+        (1, u64::MAX, u64::MAX),
+    ] {
+        let compute_budget = ComputeBudgetStrategy::PriorityFee {
+            unit_limit,
+            max_priority_fee,
+        }
+        .compute_budget();
+
+        assert_eq!(compute_budget, ComputeBudget::new(unit_limit, unit_price));
+        assert!(compute_budget.priority_fee() <= max_priority_fee);
+    }
+
+    let fixed = ComputeBudget::new(60_000, 1);
+    assert_eq!(ComputeBudgetStrategy::Fixed(fixed).compute_budget(), fixed);
+}
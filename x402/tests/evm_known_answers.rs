@@ -0,0 +1,141 @@
+//! EIP-3009 `TransferWithAuthorization` digests and signatures checked against
+//! the values of an independent EIP-712 and secp256k1 implementation
+#![cfg(feature = "evm")]
+
+use core::time::Duration;
+
+use rusty_x402::{
+    Eip712Domain, EvmAddress, EvmSignature, ExactEvmAuthorization, ExactEvmPayload,
+    ExactEvmVerifier, PaymentRequestExtras, PaymentRequirements, PaymentRequirementsBuilder,
+    PaymentScheme, X402Error, X402EvmNetworkInfo, decode_prefixed_hex,
+};
+
+/// The address of the private key `0x4c0883a6…362318`
+const FROM: &str = "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23";
+const TO: &str = "0x209693Bc6afc0C5328bA36FaF03C514EF312287C";
+const BASE_USDC: &str = "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913";
+const VALID_AFTER: u64 = 1_700_000_000;
+const VALID_BEFORE: u64 = 1_700_003_600;
+
+/// `DOMAIN_SEPARATOR()` of USDC on Base
+const DOMAIN_SEPARATOR: &str = "0x02fa7265e7c5d81118673727957699e4d68f74cd74b7db77da710fe8a2c7834f";
+const STRUCT_HASH: &str = "0xff59e25336bd874471a2d990e2c8e3404ca16dc2bc4b06733f8a80025bc9b522";
+const DIGEST: &str = "0xaf2a382e6951530fac97b30e21e350bcf3745002e8238cc9242dc19973b53974";
+
+/// Signatures of [DIGEST] by [FROM] with either parity of the curve point
+const SIGNATURE_V27: &str = "0xe493dbf1c10d80f3581e4904930b1404cc6c13900ee0758474fa94abe8c4cd13609bd8a7f24f7c3171036ba486bd09ea607fff355347dd9b109f0a0bd0efeef61b";
+const SIGNATURE_V28: &str = "0xc6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee51c9bad1027230253ee79e51a25b85e7897a887af74f6eafd9dd5a3b7f34c86c81c";
+/// [SIGNATURE_V27] with `s` replaced by `n - s` and the parity flipped, valid for
+/// `ecrecover` but rejected by EIP-3009 tokens
+const SIGNATURE_HIGH_S: &str = "0xe493dbf1c10d80f3581e4904930b1404cc6c13900ee0758474fa94abe8c4cd139f6427580db083ce8efc945b7942f6145a2eddb15c00c2a0af335480ff46524b1a";
+
+fn authorization() -> ExactEvmAuthorization {
+    let mut nonce = [0u8; 32];
+    nonce.iter_mut().zip(0u8..).for_each(|(byte, i)| *byte = i);
+
+    ExactEvmAuthorization::new(
+        EvmAddress::parse(FROM).unwrap(),
+        EvmAddress::parse(TO).unwrap(),
+        10_000,
+        VALID_AFTER,
+        VALID_BEFORE,
+        nonce,
+    )
+}
+
+fn signature(value: &str) -> EvmSignature {
+    EvmSignature::new(decode_prefixed_hex(value).unwrap())
+}
+
+fn requirements() -> PaymentRequirements<'static> {
+    let mut builder = PaymentRequirementsBuilder::new();
+    builder
+        .set_scheme(PaymentScheme::Exact)
+        .set_network(X402EvmNetworkInfo::Base)
+        .use_usdc()
+        .set_amount(10_000)
+        .set_recipient(TO)
+        .set_resource("https://example.com/resource")
+        .set_description("Integration test resource")
+        .set_max_timeout_seconds(Duration::from_secs(60))
+        .set_extra(PaymentRequestExtras::new(TO));
+
+    builder.build().unwrap()
+}
+
+#[test]
+fn base_usdc_authorizations_have_the_reference_digest() {
+    let requirements = requirements();
+    let domain = Eip712Domain::from_requirements(&requirements).unwrap();
+    assert_eq!(
+        domain.verifying_contract(),
+        EvmAddress::parse(BASE_USDC).unwrap()
+    );
+
+    assert_eq!(
+        Some(domain.separator()),
+        decode_prefixed_hex(DOMAIN_SEPARATOR)
+    );
+    assert_eq!(
+        Some(authorization().struct_hash()),
+        decode_prefixed_hex(STRUCT_HASH)
+    );
+    assert_eq!(
+        Some(authorization().signing_hash(&domain)),
+        decode_prefixed_hex(DIGEST)
+    );
+}
+
+#[test]
+fn signers_are_recovered_from_low_s_signatures() {
+    let digest = decode_prefixed_hex(DIGEST).unwrap();
+    let from = EvmAddress::parse(FROM).unwrap();
+
+    for value in [SIGNATURE_V27, SIGNATURE_V28] {
+        let signature = signature(value);
+        assert_eq!(signature.recover(&digest), Ok(from));
+
+        // The recovery id may also be encoded as 0 or 1
+        let mut bytes = *signature.as_bytes();
+        bytes[64] -= 27;
+        assert_eq!(EvmSignature::new(bytes).recover(&digest), Ok(from));
+    }
+
+    let mut bytes = *signature(SIGNATURE_V27).as_bytes();
+    bytes[64] = 29;
+    assert_eq!(
+        EvmSignature::new(bytes).recover(&digest),
+        Err(X402Error::InvalidExactSvmPayloadSignature)
+    );
+
+    assert_eq!(
+        signature(SIGNATURE_HIGH_S).recover(&digest),
+        Err(X402Error::InvalidExactSvmPayloadSignature)
+    );
+}
+
+#[test]
+fn authorizations_are_valid_between_valid_after_and_valid_before() {
+    let requirements = requirements();
+    let payload = ExactEvmPayload::new(signature(SIGNATURE_V28), authorization());
+    let verifier = ExactEvmVerifier::new(&requirements, &payload);
+    let from = EvmAddress::parse(FROM).unwrap();
+    let last_valid = VALID_BEFORE - ExactEvmVerifier::VALID_BEFORE_MARGIN;
+
+    assert_eq!(
+        verifier.verify_at(VALID_AFTER - 1),
+        Err(X402Error::InvalidExactSvmPayloadAuthorizationValidAfter)
+    );
+    assert_eq!(verifier.verify_at(VALID_AFTER), Ok(from));
+    assert_eq!(verifier.verify_at(last_valid), Ok(from));
+    assert_eq!(
+        verifier.verify_at(last_valid + 1),
+        Err(X402Error::InvalidExactSvmPayloadAuthorizationValidBefore)
+    );
+
+    let payload = ExactEvmPayload::new(signature(SIGNATURE_HIGH_S), authorization());
+    assert_eq!(
+        ExactEvmVerifier::new(&requirements, &payload).verify_at(VALID_AFTER),
+        Err(X402Error::InvalidExactSvmPayloadSignature)
+    );
+}
@@ -0,0 +1,60 @@
+//! Replay keys of EVM payments sent in the `X-PAYMENT` header
+#![cfg(feature = "evm")]
+
+use rusty_x402::{
+    EvmAddress, EvmSignature, ExactEvmAuthorization, ExactEvmPayload, PaymentScheme, ReplayKey,
+    X402EvmNetworkInfo, XPaymentPayload, XPaymentTransaction,
+};
+
+fn evm_payload(nonce: [u8; 32]) -> ExactEvmPayload {
+    ExactEvmPayload::new(
+        EvmSignature::new([1u8; 65]),
+        ExactEvmAuthorization::new(
+            EvmAddress::new([2u8; 20]),
+            EvmAddress::new([3u8; 20]),
+            10_000,
+            0,
+            u64::MAX,
+            nonce,
+        ),
+    )
+}
+
+#[test]
+fn evm_payments_are_keyed_by_authorizer_and_nonce() {
+    let evm_payload = evm_payload([4u8; 32]);
+    let header =
+        XPaymentPayload::from_transaction(XPaymentTransaction::from_evm_payload(evm_payload))
+            .set_scheme(PaymentScheme::Exact)
+            .set_network(X402EvmNetworkInfo::BaseSepolia)
+            .to_header_value()
+            .unwrap();
+
+    let mut buffer = Vec::new();
+    let payment = XPaymentPayload::from_header_value(&header, &mut buffer).unwrap();
+    assert_eq!(payment.transaction().evm_payload(), Some(&evm_payload));
+    assert_eq!(payment.transaction().transaction(), "");
+
+    assert_eq!(
+        ReplayKey::from_payment_payload(&payment),
+        Ok(ReplayKey::Evm {
+            from: [2u8; 20],
+            nonce: [4u8; 32],
+        })
+    );
+}
+
+#[test]
+fn evm_payments_with_another_nonce_have_another_key() {
+    let first = XPaymentPayload::from_transaction(XPaymentTransaction::from_evm_payload(
+        evm_payload([4u8; 32]),
+    ));
+    let second = XPaymentPayload::from_transaction(XPaymentTransaction::from_evm_payload(
+        evm_payload([5u8; 32]),
+    ));
+
+    assert_ne!(
+        ReplayKey::from_payment_payload(&first),
+        ReplayKey::from_payment_payload(&second)
+    );
+}
@@ -0,0 +1,62 @@
+//! The creation of the recipient token account in `exact` payments
+
+mod common;
+
+use rusty_x402::{
+    ExactSvmTransactionBuilder, ExactSvmVerifier, PaymentScheme, X402Error, X402Network, X402Result,
+};
+use solana_hash::Hash;
+use solana_keypair::Keypair;
+use solana_message::Message;
+use solana_pubkey::Pubkey;
+use solana_transaction::Transaction;
+
+/// Verify the payment creating the recipient token account with the instruction `data`
+fn verify_with_create_data(data: Option<Vec<u8>>) -> X402Result<()> {
+    let fee_payer = Pubkey::new_unique();
+    let payer = Keypair::new();
+    let requirements = common::requirements(
+        PaymentScheme::Exact,
+        X402Network::default(),
+        &spl_token::id(),
+        &fee_payer,
+    );
+
+    let mut instructions = ExactSvmTransactionBuilder::new(&requirements)
+        .set_create_destination(true)
+        .instructions(&payer)?;
+    if let Some(data) = data {
+        instructions
+            .iter_mut()
+            .find(|instruction| instruction.program_id == spl_associated_token_account::id())
+            .unwrap()
+            .data = data;
+    }
+
+    let recent_blockhash = Hash::new_unique();
+    let message = Message::new_with_blockhash(&instructions, Some(&fee_payer), &recent_blockhash);
+    let mut transaction = Transaction::new_unsigned(message);
+    transaction
+        .try_partial_sign(&[&payer], recent_blockhash)
+        .unwrap();
+
+    ExactSvmVerifier::new(&common::verify_payload(&requirements, &transaction))
+        .verify()
+        .map(|_| ())
+}
+
+#[test]
+fn idempotent_creation_is_accepted() {
+    assert_eq!(verify_with_create_data(None), Ok(()));
+}
+
+#[test]
+fn non_idempotent_creation_is_rejected() {
+    // `Create` and its legacy encoding as an empty instruction data
+    for data in [vec![0], vec![]] {
+        assert_eq!(
+            verify_with_create_data(Some(data)),
+            Err(X402Error::InvalidExactSvmPayloadTransactionCreateAtaInstruction)
+        );
+    }
+}
@@ -0,0 +1,65 @@
+//! Payments verified and settled by a [FacilitatorServer]
+#![cfg(feature = "facilitator-server")]
+
+mod common;
+
+use std::sync::Arc;
+
+use rusty_x402::{
+    ExactSvmTransactionBuilder, FacilitatorServer, InMemorySolanaLedger, PaymentScheme, SchemeKind,
+    UptoSvmTransactionBuilder, X402Error, X402Network, X402Version,
+};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+
+#[tokio::test]
+async fn requirements_for_another_fee_payer_are_rejected() {
+    let ledger = Arc::new(InMemorySolanaLedger::new());
+    let other_fee_payer = Keypair::new();
+    let payer = Keypair::new();
+    let kinds = [PaymentScheme::Exact, PaymentScheme::Upto].map(|scheme| SchemeKind {
+        x402_version: X402Version::V1,
+        scheme,
+        network: X402Network::default(),
+    });
+    let facilitator = FacilitatorServer::new(&kinds, ledger.clone(), Keypair::new());
+
+    for (scheme, expected) in [
+        (
+            PaymentScheme::Exact,
+            X402Error::InvalidExactSvmPayloadTransactionFeePayerMismatch,
+        ),
+        (
+            PaymentScheme::Upto,
+            X402Error::InvalidUptoSvmPayloadTransactionFeePayerMismatch,
+        ),
+    ] {
+        let requirements = common::requirements(
+            scheme,
+            X402Network::default(),
+            &spl_token::id(),
+            &other_fee_payer.pubkey(),
+        );
+        let recent_blockhash = ledger.advance_blockhash().unwrap();
+        let transaction = match scheme {
+            PaymentScheme::Exact => {
+                let mut builder = ExactSvmTransactionBuilder::new(&requirements);
+                builder.set_recent_blockhash(recent_blockhash);
+                builder.build(&payer).unwrap()
+            }
+            PaymentScheme::Upto => {
+                let mut builder = UptoSvmTransactionBuilder::new(&requirements);
+                builder.set_recent_blockhash(recent_blockhash);
+                builder.build(&payer).unwrap()
+            }
+        };
+        let payload = common::verify_payload(&requirements, &transaction);
+
+        let response = facilitator.verify(&payload).await;
+        assert!(!response.is_valid);
+        assert_eq!(response.invalid_reason_error(), Some(expected.clone()));
+        assert_eq!(expected.status_code().status_code(), 402);
+
+        assert_eq!(facilitator.settle(&payload).await, Err(expected));
+    }
+}
@@ -0,0 +1,239 @@
+//! Adversarial payment transactions trying to make the fee payer of a facilitator
+//! spend its own funds when it co-signs them. Every attack must be rejected by the
+//! `exact` and `upto` verifiers with [X402Error::FeePayerNotAllowedInInstruction].
+
+mod common;
+
+use rusty_x402::{
+    ExactSvmTransactionBuilder, ExactSvmVerifier, PaymentRequirements, PaymentScheme,
+    UptoSvmTransactionBuilder, UptoSvmVerifier, VerifyPayload, X402Error, X402Network, X402Result,
+    associated_token_address, parse_solana_pubkey, token_program_id,
+};
+use solana_hash::Hash;
+use solana_instruction::{AccountMeta, Instruction};
+use solana_keypair::Keypair;
+use solana_message::Message;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+
+/// An attack altering the transaction built for the payment requirements by
+/// [ExactSvmTransactionBuilder] or [UptoSvmTransactionBuilder], signed by the
+/// payer when it is still a signer
+#[derive(Debug, Clone, Copy)]
+enum FeePayerAttack {
+    /// `exact`: the fee payer is the authority of the transfer from its own token account
+    TransferAuthority,
+    /// `exact`: the token account of the fee payer is the source of the transfer
+    TransferSource,
+    /// `exact`: the fee payer is an additional signer of the transfer, as for a multisig authority
+    TransferMultisigSigner,
+    /// `exact`: the transfer is replaced by the closure of the token account of the fee payer
+    CloseAccount,
+    /// `exact`: the creation of the recipient token account is replaced by a transfer
+    /// of lamports from the fee payer
+    SystemTransfer,
+    /// `exact`: the fee payer is an account of the `SetComputeUnitLimit` instruction
+    ComputeBudgetAccount,
+    /// `exact`: the fee payer is the authority of the `AdvanceNonceAccount` instruction
+    NonceAuthority,
+    /// `upto`: the fee payer approves a delegation of its own token account
+    ApproveOwner,
+    /// `upto`: the token account of the fee payer is the source of the approval
+    ApproveSource,
+    /// `upto`: the approval is replaced by a transfer from the token account of the fee payer
+    ApproveReplacedByTransfer,
+}
+
+impl FeePayerAttack {
+    const ALL: [Self; 10] = [
+        Self::TransferAuthority,
+        Self::TransferSource,
+        Self::TransferMultisigSigner,
+        Self::CloseAccount,
+        Self::SystemTransfer,
+        Self::ComputeBudgetAccount,
+        Self::NonceAuthority,
+        Self::ApproveOwner,
+        Self::ApproveSource,
+        Self::ApproveReplacedByTransfer,
+    ];
+
+    /// Lamports taken from the fee payer by [Self::SystemTransfer]
+    const DRAINED_LAMPORTS: u64 = 1_000_000_000;
+
+    fn scheme(&self) -> PaymentScheme {
+        match self {
+            Self::ApproveOwner | Self::ApproveSource | Self::ApproveReplacedByTransfer => {
+                PaymentScheme::Upto
+            }
+            _ => PaymentScheme::Exact,
+        }
+    }
+
+    fn instructions(
+        &self,
+        requirements: &PaymentRequirements<'_>,
+        payer: &Keypair,
+    ) -> X402Result<Vec<Instruction>> {
+        let extra = requirements.extra();
+        let fee_payer = parse_solana_pubkey(extra.fee_payer())?;
+        let mint = parse_solana_pubkey(requirements.asset())?;
+        let token_program = token_program_id(extra.token_extensions_mint());
+        let fee_payer_ata = associated_token_address(&fee_payer, &mint, &token_program);
+
+        let mut instructions = match self {
+            Self::SystemTransfer => ExactSvmTransactionBuilder::new(requirements)
+                .set_create_destination(true)
+                .instructions(payer)?,
+            Self::NonceAuthority => ExactSvmTransactionBuilder::new(requirements)
+                .set_durable_nonce(Pubkey::new_unique(), Hash::new_unique())
+                .instructions(payer)?,
+            _ if self.scheme() == PaymentScheme::Exact => {
+                ExactSvmTransactionBuilder::new(requirements).instructions(payer)?
+            }
+            _ => UptoSvmTransactionBuilder::new(requirements).instructions(payer)?,
+        };
+        // The transfer of `exact` and the approval of `upto` payments
+        let payment = instructions
+            .last_mut()
+            .ok_or(X402Error::InvalidTransferInstruction)?;
+
+        match self {
+            Self::TransferAuthority | Self::ApproveOwner => {
+                payment.accounts[0].pubkey = fee_payer_ata;
+                payment.accounts[3].pubkey = fee_payer;
+            }
+            Self::TransferSource | Self::ApproveSource => {
+                payment.accounts[0].pubkey = fee_payer_ata;
+            }
+            Self::TransferMultisigSigner => {
+                payment
+                    .accounts
+                    .push(AccountMeta::new_readonly(fee_payer, true));
+            }
+            Self::CloseAccount => {
+                *payment = spl_token_2022::instruction::close_account(
+                    &token_program,
+                    &fee_payer_ata,
+                    &payer.pubkey(),
+                    &fee_payer,
+                    &[],
+                )
+                .map_err(|_| X402Error::InvalidTransferInstruction)?;
+            }
+            Self::SystemTransfer => {
+                instructions[2] = solana_system_interface::instruction::transfer(
+                    &fee_payer,
+                    &payer.pubkey(),
+                    Self::DRAINED_LAMPORTS,
+                );
+            }
+            Self::ComputeBudgetAccount => {
+                instructions[0]
+                    .accounts
+                    .push(AccountMeta::new(fee_payer, false));
+            }
+            Self::NonceAuthority => {
+                instructions[0].accounts[2].pubkey = fee_payer;
+            }
+            Self::ApproveReplacedByTransfer => {
+                *payment = spl_token_2022::instruction::transfer_checked(
+                    &token_program,
+                    &fee_payer_ata,
+                    &mint,
+                    &associated_token_address(&payer.pubkey(), &mint, &token_program),
+                    &fee_payer,
+                    &[],
+                    requirements.max_amount_required(),
+                    extra.decimals(),
+                )
+                .map_err(|_| X402Error::InvalidTransferInstruction)?;
+            }
+        }
+
+        Ok(instructions)
+    }
+
+    fn payload<'x>(
+        &self,
+        requirements: &PaymentRequirements<'x>,
+        payer: &Keypair,
+    ) -> X402Result<VerifyPayload<'x>> {
+        let fee_payer = parse_solana_pubkey(requirements.extra().fee_payer())?;
+        let recent_blockhash = Hash::new_unique();
+        let message = Message::new_with_blockhash(
+            &self.instructions(requirements, payer)?,
+            Some(&fee_payer),
+            &recent_blockhash,
+        );
+        let is_signer = message.signer_keys().contains(&&payer.pubkey());
+
+        let mut transaction = Transaction::new_unsigned(message);
+        if is_signer {
+            transaction
+                .try_partial_sign(&[payer], recent_blockhash)
+                .map_err(|_| X402Error::TransactionSigningError)?;
+        }
+
+        Ok(common::verify_payload(requirements, &transaction))
+    }
+}
+
+fn requirements(scheme: PaymentScheme, fee_payer: &Pubkey) -> PaymentRequirements<'static> {
+    common::requirements(scheme, X402Network::default(), &spl_token::id(), fee_payer)
+}
+
+fn verify(payload: &VerifyPayload<'_>) -> X402Result<()> {
+    match payload.payment_requirements.scheme() {
+        PaymentScheme::Exact => ExactSvmVerifier::new(payload).verify().map(|_| ()),
+        PaymentScheme::Upto => UptoSvmVerifier::new(payload).verify().map(|_| ()),
+    }
+}
+
+#[test]
+fn every_attack_is_rejected() {
+    let fee_payer = Pubkey::new_unique();
+    let payer = Keypair::new();
+
+    for attack in FeePayerAttack::ALL {
+        let requirements = requirements(attack.scheme(), &fee_payer);
+        let payload = attack.payload(&requirements, &payer).unwrap();
+
+        assert_eq!(
+            verify(&payload),
+            Err(X402Error::FeePayerNotAllowedInInstruction),
+            "{attack:?}"
+        );
+    }
+}
+
+#[test]
+fn legitimate_payments_are_accepted() {
+    let fee_payer = Pubkey::new_unique();
+    let payer = Keypair::new();
+
+    let exact = requirements(PaymentScheme::Exact, &fee_payer);
+    let mut builder = ExactSvmTransactionBuilder::new(&exact);
+    builder.set_recent_blockhash(Hash::new_unique());
+    let transaction = builder.build(&payer).unwrap();
+    assert_eq!(
+        verify(&common::verify_payload(&exact, &transaction)),
+        Ok(())
+    );
+
+    builder
+        .set_create_destination(true)
+        .set_durable_nonce(Pubkey::new_unique(), Hash::new_unique());
+    let transaction = builder.build(&payer).unwrap();
+    assert_eq!(
+        verify(&common::verify_payload(&exact, &transaction)),
+        Ok(())
+    );
+
+    let upto = requirements(PaymentScheme::Upto, &fee_payer);
+    let mut builder = UptoSvmTransactionBuilder::new(&upto);
+    builder.set_recent_blockhash(Hash::new_unique());
+    let transaction = builder.build(&payer).unwrap();
+    assert_eq!(verify(&common::verify_payload(&upto, &transaction)), Ok(()));
+}
@@ -0,0 +1,108 @@
+//! The payments recorded by a [FileReplayGuard] in its file
+
+use std::{fs, path::PathBuf};
+
+use rusty_x402::{FileReplayGuard, ReplayGuard, ReplayKey, X402Error};
+use solana_hash::Hash;
+use solana_signature::Signature;
+
+fn key() -> ReplayKey {
+    ReplayKey::Svm {
+        signature: Signature::from([7u8; 64]),
+        blockhash: Hash::new_unique(),
+    }
+}
+
+fn path(name: &str) -> PathBuf {
+    let path =
+        std::env::temp_dir().join(format!("rusty-x402-{name}-{}.replay", std::process::id()));
+    let _ = fs::remove_file(&path);
+
+    path
+}
+
+#[test]
+fn blank_lines_are_skipped() {
+    let path = path("blank-lines");
+    let key = key();
+    fs::write(&path, format!("\n{key}\n\n")).unwrap();
+
+    let guard = FileReplayGuard::open(&path).unwrap();
+    assert_eq!(guard.len(), Ok(1));
+    assert_eq!(guard.contains(&key), Ok(true));
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn unreadable_lines_are_an_error() {
+    let path = path("unreadable-lines");
+    fs::write(&path, b"svm:valid\n\xff\xfe\n").unwrap();
+
+    assert_eq!(
+        FileReplayGuard::open(&path).err(),
+        Some(X402Error::ReplayGuardError)
+    );
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn released_payments_stay_released_after_a_restart() {
+    let path = path("released");
+    let (used, released) = (key(), key());
+
+    let guard = FileReplayGuard::open(&path).unwrap();
+    guard.record(&used).await.unwrap();
+    guard.record(&released).await.unwrap();
+    guard.release(&released).await.unwrap();
+    assert_eq!(
+        guard.record(&used).await,
+        Err(X402Error::PaymentAlreadyUsed)
+    );
+    drop(guard);
+
+    let guard = FileReplayGuard::open(&path).unwrap();
+    assert_eq!(guard.contains(&used), Ok(true));
+    assert_eq!(guard.contains(&released), Ok(false));
+    assert_eq!(guard.record(&released).await, Ok(()));
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn payments_are_forgotten_after_the_max_timeout() {
+    let path = path("forgotten");
+    let (forgotten, used) = (key(), key());
+    fs::write(&path, format!("{forgotten} 0\n")).unwrap();
+
+    let guard = FileReplayGuard::open_with_max_timeout(&path, 60).unwrap();
+    assert_eq!(guard.contains(&forgotten), Ok(false));
+    guard.record(&used).await.unwrap();
+    assert_eq!(guard.len(), Ok(1));
+    assert_eq!(guard.record(&forgotten).await, Ok(()));
+    assert_eq!(guard.len(), Ok(2));
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn obsolete_lines_are_compacted() {
+    let path = path("compacted");
+    let used = key();
+
+    let guard = FileReplayGuard::open(&path).unwrap();
+    guard.record(&used).await.unwrap();
+    for _ in 0..600 {
+        let released = key();
+        guard.record(&released).await.unwrap();
+        guard.release(&released).await.unwrap();
+    }
+
+    // The file was rewritten with the payment still used once it had 1024 obsolete lines
+    let lines = fs::read_to_string(&path).unwrap().lines().count();
+    assert_eq!(lines, 1 + 2 * (600 - 512));
+    assert_eq!(FileReplayGuard::open(&path).unwrap().len(), Ok(1));
+
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,167 @@
+//! Payments the [InMemorySolanaLedger] must refuse like a validator would
+
+mod common;
+
+use rusty_x402::{
+    ExactSvmSettler, ExactSvmTransactionBuilder, ExactSvmVerifier, InMemorySolanaLedger,
+    PaymentRequirements, PaymentScheme, SolanaRpcTransport, X402Error, X402Network,
+    associated_token_address, parse_solana_pubkey,
+};
+use solana_hash::Hash;
+use solana_instruction::{AccountMeta, Instruction};
+use solana_keypair::Keypair;
+use solana_message::Message;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+
+/// A ledger with the mint of `requirements`, `funds` in the token account of `payer`
+/// and the token account of `payTo`
+fn ledger(
+    requirements: &PaymentRequirements<'_>,
+    payer: &Keypair,
+    funds: u64,
+) -> InMemorySolanaLedger {
+    let ledger = InMemorySolanaLedger::new();
+    let mint = parse_solana_pubkey(requirements.asset()).unwrap();
+    let pay_to = parse_solana_pubkey(requirements.pay_to()).unwrap();
+    ledger
+        .add_mint(mint, common::DECIMALS, spl_token::id())
+        .unwrap();
+    ledger.mint_to(&payer.pubkey(), &mint, funds).unwrap();
+    ledger
+        .create_associated_token_account(&pay_to, &mint)
+        .unwrap();
+
+    ledger
+}
+
+fn payment(
+    requirements: &PaymentRequirements<'_>,
+    payer: &Keypair,
+    recent_blockhash: Hash,
+) -> Transaction {
+    let mut builder = ExactSvmTransactionBuilder::new(requirements);
+    builder.set_recent_blockhash(recent_blockhash);
+
+    builder.build(payer).unwrap()
+}
+
+#[tokio::test]
+async fn underfunded_payers_are_rejected() {
+    let fee_payer = Keypair::new();
+    let payer = Keypair::new();
+    let requirements = common::requirements(
+        PaymentScheme::Exact,
+        X402Network::default(),
+        &spl_token::id(),
+        &fee_payer.pubkey(),
+    );
+    let ledger = ledger(&requirements, &payer, common::AMOUNT - 1);
+    let transaction = payment(&requirements, &payer, ledger.advance_blockhash().unwrap());
+    let payload = common::verify_payload(&requirements, &transaction);
+
+    assert_eq!(
+        ExactSvmVerifier::new(&payload)
+            .verify_with_rpc(&ledger)
+            .await
+            .err(),
+        Some(X402Error::InsufficientFunds)
+    );
+
+    // Settling without verifying against the ledger fails the transfer
+    let settler = ExactSvmSettler::new(&ledger, fee_payer);
+    assert_eq!(
+        settler.settle(&payload).await,
+        Err(X402Error::InvalidTransactionState)
+    );
+    assert_eq!(ledger.processed_transactions(), Ok(Vec::new()));
+    assert_eq!(
+        ledger.balance(
+            &payer.pubkey(),
+            &parse_solana_pubkey(requirements.asset()).unwrap()
+        ),
+        Ok(Some(common::AMOUNT - 1))
+    );
+}
+
+#[tokio::test]
+async fn expired_blockhashes_are_rejected() {
+    let fee_payer = Keypair::new();
+    let payer = Keypair::new();
+    let requirements = common::requirements(
+        PaymentScheme::Exact,
+        X402Network::default(),
+        &spl_token::id(),
+        &fee_payer.pubkey(),
+    );
+    let ledger = ledger(&requirements, &payer, common::AMOUNT);
+    let recent_blockhash = ledger.advance_blockhash().unwrap();
+    let transaction = payment(&requirements, &payer, recent_blockhash);
+    let payload = common::verify_payload(&requirements, &transaction);
+
+    ledger.expire_blockhash(&recent_blockhash).unwrap();
+
+    assert_eq!(
+        ExactSvmVerifier::new(&payload)
+            .verify_with_rpc(&ledger)
+            .await
+            .err(),
+        Some(X402Error::InvalidTransactionState)
+    );
+
+    let settler = ExactSvmSettler::new(&ledger, fee_payer);
+    assert_eq!(
+        settler.settle(&payload).await,
+        Err(X402Error::InvalidTransactionState)
+    );
+    assert_eq!(ledger.processed_transactions(), Ok(Vec::new()));
+}
+
+#[tokio::test]
+async fn unsupported_instructions_fail_the_transaction() {
+    let fee_payer = Keypair::new();
+    let payer = Keypair::new();
+    let requirements = common::requirements(
+        PaymentScheme::Exact,
+        X402Network::default(),
+        &spl_token::id(),
+        &fee_payer.pubkey(),
+    );
+    let ledger = ledger(&requirements, &payer, common::AMOUNT);
+    let recent_blockhash = ledger.advance_blockhash().unwrap();
+
+    let mint = parse_solana_pubkey(requirements.asset()).unwrap();
+    let pay_to = parse_solana_pubkey(requirements.pay_to()).unwrap();
+    let transfer = spl_token::instruction::transfer_checked(
+        &spl_token::id(),
+        &associated_token_address(&payer.pubkey(), &mint, &spl_token::id()),
+        &mint,
+        &associated_token_address(&pay_to, &mint, &spl_token::id()),
+        &payer.pubkey(),
+        &[],
+        common::AMOUNT,
+        common::DECIMALS,
+    )
+    .unwrap();
+    let memo = Instruction::new_with_bytes(
+        Pubkey::new_unique(),
+        b"unsupported",
+        vec![AccountMeta::new_readonly(payer.pubkey(), true)],
+    );
+
+    // The verifiers never let such a transaction reach the settler, it is
+    // submitted directly through the transport of the settler
+    let message =
+        Message::new_with_blockhash(&[transfer, memo], Some(&payer.pubkey()), &recent_blockhash);
+    let mut transaction = Transaction::new_unsigned(message);
+    transaction.sign(&[&payer], recent_blockhash);
+
+    assert_eq!(
+        ledger.send_transaction(&transaction.into()).await,
+        Err(X402Error::InvalidTransactionState)
+    );
+    assert_eq!(ledger.processed_transactions(), Ok(Vec::new()));
+    // The transfer of the failed transaction is rolled back
+    assert_eq!(ledger.balance(&pay_to, &mint), Ok(Some(0)));
+}
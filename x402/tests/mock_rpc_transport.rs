@@ -0,0 +1,36 @@
+//! The offline [MockSolanaRpcTransport]
+
+use core::time::Duration;
+
+use rusty_x402::{Commitment, MockSolanaRpcTransport, SolanaRpcTransport};
+use solana_hash::Hash;
+use solana_keypair::Keypair;
+use solana_message::Message;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+
+#[tokio::test]
+async fn default_transports_reach_finalized_commitment() {
+    let payer = Keypair::new();
+    let recent_blockhash = Hash::new_unique();
+    let message = Message::new_with_blockhash(&[], Some(&payer.pubkey()), &recent_blockhash);
+    let mut transaction = Transaction::new_unsigned(message);
+    transaction.sign(&[&payer], recent_blockhash);
+
+    for transport in [
+        MockSolanaRpcTransport::new(),
+        MockSolanaRpcTransport::default(),
+    ] {
+        let signature = transport
+            .send_transaction(&transaction.clone().into())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            transport
+                .confirm_transaction(&signature, Commitment::Finalized, Duration::from_secs(1))
+                .await,
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,39 @@
+//! Chain ids of the networks of payment requirements
+
+use rusty_x402::{BlockchainNetwork, X402EvmNetworkInfo, X402Network, X402SolanaNetworkInfo};
+
+#[test]
+#[allow(deprecated)]
+fn caip2_identifiers_are_the_chain_ids() {
+    for network in [
+        X402Network::from(X402SolanaNetworkInfo::Mainnet),
+        X402SolanaNetworkInfo::Devnet.into(),
+        X402SolanaNetworkInfo::Localnet.into(),
+        X402EvmNetworkInfo::Base.into(),
+    ] {
+        assert_eq!(network.caip2_identifier(), network.chain());
+    }
+
+    assert_eq!(
+        X402SolanaNetworkInfo::MAINNET_CAIP2,
+        X402SolanaNetworkInfo::MAINNET_CHAIN
+    );
+    assert_eq!(
+        X402SolanaNetworkInfo::TESTNET_CAIP2,
+        X402SolanaNetworkInfo::TESTNET_CHAIN
+    );
+    assert_eq!(
+        X402SolanaNetworkInfo::DEVNET_CAIP2,
+        X402SolanaNetworkInfo::DEVNET_CHAIN
+    );
+
+    // The chain names used before CAIP-2 chain ids are still parsed
+    assert_eq!(
+        X402SolanaNetworkInfo::try_from(X402SolanaNetworkInfo::MAINNET_LEGACY_CHAIN),
+        Ok(X402SolanaNetworkInfo::Mainnet)
+    );
+    assert_eq!(
+        X402SolanaNetworkInfo::try_from(X402SolanaNetworkInfo::MAINNET_CHAIN),
+        Ok(X402SolanaNetworkInfo::Mainnet)
+    );
+}
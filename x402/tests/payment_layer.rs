@@ -0,0 +1,154 @@
+//! Payments through the [X402PaymentLayer] settled by a [FacilitatorServer]
+#![cfg(all(feature = "axum-middleware", feature = "facilitator-server"))]
+
+mod common;
+
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+};
+use rusty_x402::{
+    ExactSvmTransactionBuilder, Facilitator, FacilitatorServer, InMemorySolanaLedger,
+    PAYMENT_RESPONSE_HEADER_KEY, PAYMENT_SIGNATURE_HEADER_KEY, PaymentPayloadV2,
+    PaymentRequirements, PaymentRequirementsResponse, PaymentRequirementsV2, PaymentScheme,
+    SchemeKind, SettlementResponse, VerifyPayload, VerifyPayloadResponse, X402Error, X402Network,
+    X402PaymentLayer, X402Result, X402Version, parse_solana_pubkey,
+};
+use solana_hash::Hash;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use tower::Service;
+
+/// A facilitator failing every verification with `error`
+#[derive(Debug, Clone)]
+struct FailingFacilitator {
+    error: X402Error,
+}
+
+impl Facilitator for FailingFacilitator {
+    async fn verify(
+        &self,
+        _payload: &VerifyPayload<'_>,
+    ) -> X402Result<VerifyPayloadResponse<'static>> {
+        Err(self.error.clone())
+    }
+
+    async fn settle(
+        &self,
+        _payload: &VerifyPayload<'_>,
+    ) -> X402Result<SettlementResponse<'static, ()>> {
+        Err(self.error.clone())
+    }
+}
+
+/// A request paying `requirements` with an x402 v2 `PAYMENT-SIGNATURE` header
+fn paid_request(
+    requirements: &PaymentRequirements<'_>,
+    payer: &Keypair,
+    recent_blockhash: Hash,
+) -> Request<Body> {
+    let mut builder = ExactSvmTransactionBuilder::new(requirements);
+    builder.set_recent_blockhash(recent_blockhash);
+    let transaction = bincode::serialize(&builder.build(payer).unwrap()).unwrap();
+    let header = PaymentPayloadV2::new(PaymentRequirementsV2::from(requirements), transaction)
+        .to_header_value()
+        .unwrap();
+
+    Request::builder()
+        .uri("/")
+        .header(PAYMENT_SIGNATURE_HEADER_KEY, header)
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn v2_payments_are_settled_by_v1_facilitators() {
+    let ledger = Arc::new(InMemorySolanaLedger::new());
+    let fee_payer = Keypair::new();
+    let payer = Keypair::new();
+
+    let requirements = common::requirements(
+        PaymentScheme::Exact,
+        X402Network::default(),
+        &spl_token::id(),
+        &fee_payer.pubkey(),
+    );
+    let mint = parse_solana_pubkey(requirements.asset()).unwrap();
+    let pay_to = parse_solana_pubkey(requirements.pay_to()).unwrap();
+    ledger
+        .add_mint(mint, common::DECIMALS, spl_token::id())
+        .unwrap();
+    ledger
+        .mint_to(&payer.pubkey(), &mint, common::AMOUNT)
+        .unwrap();
+    ledger
+        .create_associated_token_account(&pay_to, &mint)
+        .unwrap();
+
+    let kind = SchemeKind {
+        x402_version: X402Version::V1,
+        scheme: PaymentScheme::Exact,
+        network: requirements.network(),
+    };
+    let facilitator = Arc::new(FacilitatorServer::new(&[kind], ledger.clone(), fee_payer));
+
+    let request = paid_request(&requirements, &payer, ledger.advance_blockhash().unwrap());
+    let mut router = Router::new()
+        .route("/", get(|| async { "paid" }))
+        .layer(X402PaymentLayer::new(facilitator, requirements.clone()));
+
+    let response = router.call(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().contains_key(PAYMENT_RESPONSE_HEADER_KEY));
+    assert_eq!(ledger.balance(&pay_to, &mint), Ok(Some(common::AMOUNT)));
+}
+
+#[tokio::test]
+async fn errors_are_reported_by_code_or_by_status() {
+    let requirements = common::requirements(
+        PaymentScheme::Exact,
+        X402Network::default(),
+        &spl_token::id(),
+        &Keypair::new().pubkey(),
+    );
+
+    for (error, status, reason) in [
+        (
+            X402Error::InsufficientFunds,
+            StatusCode::PAYMENT_REQUIRED,
+            "insufficient_funds",
+        ),
+        (
+            X402Error::InvalidSolanaPublicKey,
+            StatusCode::BAD_REQUEST,
+            "invalid_payload",
+        ),
+        (
+            X402Error::RpcRequestError,
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "unexpected_verify_error",
+        ),
+    ] {
+        let mut router =
+            Router::new()
+                .route("/", get(|| async { "paid" }))
+                .layer(X402PaymentLayer::new(
+                    FailingFacilitator { error },
+                    requirements.clone(),
+                ));
+        let request = paid_request(&requirements, &Keypair::new(), Hash::new_unique());
+
+        let response = router.call(request).await.unwrap();
+        assert_eq!(response.status(), status);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = serde_json::from_slice::<PaymentRequirementsResponse<'_>>(&body).unwrap();
+        assert_eq!(body.error_reason(), reason);
+    }
+}
@@ -0,0 +1,100 @@
+//! The payment requirements a [PaymentPolicy] is willing to pay
+#![cfg(feature = "paying-client")]
+
+mod common;
+
+use rusty_x402::{
+    PaymentPolicy, PaymentRequirements, PaymentScheme, SolanaTransferFee, X402Error,
+    X402SolanaNetworkInfo,
+};
+use solana_pubkey::Pubkey;
+
+fn requirements(
+    network: X402SolanaNetworkInfo,
+    asset: &str,
+    amount: u64,
+) -> PaymentRequirements<'static> {
+    let mut builder = common::requirements_builder(
+        PaymentScheme::Exact,
+        network,
+        &spl_token::id(),
+        &Pubkey::new_unique(),
+    );
+    builder.set_amount(amount).set_asset(asset.to_owned());
+
+    builder.build().unwrap()
+}
+
+#[test]
+fn each_asset_has_its_own_max_amount() {
+    let usdc = Pubkey::new_unique().to_string();
+    let bonk = Pubkey::new_unique().to_string();
+    let mut policy = PaymentPolicy::new();
+    policy
+        .allow_asset(X402SolanaNetworkInfo::Devnet, &usdc, 10_000)
+        .allow_asset(X402SolanaNetworkInfo::Devnet, &bonk, 1_000_000_000);
+
+    let devnet = X402SolanaNetworkInfo::Devnet;
+    assert!(policy.accepts(&requirements(devnet, &usdc, 10_000)));
+    assert!(!policy.accepts(&requirements(devnet, &usdc, 10_001)));
+    assert!(policy.accepts(&requirements(devnet, &bonk, 1_000_000_000)));
+
+    // The asset is only allowed on the network it was allowed on
+    let mainnet = X402SolanaNetworkInfo::Mainnet;
+    assert!(!policy.accepts(&requirements(mainnet, &usdc, 1)));
+    assert!(!policy.accepts(&requirements(devnet, &Pubkey::new_unique().to_string(), 1)));
+    assert!(!PaymentPolicy::new().accepts(&requirements(devnet, &usdc, 1)));
+}
+
+#[test]
+fn the_preferred_asset_is_selected_before_cheaper_amounts_of_other_assets() {
+    let usdc = Pubkey::new_unique().to_string();
+    let bonk = Pubkey::new_unique().to_string();
+    let devnet = X402SolanaNetworkInfo::Devnet;
+    let accepts = [
+        requirements(devnet, &bonk, 500),
+        requirements(devnet, &usdc, 20_000),
+        requirements(devnet, &usdc, 10_000),
+    ];
+
+    let mut policy = PaymentPolicy::new();
+    policy
+        .allow_asset(devnet, &usdc, 10_000)
+        .allow_asset(devnet, &bonk, 1_000);
+    assert_eq!(policy.select(&accepts), Ok(&accepts[2]));
+
+    // Unless the preferred asset costs more than its maximum amount
+    let mut policy = PaymentPolicy::new();
+    policy
+        .allow_asset(devnet, &usdc, 5_000)
+        .allow_asset(devnet, &bonk, 1_000);
+    assert_eq!(policy.select(&accepts), Ok(&accepts[0]));
+
+    let mut policy = PaymentPolicy::new();
+    policy.allow_asset(devnet, &usdc, 5_000);
+    assert_eq!(
+        policy.select(&accepts),
+        Err(X402Error::NoAcceptablePaymentRequirements)
+    );
+}
+
+#[test]
+fn transfer_fees_count_towards_the_max_amount() {
+    let usdc = Pubkey::new_unique().to_string();
+    let requirements = requirements(X402SolanaNetworkInfo::Devnet, &usdc, 10_000);
+    let mut policy = PaymentPolicy::new();
+    policy.allow_asset(requirements.network(), &usdc, 10_000);
+    assert!(policy.accepts(&requirements));
+
+    // 1% of the amount transferred is withheld from the recipient
+    let transfer_fee = SolanaTransferFee::new(100, u64::MAX);
+    let amount = transfer_fee
+        .calculate_pre_fee_amount(requirements.max_amount_required())
+        .unwrap();
+    assert!(amount > 10_000);
+    assert!(!policy.accepts_amount(&requirements, amount));
+
+    let mut policy = PaymentPolicy::new();
+    policy.allow_asset(requirements.network(), &usdc, amount);
+    assert!(policy.accepts_amount(&requirements, amount));
+}
@@ -0,0 +1,63 @@
+//! Exporting [PaymentReceipt]s as CSV and JSON
+
+use rusty_x402::{
+    BlockchainNetwork, PaymentReceipt, RECEIPTS_CSV_HEADER, X402SolanaNetworkInfo, X402Timestamp,
+    export_receipts_csv, export_receipts_json,
+};
+
+fn receipts() -> [PaymentReceipt; 2] {
+    [
+        PaymentReceipt::new(
+            "https://example.com/a",
+            "alice",
+            10_000,
+            "4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU",
+            X402SolanaNetworkInfo::Devnet,
+            "sig-1",
+            X402Timestamp::from_unix_seconds(1_705_314_600),
+        ),
+        PaymentReceipt::new(
+            "https://example.com/search?q=a,b&title=\"x\"",
+            "line\nbreak",
+            1,
+            "asset",
+            X402SolanaNetworkInfo::Devnet,
+            "sig-2",
+            X402Timestamp::from_unix_seconds(1_705_314_601),
+        ),
+    ]
+}
+
+#[test]
+fn csv_fields_with_separators_are_quoted() {
+    let mut csv = Vec::new();
+    export_receipts_csv(&receipts(), &mut csv).unwrap();
+
+    let network = X402SolanaNetworkInfo::Devnet.x402_identifier();
+    assert_eq!(
+        String::from_utf8(csv).unwrap(),
+        format!(
+            "{RECEIPTS_CSV_HEADER}\n\
+            2024-01-15T10:30:00Z,{network},https://example.com/a,alice,\
+            4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU,10000,sig-1\n\
+            2024-01-15T10:30:01Z,{network},\"https://example.com/search?q=a,b&title=\"\"x\"\"\",\
+            \"line\nbreak\",asset,1,sig-2\n"
+        )
+    );
+}
+
+#[test]
+fn json_exports_are_parsed_back() {
+    let mut json = Vec::new();
+    export_receipts_json(&receipts(), &mut json).unwrap();
+
+    assert_eq!(
+        serde_json::from_slice::<Vec<PaymentReceipt>>(&json).unwrap(),
+        receipts()
+    );
+
+    let value = serde_json::from_slice::<serde_json::Value>(&json).unwrap();
+    assert_eq!(value[0]["timestamp"], "2024-01-15T10:30:00Z");
+    assert_eq!(value[0]["amount"], 10_000);
+    assert_eq!(value[1]["payer"], "line\nbreak");
+}
@@ -0,0 +1,65 @@
+//! Receipts recorded by a [ReceiptRecorder] for the settlements of a facilitator
+#![cfg(any(
+    feature = "facilitator-server",
+    feature = "facilitator-client",
+    feature = "axum-middleware"
+))]
+
+use std::sync::Mutex;
+
+use rusty_x402::{
+    Facilitator, InMemoryReceiptStore, ReceiptRecorder, SettlementResponse, VerifyPayload,
+    VerifyPayloadResponse, X402Error, X402Result,
+};
+
+/// A facilitator settling every payment with `settlement`
+struct FixedFacilitator {
+    settlement: SettlementResponse<'static, ()>,
+}
+
+impl Facilitator for FixedFacilitator {
+    async fn verify(
+        &self,
+        _payload: &VerifyPayload<'_>,
+    ) -> X402Result<VerifyPayloadResponse<'static>> {
+        Err(X402Error::UnexpectedVerifyError)
+    }
+
+    async fn settle(
+        &self,
+        _payload: &VerifyPayload<'_>,
+    ) -> X402Result<SettlementResponse<'static, ()>> {
+        Ok(self.settlement.clone())
+    }
+}
+
+#[tokio::test]
+async fn receipts_that_cannot_be_recorded_are_reported() {
+    let failures = Mutex::new(Vec::new());
+    let mut settled = SettlementResponse::new(true);
+    settled.set_transaction_signature("5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnb");
+
+    // A successful settlement without a transaction signature has no receipt
+    for (settlement, recorded) in [(settled, 1), (SettlementResponse::new(true), 0)] {
+        let recorder = ReceiptRecorder::new(
+            FixedFacilitator {
+                settlement: settlement.clone(),
+            },
+            InMemoryReceiptStore::new(),
+        )
+        .with_failure_callback(|error: &X402Error, _: &VerifyPayload<'_>, _: &_| {
+            failures.lock().unwrap().push(error.clone());
+        });
+
+        assert_eq!(
+            recorder.settle(&VerifyPayload::default()).await,
+            Ok(settlement)
+        );
+        assert_eq!(recorder.store().len(), Ok(recorded));
+    }
+
+    assert_eq!(
+        failures.into_inner().unwrap(),
+        vec![X402Error::UnsettledPaymentReceipt]
+    );
+}
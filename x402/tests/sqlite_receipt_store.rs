@@ -0,0 +1,92 @@
+//! Receipts recorded and queried in a [SqliteReceiptStore]
+#![cfg(feature = "receipts-sqlite")]
+
+use rusty_x402::{
+    PaymentReceipt, ReceiptQuery, ReceiptStore, SqliteReceiptStore, X402SolanaNetworkInfo,
+    X402Timestamp,
+};
+
+fn receipt(resource: &str, payer: &str, signature: &str, unix_seconds: u64) -> PaymentReceipt {
+    PaymentReceipt::new(
+        resource,
+        payer,
+        10_000,
+        "4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU",
+        X402SolanaNetworkInfo::Devnet,
+        signature,
+        X402Timestamp::from_unix_seconds(unix_seconds),
+    )
+}
+
+async fn query(store: &SqliteReceiptStore, query: &ReceiptQuery) -> Vec<PaymentReceipt> {
+    store.query(query).await.unwrap()
+}
+
+#[tokio::test]
+async fn receipts_are_queried_by_payer_resource_and_time() {
+    let store = SqliteReceiptStore::open_in_memory().unwrap();
+    let receipts = [
+        receipt("https://example.com/a", "alice", "sig-1", 1_000),
+        receipt("https://example.com/b", "alice", "sig-2", 2_000),
+        receipt("https://example.com/a", "bob", "sig-3", 3_000),
+        receipt("https://example.com/a", "alice", "sig-4", 4_000),
+    ];
+    // Receipts are returned in the order they were settled
+    for receipt in receipts.iter().rev() {
+        store.record(receipt).await.unwrap();
+    }
+
+    assert_eq!(query(&store, &ReceiptQuery::new()).await, receipts);
+    assert_eq!(
+        store.get(X402SolanaNetworkInfo::Devnet.into(), "sig-3"),
+        Ok(Some(receipts[2].clone()))
+    );
+    assert_eq!(
+        store.get(X402SolanaNetworkInfo::Mainnet.into(), "sig-3"),
+        Ok(None)
+    );
+
+    let mut by_payer = ReceiptQuery::new();
+    by_payer.set_payer("alice");
+    assert_eq!(
+        query(&store, &by_payer).await,
+        [&receipts[0], &receipts[1], &receipts[3]].map(Clone::clone)
+    );
+
+    let mut by_resource = ReceiptQuery::new();
+    by_resource
+        .set_payer("alice")
+        .set_resource("https://example.com/a");
+    assert_eq!(
+        query(&store, &by_resource).await,
+        [&receipts[0], &receipts[3]].map(Clone::clone)
+    );
+
+    // `from` is inclusive and `until` exclusive
+    let mut by_time = ReceiptQuery::new();
+    by_time
+        .set_from(X402Timestamp::from_unix_seconds(2_000))
+        .set_until(X402Timestamp::from_unix_seconds(4_000));
+    assert_eq!(query(&store, &by_time).await, receipts[1..3]);
+
+    let mut limited = ReceiptQuery::new();
+    limited.set_limit(2);
+    assert_eq!(query(&store, &limited).await, receipts[..2]);
+    limited.set_limit(0);
+    assert_eq!(query(&store, &limited).await, []);
+}
+
+#[tokio::test]
+async fn receipts_of_the_same_settlement_are_recorded_once() {
+    let store = SqliteReceiptStore::open_in_memory().unwrap();
+    let first = receipt("https://example.com/a", "alice", "sig-1", 1_000);
+
+    store.record(&first).await.unwrap();
+    // A receipt recorded again keeps the first record of its settlement
+    store
+        .record(&receipt("https://example.com/b", "bob", "sig-1", 2_000))
+        .await
+        .unwrap();
+
+    assert_eq!(query(&store, &ReceiptQuery::new()).await, [first]);
+}
@@ -0,0 +1,111 @@
+//! Parsing and formatting [X402Timestamp]s as RFC 3339 dates and times
+
+use core::time::Duration;
+use std::time::SystemTime;
+
+use rusty_x402::{X402Error, X402Timestamp};
+
+#[test]
+fn offsets_and_fractions_are_parsed() {
+    for (value, seconds, nanos) in [
+        ("1970-01-01T00:00:00Z", 0, 0),
+        ("2024-01-15T10:30:00Z", 1_705_314_600, 0),
+        ("2024-01-15t10:30:00z", 1_705_314_600, 0),
+        ("2024-01-15 10:30:00Z", 1_705_314_600, 0),
+        ("2024-01-15T12:30:00+02:00", 1_705_314_600, 0),
+        ("2024-01-15T05:00:00-05:30", 1_705_314_600, 0),
+        ("2024-01-15T10:30:00.5Z", 1_705_314_600, 500_000_000),
+        ("2024-01-15T10:30:00.000000001Z", 1_705_314_600, 1),
+        // Digits beyond nanoseconds are truncated
+        (
+            "2024-01-15T10:30:00.1234567899Z",
+            1_705_314_600,
+            123_456_789,
+        ),
+        // A leap second is the first second of the next minute
+        ("2016-12-31T23:59:60Z", 1_483_228_800, 0),
+        // Feb 29 of leap years, including years divisible by 400
+        ("2024-02-29T00:00:00Z", 1_709_164_800, 0),
+        ("2000-02-29T00:00:00Z", 951_782_400, 0),
+    ] {
+        assert_eq!(
+            X402Timestamp::parse_rfc3339(value),
+            Ok(X402Timestamp::from_duration_since_epoch(Duration::new(
+                seconds, nanos
+            ))),
+            "{value}"
+        );
+    }
+}
+
+#[test]
+fn invalid_dates_and_times_are_rejected() {
+    for value in [
+        "",
+        "2024-01-15",
+        "2024-01-15T10:30:00",
+        "2024-01-15T10:30Z",
+        "2024/01/15T10:30:00Z",
+        "2024-01-15T10:30:00.Z",
+        "2024-01-15T10:30:00+0200",
+        "2024-01-15T10:30:00+24:00",
+        "2024-13-01T00:00:00Z",
+        "2024-00-01T00:00:00Z",
+        "2024-01-00T00:00:00Z",
+        "2024-04-31T00:00:00Z",
+        "2024-01-15T24:00:00Z",
+        "2024-01-15T10:60:00Z",
+        "2024-01-15T10:30:61Z",
+        // Feb 29 of non-leap years, including centuries not divisible by 400
+        "2023-02-29T00:00:00Z",
+        "1900-02-29T00:00:00Z",
+        // Before the Unix epoch
+        "0000-01-01T00:00:00Z",
+        "1969-12-31T23:59:59Z",
+        "1970-01-01T00:30:00+01:00",
+    ] {
+        assert_eq!(
+            X402Timestamp::parse_rfc3339(value),
+            Err(X402Error::InvalidTimestamp),
+            "{value}"
+        );
+    }
+}
+
+#[test]
+fn formatted_timestamps_are_parsed_back() {
+    for value in [
+        "1970-01-01T00:00:00Z",
+        "2000-02-29T23:59:59Z",
+        "2024-01-15T10:30:00.5Z",
+        "2038-01-19T03:14:08.000000001Z",
+        "9999-12-31T23:59:59.999999999Z",
+    ] {
+        let timestamp = X402Timestamp::parse_rfc3339(value).unwrap();
+        assert_eq!(timestamp.to_rfc3339(), value);
+        assert_eq!(
+            X402Timestamp::parse_rfc3339(&timestamp.to_rfc3339()),
+            Ok(timestamp)
+        );
+    }
+
+    // Offsets are formatted in UTC
+    assert_eq!(
+        X402Timestamp::parse_rfc3339("2024-01-15T12:30:00.250+02:00")
+            .unwrap()
+            .to_rfc3339(),
+        "2024-01-15T10:30:00.25Z"
+    );
+}
+
+#[test]
+fn timestamps_past_the_latest_system_time_are_an_error() {
+    let timestamp = X402Timestamp::from_unix_seconds(1_705_314_600);
+    let time = SystemTime::try_from(timestamp).unwrap();
+    assert_eq!(X402Timestamp::try_from(time), Ok(timestamp));
+
+    assert_eq!(
+        SystemTime::try_from(X402Timestamp::from_unix_seconds(u64::MAX)),
+        Err(X402Error::InvalidTimestamp)
+    );
+}
@@ -0,0 +1,95 @@
+//! Parsing, formatting and arithmetic of [TokenAmount]s
+
+use rusty_x402::{TokenAmount, X402Error};
+
+#[test]
+fn decimal_amounts_are_parsed_into_atomic_units() {
+    for (value, decimals, atomic) in [
+        ("0.01", 6, 10_000),
+        ("12", 6, 12_000_000),
+        ("0", 6, 0),
+        ("007.5", 2, 750),
+        ("1.000001", 6, 1_000_001),
+        // Trailing zeros beyond the decimals of the token are not significant
+        ("1.1000000000", 6, 1_100_000),
+        ("5.000", 0, 5),
+        ("18446744073709551615", 0, u64::MAX),
+        ("18446744073709.551615", 6, u64::MAX),
+    ] {
+        assert_eq!(
+            TokenAmount::parse(value, decimals),
+            Ok(TokenAmount::new(atomic, decimals)),
+            "{value}"
+        );
+    }
+}
+
+#[test]
+fn malformed_amounts_are_rejected() {
+    for (value, decimals) in [
+        ("", 6),
+        (".", 6),
+        ("1.", 6),
+        (".5", 6),
+        ("1.2.3", 6),
+        ("+1", 6),
+        ("-1", 6),
+        (" 1", 6),
+        ("1e6", 6),
+        ("0x10", 6),
+        // More significant fractional digits than the decimals of the token
+        ("0.0000001", 6),
+        ("1.5", 0),
+        // Overflows of u64 atomic units
+        ("18446744073709551616", 0),
+        ("18446744073709.551616", 6),
+        ("1", 20),
+    ] {
+        assert_eq!(
+            TokenAmount::parse(value, decimals),
+            Err(X402Error::InvalidTokenAmount),
+            "{value}"
+        );
+    }
+}
+
+#[test]
+fn formatted_amounts_are_parsed_back() {
+    for (atomic, decimals, formatted) in [
+        (10_000, 6, "0.01"),
+        (12_000_000, 6, "12"),
+        (0, 6, "0"),
+        (1, 6, "0.000001"),
+        (1_000_001, 6, "1.000001"),
+        (5, 0, "5"),
+        (u64::MAX, 6, "18446744073709.551615"),
+        (u64::MAX, 19, "1.8446744073709551615"),
+    ] {
+        let amount = TokenAmount::new(atomic, decimals);
+        assert_eq!(amount.to_string(), formatted);
+        assert_eq!(TokenAmount::parse(formatted, decimals), Ok(amount));
+    }
+}
+
+#[test]
+fn only_amounts_of_the_same_decimals_are_added_or_subtracted() {
+    let cent = TokenAmount::new(10_000, 6);
+    let dollar = TokenAmount::new(1_000_000, 6);
+
+    assert_eq!(
+        dollar.checked_add(cent),
+        Some(TokenAmount::new(1_010_000, 6))
+    );
+    assert_eq!(dollar.checked_sub(cent), Some(TokenAmount::new(990_000, 6)));
+    assert_eq!(cent.checked_sub(dollar), None);
+    assert_eq!(TokenAmount::new(u64::MAX, 6).checked_add(cent), None);
+
+    let other_token = TokenAmount::new(10_000, 9);
+    assert_eq!(dollar.checked_add(other_token), None);
+    assert_eq!(dollar.checked_sub(other_token), None);
+
+    assert_eq!(cent.checked_mul(3), Some(TokenAmount::new(30_000, 6)));
+    assert_eq!(dollar.checked_mul(u64::MAX), None);
+    assert_eq!(cent.checked_div(3), Some(TokenAmount::new(3_333, 6)));
+    assert_eq!(cent.checked_div(0), None);
+}
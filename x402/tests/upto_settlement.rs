@@ -0,0 +1,151 @@
+//! Settlement of `upto` payments against an [InMemorySolanaLedger]
+
+mod common;
+
+use rusty_x402::{
+    InMemorySolanaLedger, PaymentRequirements, PaymentScheme, UptoSvmSettler,
+    UptoSvmTransactionBuilder, VerifyPayload, X402Error, X402Network,
+};
+use solana_keypair::Keypair;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+
+const CAP: u64 = common::AMOUNT;
+
+struct Setup {
+    ledger: InMemorySolanaLedger,
+    fee_payer: Keypair,
+    payer: Keypair,
+    mint: Pubkey,
+    pay_to: Pubkey,
+    requirements: PaymentRequirements<'static>,
+}
+
+impl Setup {
+    fn new(token_program: Pubkey) -> Self {
+        let ledger = InMemorySolanaLedger::new();
+        let fee_payer = Keypair::new();
+        let payer = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let pay_to = Pubkey::new_unique();
+
+        ledger
+            .add_mint(mint, common::DECIMALS, token_program)
+            .unwrap();
+        ledger.mint_to(&payer.pubkey(), &mint, 100_000).unwrap();
+        ledger
+            .create_associated_token_account(&pay_to, &mint)
+            .unwrap();
+
+        let mut builder = common::requirements_builder(
+            PaymentScheme::Upto,
+            X402Network::default(),
+            &token_program,
+            &fee_payer.pubkey(),
+        );
+        builder
+            .set_asset(mint.to_string())
+            .set_recipient(pay_to.to_string());
+
+        Self {
+            ledger,
+            fee_payer,
+            payer,
+            mint,
+            pay_to,
+            requirements: builder.build().unwrap(),
+        }
+    }
+
+    fn payload(&self, settlement_amount: Option<u64>) -> VerifyPayload<'static> {
+        let mut builder = UptoSvmTransactionBuilder::new(&self.requirements);
+        builder.set_recent_blockhash(self.ledger.advance_blockhash().unwrap());
+
+        VerifyPayload {
+            payment_payload: builder.build_payload(&self.payer).unwrap(),
+            payment_requirements: self.requirements.clone(),
+            settlement_amount,
+        }
+    }
+
+    fn settler(&self) -> UptoSvmSettler<&InMemorySolanaLedger, &Keypair> {
+        UptoSvmSettler::new(&self.ledger, &self.fee_payer)
+    }
+}
+
+#[tokio::test]
+async fn the_unused_cap_is_revoked_for_token_2022_mints() {
+    let setup = Setup::new(spl_token_2022::id());
+
+    setup
+        .settler()
+        .settle(&setup.payload(Some(4_000)))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        setup.ledger.balance(&setup.pay_to, &setup.mint),
+        Ok(Some(4_000))
+    );
+    assert_eq!(
+        setup.ledger.delegation(&setup.payer.pubkey(), &setup.mint),
+        Ok(None)
+    );
+}
+
+#[tokio::test]
+async fn the_unused_cap_stays_delegated_for_token_program_mints() {
+    let setup = Setup::new(spl_token::id());
+
+    setup
+        .settler()
+        .settle(&setup.payload(Some(4_000)))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        setup.ledger.balance(&setup.pay_to, &setup.mint),
+        Ok(Some(4_000))
+    );
+    assert_eq!(
+        setup.ledger.delegation(&setup.payer.pubkey(), &setup.mint),
+        Ok(Some((setup.fee_payer.pubkey(), CAP - 4_000)))
+    );
+}
+
+#[tokio::test]
+async fn settling_the_whole_cap_ends_the_delegation() {
+    let setup = Setup::new(spl_token_2022::id());
+
+    setup
+        .settler()
+        .settle(&setup.payload(Some(CAP)))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        setup.ledger.balance(&setup.pay_to, &setup.mint),
+        Ok(Some(CAP))
+    );
+    assert_eq!(
+        setup.ledger.delegation(&setup.payer.pubkey(), &setup.mint),
+        Ok(None)
+    );
+}
+
+#[tokio::test]
+async fn the_settlement_amount_is_required() {
+    let setup = Setup::new(spl_token_2022::id());
+
+    for settlement_amount in [None, Some(CAP + 1)] {
+        assert_eq!(
+            setup
+                .settler()
+                .settle(&setup.payload(settlement_amount))
+                .await
+                .err(),
+            Some(X402Error::InvalidUptoSvmSettlementAmount)
+        );
+    }
+    assert_eq!(setup.ledger.processed_transactions(), Ok(Vec::new()));
+}